@@ -1,5 +1,11 @@
 use std::fmt::Display;
 
+use nalgebra::Matrix3;
+
+use crate::{hall_symbols::SymmetryElement, SeitzMatrix};
+
+use super::SpaceGroupHallSymbol;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub enum CrystalSystem {
     #[default]
@@ -18,3 +24,221 @@ impl Display for CrystalSystem {
         write!(f, "{}", name)
     }
 }
+
+impl CrystalSystem {
+    /// Crystal system of the space group with the given number (1-230)
+    pub fn from_space_group_number(number: u8) -> Option<Self> {
+        let system = match number {
+            1..=2 => CrystalSystem::Triclinic,
+            3..=15 => CrystalSystem::Monoclinic,
+            16..=74 => CrystalSystem::Orthorhombic,
+            75..=142 => CrystalSystem::Tetragonal,
+            143..=167 => CrystalSystem::Trigonal,
+            168..=194 => CrystalSystem::Hexagonal,
+            195..=230 => CrystalSystem::Cubic,
+            _ => return None,
+        };
+        Some(system)
+    }
+
+    pub fn crystal_family(&self) -> CrystalFamily {
+        match self {
+            CrystalSystem::Triclinic => CrystalFamily::Triclinic,
+            CrystalSystem::Monoclinic => CrystalFamily::Monoclinic,
+            CrystalSystem::Orthorhombic => CrystalFamily::Orthorhombic,
+            CrystalSystem::Tetragonal => CrystalFamily::Tetragonal,
+            CrystalSystem::Trigonal | CrystalSystem::Hexagonal => CrystalFamily::Hexagonal,
+            CrystalSystem::Cubic => CrystalFamily::Cubic,
+        }
+    }
+
+    /// Crystal system of a group from the rotation parts of its operations
+    pub(crate) fn from_rotations(rotations: &[Matrix3<i32>]) -> Self {
+        let orders: Vec<i32> = rotations.iter().map(proper_rotation_order).collect();
+        let count = |n: i32| orders.iter().filter(|&&order| order == n).count();
+        if count(3) >= 8 {
+            CrystalSystem::Cubic
+        } else if count(6) > 0 {
+            CrystalSystem::Hexagonal
+        } else if count(3) > 0 {
+            CrystalSystem::Trigonal
+        } else if count(4) > 0 {
+            CrystalSystem::Tetragonal
+        } else if count(2) >= 3 {
+            CrystalSystem::Orthorhombic
+        } else if count(2) > 0 {
+            CrystalSystem::Monoclinic
+        } else {
+            CrystalSystem::Triclinic
+        }
+    }
+}
+
+/// Order of the proper rotation `det(W) W`
+pub(crate) fn proper_rotation_order(rotation: &Matrix3<i32>) -> i32 {
+    SeitzMatrix::new(rotation.to_homogeneous()).equiv_num() as i32
+}
+
+/// Crystal families, where the trigonal and hexagonal crystal systems
+/// form the hexagonal family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum CrystalFamily {
+    #[default]
+    Triclinic,
+    Monoclinic,
+    Orthorhombic,
+    Tetragonal,
+    Hexagonal,
+    Cubic,
+}
+
+impl Display for CrystalFamily {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = format!("{:?}", self).to_lowercase();
+        write!(f, "{}", name)
+    }
+}
+
+/// Lattice systems, which split the trigonal groups by their rhombohedral
+/// or hexagonal lattices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum LatticeSystem {
+    #[default]
+    Triclinic,
+    Monoclinic,
+    Orthorhombic,
+    Tetragonal,
+    Rhombohedral,
+    Hexagonal,
+    Cubic,
+}
+
+impl Display for LatticeSystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = format!("{:?}", self).to_lowercase();
+        write!(f, "{}", name)
+    }
+}
+
+/// Space groups with a rhombohedral lattice
+const RHOMBOHEDRAL_SPACE_GROUPS: [u8; 7] = [146, 148, 155, 160, 161, 166, 167];
+
+impl LatticeSystem {
+    /// Lattice system of the space group with the given number (1-230)
+    pub fn from_space_group_number(number: u8) -> Option<Self> {
+        let system = match CrystalSystem::from_space_group_number(number)? {
+            CrystalSystem::Triclinic => LatticeSystem::Triclinic,
+            CrystalSystem::Monoclinic => LatticeSystem::Monoclinic,
+            CrystalSystem::Orthorhombic => LatticeSystem::Orthorhombic,
+            CrystalSystem::Tetragonal => LatticeSystem::Tetragonal,
+            CrystalSystem::Trigonal if RHOMBOHEDRAL_SPACE_GROUPS.contains(&number) => {
+                LatticeSystem::Rhombohedral
+            }
+            CrystalSystem::Trigonal | CrystalSystem::Hexagonal => LatticeSystem::Hexagonal,
+            CrystalSystem::Cubic => LatticeSystem::Cubic,
+        };
+        Some(system)
+    }
+}
+
+/// The 14 Bravais lattice types in the Pearson notation, with the one-face
+/// centred lattices written as `C` whatever the face is in the setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[allow(non_camel_case_types)]
+pub enum BravaisLattice {
+    aP,
+    mP,
+    mC,
+    oP,
+    oC,
+    oI,
+    oF,
+    tP,
+    tI,
+    hP,
+    hR,
+    cP,
+    cI,
+    cF,
+}
+
+impl Display for BravaisLattice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl SpaceGroupHallSymbol {
+    /// Space group number (1-230) of the setting
+    pub fn space_group_number(&self) -> u8 {
+        self.entry().number()
+    }
+
+    pub fn crystal_system(&self) -> CrystalSystem {
+        CrystalSystem::from_space_group_number(self.space_group_number())
+            .expect("Tabulated space group numbers are within 1-230")
+    }
+
+    pub fn crystal_family(&self) -> CrystalFamily {
+        self.crystal_system().crystal_family()
+    }
+
+    pub fn lattice_system(&self) -> LatticeSystem {
+        LatticeSystem::from_space_group_number(self.space_group_number())
+            .expect("Tabulated space group numbers are within 1-230")
+    }
+
+    /// Bravais lattice type of the space group, independent of the setting,
+    /// e.g. `mC` for both `C 1 2/c 1` and `I 1 2/a 1`.
+    pub fn bravais_lattice(&self) -> BravaisLattice {
+        let hall_symbol = self.get_hall_symbol();
+        let centring = hall_symbol.trim_start_matches('-').chars().next();
+        match (self.lattice_system(), centring) {
+            (LatticeSystem::Triclinic, _) => BravaisLattice::aP,
+            (LatticeSystem::Monoclinic, Some('P')) => BravaisLattice::mP,
+            (LatticeSystem::Monoclinic, _) => BravaisLattice::mC,
+            (LatticeSystem::Orthorhombic, Some('P')) => BravaisLattice::oP,
+            (LatticeSystem::Orthorhombic, Some('I')) => BravaisLattice::oI,
+            (LatticeSystem::Orthorhombic, Some('F')) => BravaisLattice::oF,
+            (LatticeSystem::Orthorhombic, _) => BravaisLattice::oC,
+            (LatticeSystem::Tetragonal, Some('P')) => BravaisLattice::tP,
+            (LatticeSystem::Tetragonal, _) => BravaisLattice::tI,
+            (LatticeSystem::Rhombohedral, _) => BravaisLattice::hR,
+            (LatticeSystem::Hexagonal, _) => BravaisLattice::hP,
+            (LatticeSystem::Cubic, Some('P')) => BravaisLattice::cP,
+            (LatticeSystem::Cubic, Some('I')) => BravaisLattice::cI,
+            (LatticeSystem::Cubic, _) => BravaisLattice::cF,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BravaisLattice, CrystalSystem, LatticeSystem};
+    use crate::database::SpaceGroupHallSymbol;
+
+    #[test]
+    fn lattice_of_space_groups() {
+        let lookup = |hm: &str| SpaceGroupHallSymbol::try_from_hm(hm).unwrap();
+        assert_eq!(BravaisLattice::mC, lookup("I 1 2/a 1").bravais_lattice());
+        assert_eq!(BravaisLattice::oC, lookup("A m a 2").bravais_lattice());
+        assert_eq!(BravaisLattice::oF, lookup("F d d d:2").bravais_lattice());
+        assert_eq!(BravaisLattice::hR, lookup("R -3 m:R").bravais_lattice());
+        assert_eq!(BravaisLattice::hR, lookup("R -3 m:H").bravais_lattice());
+        assert_eq!("hP", format!("{}", lookup("P -3 m 1").bravais_lattice()));
+        assert_eq!(
+            LatticeSystem::Rhombohedral,
+            lookup("R 3 c").lattice_system()
+        );
+        assert_eq!(CrystalSystem::Trigonal, lookup("R 3 c").crystal_system());
+        assert_eq!("hexagonal", format!("{}", lookup("R 3 c").crystal_family()));
+        // Every setting agrees with its point group, and all 14 types appear
+        let mut found: Vec<BravaisLattice> = SpaceGroupHallSymbol::all()
+            .inspect(|s| assert_eq!(s.point_group().crystal_system(), s.crystal_system()))
+            .map(|s| s.bravais_lattice())
+            .collect();
+        found.sort();
+        found.dedup();
+        assert_eq!(14, found.len());
+    }
+}
@@ -0,0 +1,281 @@
+use std::{fmt::Display, sync::OnceLock};
+
+use super::{
+    CrystalSystem, LookUpSpaceGroup, SpaceGroupHallSymbol, DEFAULT_SPACE_GROUP_SYMBOLS,
+    FULL_SPACE_GROUP_SYMBOLS,
+};
+
+/// Unique axis of a monoclinic setting. The negative axes denote the settings
+/// with the unique axis reversed and the other two axes interchanged, e.g. `-b`
+/// for `c -b a`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UniqueAxis {
+    A,
+    B,
+    C,
+    MinusA,
+    MinusB,
+    MinusC,
+}
+
+/// Setting of the orthorhombic axes relative to the standard `abc`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OrthorhombicAxes {
+    Abc,
+    BaMinusC,
+    Cab,
+    MinusCba,
+    Bca,
+    AMinusCb,
+}
+
+/// Setting of the axes of a space group
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Setting {
+    /// The only setting of the axes tabulated for the group
+    Standard,
+    Monoclinic(UniqueAxis),
+    Orthorhombic(OrthorhombicAxes),
+    /// Hexagonal axes of a rhombohedral group
+    HexagonalAxes,
+    /// Rhombohedral axes of a rhombohedral group
+    RhombohedralAxes,
+}
+
+/// Cell choice of the monoclinic centred and glide-plane groups
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CellChoice {
+    One,
+    Two,
+    Three,
+}
+
+/// Origin choice of the groups with two tabulated origins
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OriginChoice {
+    One,
+    Two,
+}
+
+/// A row of `FULL_SPACE_GROUP_SYMBOLS` with the number code parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpaceGroupEntry {
+    symbol: SpaceGroupHallSymbol,
+    number: u8,
+    setting: Setting,
+    cell_choice: Option<CellChoice>,
+    origin_choice: Option<OriginChoice>,
+    hm_symbol: &'static str,
+    hall_symbol: &'static str,
+    schoenflies_symbol: &'static str,
+    default_setting: SpaceGroupHallSymbol,
+}
+
+impl SpaceGroupEntry {
+    pub fn symbol(&self) -> SpaceGroupHallSymbol {
+        self.symbol
+    }
+
+    /// Space group number, 1-230
+    pub fn number(&self) -> u8 {
+        self.number
+    }
+
+    pub fn setting(&self) -> Setting {
+        self.setting
+    }
+
+    pub fn cell_choice(&self) -> Option<CellChoice> {
+        self.cell_choice
+    }
+
+    pub fn origin_choice(&self) -> Option<OriginChoice> {
+        self.origin_choice
+    }
+
+    /// Full Hermann-Mauguin symbol with the setting suffix, e.g. `R -3 m:H`
+    pub fn hm_symbol(&self) -> &'static str {
+        self.hm_symbol
+    }
+
+    pub fn hall_symbol(&self) -> &'static str {
+        self.hall_symbol
+    }
+
+    /// Schoenflies symbol like `C2h^5`
+    pub fn schoenflies_symbol(&self) -> &'static str {
+        self.schoenflies_symbol
+    }
+
+    /// The setting of the group listed in `DEFAULT_SPACE_GROUP_SYMBOLS`
+    pub fn default_setting(&self) -> SpaceGroupHallSymbol {
+        self.default_setting
+    }
+
+    pub fn is_default_setting(&self) -> bool {
+        self.symbol == self.default_setting
+    }
+
+    /// Iterate over all the 530 entries, in the order of `FULL_SPACE_GROUP_SYMBOLS`
+    pub fn all() -> impl Iterator<Item = &'static SpaceGroupEntry> {
+        entries().iter()
+    }
+
+    /// Iterate over the tabulated settings of the space group with the given number
+    pub fn settings_of(number: u8) -> impl Iterator<Item = &'static SpaceGroupEntry> {
+        entries().iter().filter(move |entry| entry.number == number)
+    }
+}
+
+impl Display for SpaceGroupEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.hm_symbol, self.hall_symbol)
+    }
+}
+
+impl SpaceGroupHallSymbol {
+    /// The parsed row of the table for this setting
+    pub fn entry(&self) -> &'static SpaceGroupEntry {
+        &entries()[*self as usize]
+    }
+}
+
+fn entries() -> &'static [SpaceGroupEntry] {
+    static ENTRIES: OnceLock<Vec<SpaceGroupEntry>> = OnceLock::new();
+    ENTRIES.get_or_init(|| {
+        let default_codes = DEFAULT_SPACE_GROUP_SYMBOLS.first().unwrap();
+        let codes = FULL_SPACE_GROUP_SYMBOLS.first().unwrap();
+        SpaceGroupHallSymbol::all()
+            .enumerate()
+            .map(|(i, symbol)| {
+                let code = codes[i];
+                let (number, choice) = code.split_once(':').unwrap_or((code, ""));
+                let number: u8 = number
+                    .parse()
+                    .expect("Tabulated number codes start with the space group number");
+                let (setting, cell_choice, origin_choice) =
+                    parse_choice(number, choice).expect("Valid tabulated setting code");
+                let default_code = default_codes[number as usize - 1];
+                let default_index = codes
+                    .iter()
+                    .position(|&c| c == default_code)
+                    .expect("Default settings are in the full table");
+                SpaceGroupEntry {
+                    symbol,
+                    number,
+                    setting,
+                    cell_choice,
+                    origin_choice,
+                    hm_symbol: FULL_SPACE_GROUP_SYMBOLS
+                        .get_hm_full_notation(i)
+                        .unwrap()
+                        .trim(),
+                    hall_symbol: FULL_SPACE_GROUP_SYMBOLS.get_hall_symbol(i).unwrap().trim(),
+                    schoenflies_symbol: FULL_SPACE_GROUP_SYMBOLS.get_schoenflies_symbol(i).unwrap(),
+                    default_setting: SpaceGroupHallSymbol::from_index(default_index).unwrap(),
+                }
+            })
+            .collect()
+    })
+}
+
+/// Parse the part after `:` of the number codes, e.g. `-b1`, `2cab`, `H`
+fn parse_choice(
+    number: u8,
+    choice: &str,
+) -> Option<(Setting, Option<CellChoice>, Option<OriginChoice>)> {
+    let system = CrystalSystem::from_space_group_number(number)?;
+    match (system, choice) {
+        (_, "H") => Some((Setting::HexagonalAxes, None, None)),
+        (_, "R") => Some((Setting::RhombohedralAxes, None, None)),
+        (CrystalSystem::Monoclinic, _) => {
+            let (axis, reversed) = match choice.strip_prefix('-') {
+                Some(rest) => (rest, true),
+                None => (choice, false),
+            };
+            let mut chars = axis.chars();
+            let unique_axis = match (chars.next()?, reversed) {
+                ('a', false) => UniqueAxis::A,
+                ('b', false) => UniqueAxis::B,
+                ('c', false) => UniqueAxis::C,
+                ('a', true) => UniqueAxis::MinusA,
+                ('b', true) => UniqueAxis::MinusB,
+                ('c', true) => UniqueAxis::MinusC,
+                _ => return None,
+            };
+            let cell_choice = match chars.as_str() {
+                "" => None,
+                "1" => Some(CellChoice::One),
+                "2" => Some(CellChoice::Two),
+                "3" => Some(CellChoice::Three),
+                _ => return None,
+            };
+            Some((Setting::Monoclinic(unique_axis), cell_choice, None))
+        }
+        (CrystalSystem::Orthorhombic, _) => {
+            let (origin_choice, axes) = split_origin_choice(choice);
+            let axes = match axes {
+                "" => OrthorhombicAxes::Abc,
+                "ba-c" => OrthorhombicAxes::BaMinusC,
+                "cab" => OrthorhombicAxes::Cab,
+                "-cba" => OrthorhombicAxes::MinusCba,
+                "bca" => OrthorhombicAxes::Bca,
+                "a-cb" => OrthorhombicAxes::AMinusCb,
+                _ => return None,
+            };
+            Some((Setting::Orthorhombic(axes), None, origin_choice))
+        }
+        _ => match split_origin_choice(choice) {
+            (origin_choice, "") => Some((Setting::Standard, None, origin_choice)),
+            _ => None,
+        },
+    }
+}
+
+fn split_origin_choice(choice: &str) -> (Option<OriginChoice>, &str) {
+    if let Some(rest) = choice.strip_prefix('1') {
+        (Some(OriginChoice::One), rest)
+    } else if let Some(rest) = choice.strip_prefix('2') {
+        (Some(OriginChoice::Two), rest)
+    } else {
+        (None, choice)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CellChoice, OriginChoice, OrthorhombicAxes, Setting, SpaceGroupEntry, UniqueAxis};
+    use crate::database::SpaceGroupHallSymbol;
+
+    #[test]
+    fn parse_entries() {
+        assert_eq!(530, SpaceGroupEntry::all().count());
+        assert_eq!(
+            230,
+            SpaceGroupEntry::all()
+                .filter(|e| e.is_default_setting())
+                .count()
+        );
+        let entries: Vec<&SpaceGroupEntry> = SpaceGroupEntry::settings_of(15).collect();
+        assert_eq!(18, entries.len());
+        let entry = entries[3];
+        assert_eq!(Setting::Monoclinic(UniqueAxis::MinusB), entry.setting());
+        assert_eq!(Some(CellChoice::One), entry.cell_choice());
+        assert_eq!(entries[0].symbol(), entry.default_setting());
+        let entry = SpaceGroupHallSymbol::try_from_hm("F d d d:2")
+            .unwrap()
+            .entry();
+        assert_eq!(70, entry.number());
+        assert_eq!(Some(OriginChoice::Two), entry.origin_choice());
+        assert_eq!(
+            Setting::Orthorhombic(OrthorhombicAxes::Abc),
+            entry.setting()
+        );
+        assert!(!entry.is_default_setting());
+        let entry = SpaceGroupHallSymbol::MP_3A_2.entry();
+        assert_eq!(Setting::RhombohedralAxes, entry.setting());
+        assert_eq!("R -3 m:R", entry.hm_symbol());
+        assert_eq!("D3d^5", entry.schoenflies_symbol());
+        assert_eq!(SpaceGroupHallSymbol::MR_3_2D, entry.default_setting());
+    }
+}
@@ -0,0 +1,295 @@
+use super::{LookUpSpaceGroup, SpaceGroupHallSymbol, FULL_SPACE_GROUP_SYMBOLS};
+
+/// Lattice symbols accepted at the beginning of a Hermann-Mauguin symbol
+const LATTICE_SYMBOLS: [char; 7] = ['P', 'A', 'B', 'C', 'I', 'R', 'F'];
+/// Symbols of the glide and mirror planes
+const PLANE_SYMBOLS: [char; 7] = ['m', 'a', 'b', 'c', 'n', 'd', 'e'];
+/// Rotation orders of the crystallographic axes
+const ROTATION_ORDERS: [char; 5] = ['1', '2', '3', '4', '6'];
+
+impl SpaceGroupHallSymbol {
+    /// Resolve a Hermann-Mauguin symbol to the tabulated setting.
+    ///
+    /// Both the short (`P 21/c`, `F m -3 m`) and the full (`P 1 21/c 1`,
+    /// `F 4/m -3 2/m`) symbols are accepted, with or without spaces (`P21/c`).
+    /// Screw components can be written as `21`, `2_1` or `2₁`, and rotoinversions as
+    /// `-4`, `¯4` or with a combining overbar `4̄`. The new glide symbol `e` and the
+    /// old cubic notations without the bar (`Fm3m`) are also understood.
+    ///
+    /// The origin choice or the axes of rhombohedral groups can be selected by
+    /// a `:1`, `:2`, `:H` or `:R` suffix. Without the suffix, or for the short monoclinic
+    /// symbols which do not tell the unique axis, the first matching setting in
+    /// `FULL_SPACE_GROUP_SYMBOLS` is returned, which is origin choice 1, hexagonal
+    /// axes and unique axis b respectively.
+    pub fn try_from_hm(input: &str) -> Option<Self> {
+        let normalized = normalize_hm(input)?;
+        let (body, setting) = split_setting(&normalized)?;
+        let mut chars = body.trim_start().chars();
+        let lattice = chars.next()?.to_ascii_uppercase();
+        if !LATTICE_SYMBOLS.contains(&lattice) {
+            return None;
+        }
+        let rest: String = chars.as_str().to_lowercase();
+        let chunks: Vec<&str> = rest.split_whitespace().collect();
+        if chunks.is_empty() {
+            return None;
+        }
+        let candidates: Vec<String> = tokenize_chunks(&chunks)
+            .into_iter()
+            .flat_map(|tokens| symbol_variants(&tokens))
+            .map(|tokens| format!("{lattice} {}", tokens.join(" ")))
+            .collect();
+        (0..530).find_map(|i| {
+            let hm = FULL_SPACE_GROUP_SYMBOLS.get_hm_full_notation(i)?;
+            let (table_body, table_setting) = hm.split_once(':').unwrap_or((hm, ""));
+            if setting.as_ref().is_some_and(|s| s != table_setting) {
+                return None;
+            }
+            table_keys(table_body)
+                .iter()
+                .any(|key| candidates.contains(key))
+                .then(|| SpaceGroupHallSymbol::from_index(i))
+                .flatten()
+        })
+    }
+}
+
+/// Replace the Unicode characters by their ASCII counterparts, and
+/// write the overbars and subscripts in the form of the tables,
+/// e.g. `P4̄2₁c` -> `P-421c`.
+fn normalize_hm(input: &str) -> Option<String> {
+    let mut output = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            // combining overline and macron after the digit
+            '\u{0305}' | '\u{0304}' => {
+                let digit = output.pop()?;
+                output.push('-');
+                output.push(digit);
+            }
+            // spacing macron, minus sign, hyphen, en dash before the digit
+            '\u{00AF}' | '\u{2212}' | '\u{2010}' | '\u{2013}' => output.push('-'),
+            '\u{2080}'..='\u{2089}' => {
+                output.push(char::from(b'0' + (c as u32 - 0x2080) as u8));
+            }
+            '_' => (),
+            c if c.is_whitespace() => output.push(' '),
+            c => output.push(c),
+        }
+    }
+    Some(output)
+}
+
+/// Separate the `:1`, `:2`, `:H` or `:R` suffix
+fn split_setting(input: &str) -> Option<(&str, Option<String>)> {
+    match input.split_once(':') {
+        Some((body, setting)) => {
+            let setting = setting.trim().to_uppercase();
+            matches!(setting.as_str(), "1" | "2" | "H" | "R").then_some((body, Some(setting)))
+        }
+        None => Some((input, None)),
+    }
+}
+
+/// All the possible ways to tokenize the space-separated chunks.
+/// `P4212` is ambiguous as `P 4 21 2` or `P 42 1 2`, so every reading is kept and
+/// left to the lookup in the table.
+fn tokenize_chunks(chunks: &[&str]) -> Vec<Vec<String>> {
+    chunks.iter().fold(vec![Vec::new()], |readings, chunk| {
+        let chars: Vec<char> = chunk.chars().collect();
+        let chunk_readings = tokenize(&chars);
+        readings
+            .iter()
+            .flat_map(|prefix| {
+                chunk_readings.iter().map(move |tokens| {
+                    let mut joined = prefix.clone();
+                    joined.extend(tokens.iter().cloned());
+                    joined
+                })
+            })
+            .collect()
+    })
+}
+
+/// Split a chunk without spaces into symmetry directions in every possible way.
+/// A direction is either a plane `m`, or a rotation `-4`, `21`, `42/n`.
+fn tokenize(chars: &[char]) -> Vec<Vec<String>> {
+    if chars.is_empty() {
+        return vec![Vec::new()];
+    }
+    let mut readings = Vec::new();
+    if PLANE_SYMBOLS.contains(&chars[0]) {
+        tokenize(&chars[1..]).into_iter().for_each(|mut rest| {
+            rest.insert(0, chars[0].to_string());
+            readings.push(rest);
+        });
+        return readings;
+    }
+    let minus = chars[0] == '-';
+    let start = usize::from(minus);
+    let Some(&order) = chars.get(start).filter(|c| ROTATION_ORDERS.contains(c)) else {
+        return readings;
+    };
+    let mut rotations = vec![start + 1];
+    // screw component, only for proper rotations
+    if let Some(sub) = chars.get(start + 1).filter(|c| c.is_ascii_digit()) {
+        if !minus && *sub > '0' && *sub < order {
+            rotations.push(start + 2);
+        }
+    }
+    for end in rotations {
+        let rotation: String = chars[..end].iter().collect();
+        let (head, next) = match chars.get(end) {
+            Some('/') => match chars.get(end + 1).filter(|c| PLANE_SYMBOLS.contains(c)) {
+                Some(plane) => (format!("{rotation}/{plane}"), end + 2),
+                None => continue,
+            },
+            _ => (rotation, end),
+        };
+        tokenize(&chars[next..]).into_iter().for_each(|mut rest| {
+            rest.insert(0, head.clone());
+            readings.push(rest);
+        });
+    }
+    readings
+}
+
+/// The tokens as given, together with the short symbol reduced from a full symbol,
+/// and the readings with the `e` glide and the old cubic `3` replaced.
+fn symbol_variants(tokens: &[String]) -> Vec<Vec<String>> {
+    let mut variants = vec![tokens.to_vec()];
+    if let Some(short) = reduce_full_symbol(tokens) {
+        variants.push(short);
+    }
+    // Old cubic notation like `F m 3 m` for `F m -3 m`
+    let centric_cubic =
+        |v: &Vec<String>| v.len() >= 2 && v[1] == "3" && (v[0].contains('/') || is_plane(&v[0]));
+    let old_cubic: Vec<Vec<String>> = variants
+        .iter()
+        .filter(|v| centric_cubic(v))
+        .map(|v| {
+            let mut v = v.clone();
+            v[1] = "-3".to_string();
+            v
+        })
+        .collect();
+    variants.extend(old_cubic);
+    // The `e` glide is tabulated as `a`, `b` or `c`
+    variants
+        .into_iter()
+        .flat_map(|v| {
+            ['a', 'b', 'c'].iter().map(move |glide| {
+                v.iter()
+                    .map(|t| t.replace('e', &glide.to_string()))
+                    .collect::<Vec<String>>()
+            })
+        })
+        .collect()
+}
+
+fn is_plane(token: &str) -> bool {
+    token.len() == 1 && token.chars().all(|c| PLANE_SYMBOLS.contains(&c))
+}
+
+/// Drop the rotation parts of `X/p` which are omitted in the short symbols,
+/// e.g. `P 21/n 21/m 21/a` -> `P n m a`, `F 4/m -3 2/m` -> `F m -3 m`,
+/// `P 4/m 2/m 2/m` -> `P 4/m m m`. Full monoclinic symbols are tabulated as is.
+fn reduce_full_symbol(tokens: &[String]) -> Option<Vec<String>> {
+    if tokens.len() < 2 || !tokens.iter().any(|t| t.contains('/')) {
+        return None;
+    }
+    let plane_of = |t: &String| t.split_once('/').map_or(t.clone(), |(_, p)| p.to_string());
+    let principal = tokens[0].trim_start_matches('-').chars().next()?;
+    let is_cubic = tokens[1] == "3" || tokens[1] == "-3";
+    let kept_index: Option<usize> = if is_cubic {
+        None
+    } else if matches!(principal, '3' | '4' | '6') {
+        Some(0)
+    } else if tokens.iter().filter(|t| t.as_str() == "1").count() == 2 {
+        return None;
+    } else {
+        None
+    };
+    let reduced: Vec<String> = tokens
+        .iter()
+        .enumerate()
+        .map(|(i, t)| {
+            if Some(i) == kept_index {
+                t.clone()
+            } else {
+                plane_of(t)
+            }
+        })
+        .collect();
+    (reduced != tokens).then_some(reduced)
+}
+
+/// The tabulated symbol, and the short symbol for the monoclinic groups
+/// (`P 1 21/c 1` -> `P 21/c`).
+fn table_keys(table_body: &str) -> Vec<String> {
+    let mut keys = vec![table_body.to_string()];
+    let tokens: Vec<&str> = table_body.split_whitespace().collect();
+    if tokens.len() == 4 && tokens[1..].iter().filter(|&&t| t == "1").count() == 2 {
+        let short: Vec<&str> = tokens.into_iter().filter(|&t| t != "1").collect();
+        keys.push(short.join(" "));
+    }
+    keys
+}
+
+#[cfg(test)]
+mod test {
+    use crate::database::SpaceGroupHallSymbol;
+
+    fn number_code(input: &str) -> String {
+        SpaceGroupHallSymbol::try_from_hm(input)
+            .unwrap_or_else(|| panic!("{input} is not resolved"))
+            .get_space_group_number_code()
+    }
+
+    #[test]
+    fn parse_short_and_full() {
+        assert_eq!("14:b1", number_code("P 1 21/c 1"));
+        assert_eq!("14:b1", number_code("P21/c"));
+        assert_eq!("14:b2", number_code("P 21/n"));
+        assert_eq!("62", number_code("Pnma"));
+        assert_eq!("62", number_code("P 21/n 21/m 21/a"));
+        assert_eq!("123", number_code("P 4/m 2/m 2/m"));
+        assert_eq!("225", number_code("Fm-3m"));
+        assert_eq!("225", number_code("F 4/m -3 2/m"));
+        assert_eq!("225", number_code("Fm3m"));
+        assert_eq!("194", number_code("P63/mmc"));
+        assert_eq!("164", number_code("P -3 2/m 1"));
+        assert_eq!("64", number_code("Cmce"));
+        assert_eq!("3:b", number_code("P2"));
+        assert_eq!("3:c", number_code("P 1 1 2"));
+    }
+
+    #[test]
+    fn parse_compact_screws() {
+        assert_eq!("90", number_code("P4212"));
+        assert_eq!("94", number_code("P42212"));
+        assert_eq!("152", number_code("P3121"));
+        assert_eq!("149", number_code("P312"));
+        assert_eq!("212", number_code("P4332"));
+        assert_eq!("198", number_code("P213"));
+        assert_eq!("155:H", number_code("R32"));
+        assert_eq!("114", number_code("P-421c"));
+    }
+
+    #[test]
+    fn parse_settings_and_unicode() {
+        assert_eq!("227:1", number_code("Fd-3m"));
+        assert_eq!("227:2", number_code("F d -3 m:2"));
+        assert_eq!("166:R", number_code("R-3m:R"));
+        assert_eq!("166:H", number_code("R -3 m :h"));
+        assert_eq!("14:b1", number_code("P2₁/c"));
+        assert_eq!("14:b1", number_code("P2_1/c"));
+        assert_eq!("215", number_code("P4\u{0305}3m"));
+        assert_eq!("215", number_code("P¯43m"));
+        assert_eq!("141:2", number_code("I4₁/amd:2"));
+        assert!(SpaceGroupHallSymbol::try_from_hm("P 5").is_none());
+        assert!(SpaceGroupHallSymbol::try_from_hm("Q 2").is_none());
+        assert!(SpaceGroupHallSymbol::try_from_hm("P 2:3").is_none());
+        assert!(SpaceGroupHallSymbol::try_from_hm("").is_none());
+    }
+}
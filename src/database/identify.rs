@@ -0,0 +1,413 @@
+use std::{collections::HashMap, sync::OnceLock};
+
+use fraction::{Integer, Zero};
+
+use nalgebra::{Matrix3, Vector3};
+
+use crate::{
+    hall_symbols::{
+        change_of_basis::basis_changes, RotationType, CHANGE_OF_BASIS_BASE_NUMBER,
+        SEITZ_TRANSLATE_BASE_NUMBER,
+    },
+    utils::Rational,
+    ChangeOfBasis, GeneralPositions, HallSymbolNotation, SeitzMatrix,
+};
+
+use super::SpaceGroupHallSymbol;
+
+/// The origin shift is searched on a grid of `1/ORIGIN_SHIFT_SEARCH_BASE`,
+/// which covers the shifts like 1/8 between the origin choices.
+const ORIGIN_SHIFT_SEARCH_BASE: i32 = 24;
+
+/// Rotation part as the arrays of its columns, which can be sorted
+type RotationPart = [[i32; 3]; 3];
+
+/// Result of identifying a list of symmetry operators against the 530
+/// tabulated settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpaceGroupMatch {
+    symbol: SpaceGroupHallSymbol,
+    change_of_basis: ChangeOfBasis,
+}
+
+impl SpaceGroupMatch {
+    pub fn new(symbol: SpaceGroupHallSymbol, change_of_basis: ChangeOfBasis) -> Self {
+        Self {
+            symbol,
+            change_of_basis,
+        }
+    }
+
+    pub fn symbol(&self) -> SpaceGroupHallSymbol {
+        self.symbol
+    }
+
+    /// The change of basis `(P, p)` that transforms the input operators into the
+    /// tabulated setting: `x' = P⁻¹(x - p)`, `(W, w) -> (P⁻¹WP, P⁻¹(w + (W - I)p))`.
+    pub fn change_of_basis(&self) -> ChangeOfBasis {
+        self.change_of_basis
+    }
+}
+
+/// General positions of all the 530 settings, generated once on demand.
+pub(crate) fn tabulated_general_positions() -> &'static [GeneralPositions] {
+    static TABLE: OnceLock<Vec<GeneralPositions>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        SpaceGroupHallSymbol::all()
+            .map(|symbol| HallSymbolNotation::from(symbol).general_positions())
+            .collect()
+    })
+}
+
+/// Identify the space group setting of an arbitrary list of symmetry operators,
+/// e.g. from the `_space_group_symop_operation_xyz` loop of a CIF file.
+/// The list can either contain all operators or only one operator per rotation part
+/// together with the centring translations.
+/// The settings in the basis of the input are tried first, among which the one
+/// requiring the smallest origin shift is returned. Otherwise the other bases of the
+/// same cell volume are searched, and the simplest change of basis is returned.
+pub fn identify_space_group(operators: &[SeitzMatrix]) -> Option<SpaceGroupMatch> {
+    let input = GeneralPositions::from_operators(operators);
+    let classes = rotation_classes(&input);
+    let candidates: Vec<(SpaceGroupHallSymbol, &GeneralPositions)> = SpaceGroupHallSymbol::all()
+        .zip(tabulated_general_positions())
+        .filter(|(_, reference)| {
+            reference.num_of_general_pos() == input.num_of_general_pos()
+                && reference.lattice_translations().len() == input.lattice_translations().len()
+                && rotation_classes(reference) == classes
+        })
+        .collect();
+    let same_basis = candidates
+        .iter()
+        .filter_map(|(symbol, reference)| {
+            let shift = find_origin_shift(&input, reference)?;
+            Some(SpaceGroupMatch::new(*symbol, shifted_origin(shift)?))
+        })
+        .min_by_key(simplicity);
+    same_basis.or_else(|| {
+        // The settings with the same rotation parts share the changes of basis,
+        // with which the input is transformed once
+        let mut bases: HashMap<Vec<RotationPart>, Vec<(ChangeOfBasis, GeneralPositions)>> =
+            HashMap::new();
+        candidates
+            .iter()
+            .filter_map(|(symbol, reference)| {
+                bases
+                    .entry(rotation_parts(reference))
+                    .or_insert_with(|| {
+                        basis_changes(ChangeOfBasis::identity(), &input, reference)
+                            .filter_map(|cob| Some((cob, cob.transform_general_positions(&input)?)))
+                            .collect()
+                    })
+                    .iter()
+                    .find_map(|(cob, transformed)| {
+                        let shift = find_origin_shift(transformed, reference)?;
+                        cob.then(&shifted_origin(shift)?)
+                    })
+                    .map(|cob| SpaceGroupMatch::new(*symbol, cob))
+            })
+            .min_by_key(simplicity)
+    })
+}
+
+/// The change of basis moving the origin by `shift`, in the unit of `1/ORIGIN_SHIFT_SEARCH_BASE`
+fn shifted_origin(shift: Vector3<i32>) -> Option<ChangeOfBasis> {
+    ChangeOfBasis::new(
+        Matrix3::identity() * SEITZ_TRANSLATE_BASE_NUMBER,
+        shift * (CHANGE_OF_BASIS_BASE_NUMBER / ORIGIN_SHIFT_SEARCH_BASE),
+    )
+}
+
+/// Whether two sets of lattice translations are identical modulo integer translations.
+fn same_lattice(lhs: &[Vector3<i32>], rhs: &[Vector3<i32>]) -> bool {
+    lhs.len() == rhs.len()
+        && lhs.iter().all(|l| {
+            rhs.iter()
+                .any(|r| (l - r).iter().all(|v| v % SEITZ_TRANSLATE_BASE_NUMBER == 0))
+        })
+}
+
+/// The translation parts `f·(w + t)` of the operations, for all the centring translations
+/// `t` and the integer row vectors `f` with `f(W - I) = 0`, which depend neither on the
+/// origin nor on the integer translations. They keep the screw and glide components and
+/// are listed in order for each rotation part, in the unit of
+/// `1/SEITZ_TRANSLATE_BASE_NUMBER` and reduced into `[0, 1)`.
+fn invariant_translations(positions: &GeneralPositions) -> Vec<(RotationPart, Vec<Vec<i32>>)> {
+    let mut translations: Vec<_> = positions
+        .core_position_set()
+        .iter()
+        .map(|m| {
+            let w = m.rotation_part();
+            let functionals = invariant_functionals(&w);
+            let mut components: Vec<Vec<i32>> = positions
+                .lattice_translations()
+                .iter()
+                .map(|t| {
+                    functionals
+                        .iter()
+                        .map(|f| f.dot(&(m.translation_part() + t)))
+                        .map(|v| v.rem_euclid(SEITZ_TRANSLATE_BASE_NUMBER))
+                        .collect()
+                })
+                .collect();
+            components.sort();
+            components.dedup();
+            (w.data.0, components)
+        })
+        .collect();
+    translations.sort();
+    translations
+}
+
+/// Primitive integer vectors `f` with `f(W - I) = 0`, i.e. fixed by `Wᵀ`: the unit
+/// vectors for the identity, the axis of `Wᵀ` for a rotation, and the vectors `u × e`
+/// spanning the mirror plane with the normal `u`. Other rotoinversions fix none.
+fn invariant_functionals(rotation: &Matrix3<i32>) -> Vec<Vector3<i32>> {
+    let seitz_matrix = SeitzMatrix::new(rotation.to_homogeneous());
+    match seitz_matrix.rotation_type() {
+        Ok(RotationType::E) => (0..3).map(|i| Vector3::ith(i, 1)).collect(),
+        Ok(RotationType::M) => {
+            let normal = seitz_matrix
+                .axis_direction()
+                .expect("A mirror has a normal");
+            let mut functionals: Vec<Vector3<i32>> = (0..3)
+                .map(|i| normal.cross(&Vector3::ith(i, 1)))
+                .filter(|f| *f != Vector3::zeros())
+                .map(|f| {
+                    let f = f / f.iter().fold(0, |acc, v| acc.gcd(v));
+                    // Fix the sign by the first non-zero component
+                    if f.iter().find(|v| **v != 0).is_some_and(|v| *v < 0) {
+                        -f
+                    } else {
+                        f
+                    }
+                })
+                .collect();
+            functionals.sort_by_key(|f| f.data.0);
+            functionals.dedup();
+            functionals
+        }
+        Ok(RotationType::N2 | RotationType::N3 | RotationType::N4 | RotationType::N6) => {
+            SeitzMatrix::new(rotation.transpose().to_homogeneous())
+                .axis_direction()
+                .into_iter()
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Search the origin shift `p` (in the unit of `1/ORIGIN_SHIFT_SEARCH_BASE`) with
+/// the smallest components so that the input maps onto the reference.
+fn find_origin_shift(
+    input: &GeneralPositions,
+    reference: &GeneralPositions,
+) -> Option<Vector3<i32>> {
+    if input.num_of_general_pos() != reference.num_of_general_pos()
+        || !same_lattice(
+            input.lattice_translations(),
+            reference.lattice_translations(),
+        )
+        || invariant_translations(input) != invariant_translations(reference)
+    {
+        return None;
+    }
+    let scale = ORIGIN_SHIFT_SEARCH_BASE / SEITZ_TRANSLATE_BASE_NUMBER;
+    // (W - I, w_in - w_ref) pairs with the translations in the unit of
+    // 1/ORIGIN_SHIFT_SEARCH_BASE
+    let pairs: Vec<(Matrix3<i32>, Vector3<i32>)> = input
+        .core_position_set()
+        .iter()
+        .map(|m| {
+            reference
+                .core_position_set()
+                .iter()
+                .find(|r| r.rotation_part() == m.rotation_part())
+                .map(|r| {
+                    (
+                        m.rotation_part() - Matrix3::identity(),
+                        (m.translation_part() - r.translation_part()) * scale,
+                    )
+                })
+        })
+        .collect::<Option<Vec<(Matrix3<i32>, Vector3<i32>)>>>()?;
+    let lattice: Vec<Vector3<i32>> = reference
+        .lattice_translations()
+        .iter()
+        .map(|t| t * scale)
+        .collect();
+    let half = ORIGIN_SHIFT_SEARCH_BASE / 2;
+    // Search in (-1/2, 1/2] and keep the shortest one, positive components are preferred.
+    let mut trials: Vec<i32> = (-half + 1..=half).collect();
+    trials.sort_by_key(|&v| (v.abs(), v < 0));
+    let mut best: Option<Vector3<i32>> = None;
+    for &x in trials.iter() {
+        for &y in trials.iter() {
+            for &z in trials.iter() {
+                let p = Vector3::new(x, y, z);
+                let fits = pairs.iter().all(|(w_i, diff)| {
+                    let d = diff + w_i * p;
+                    lattice
+                        .iter()
+                        .any(|t| (d - t).iter().all(|v| v % ORIGIN_SHIFT_SEARCH_BASE == 0))
+                });
+                if fits && best.is_none_or(|b| p.dot(&p) < b.dot(&b)) {
+                    best = Some(p);
+                }
+            }
+        }
+    }
+    best
+}
+
+/// The pairs of the determinant and the trace of the rotation parts, which do not
+/// depend on the basis, in order.
+fn rotation_classes(positions: &GeneralPositions) -> Vec<(i32, i32)> {
+    let mut classes: Vec<(i32, i32)> = positions
+        .core_position_set()
+        .iter()
+        .map(|m| {
+            let w = m.rotation_part();
+            (w.map(|v| v as f64).determinant().round() as i32, w.trace())
+        })
+        .collect();
+    classes.sort();
+    classes
+}
+
+/// The rotation parts of the coset representatives, in order
+fn rotation_parts(positions: &GeneralPositions) -> Vec<RotationPart> {
+    let mut rotations: Vec<RotationPart> = positions
+        .core_position_set()
+        .iter()
+        .map(|m| m.rotation_part().data.0)
+        .collect();
+    rotations.sort();
+    rotations
+}
+
+/// Sort key of the matches: the fewer non-zero and negative entries of `P` and
+/// then the shorter origin shift first.
+fn simplicity(found: &SpaceGroupMatch) -> (usize, usize, Rational) {
+    let p = found.change_of_basis.matrix_rational();
+    let shift = found.change_of_basis.vector_rational();
+    (
+        p.iter().filter(|v| !v.is_zero()).count(),
+        p.iter().filter(|v| **v < Rational::zero()).count(),
+        shift.dot(&shift),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use nalgebra::{Matrix3, Vector3};
+
+    use crate::{
+        database::{identify_space_group, SpaceGroupHallSymbol, ALL_SPACE_GROUP_HALL_SYMBOLS},
+        ChangeOfBasis, HallSymbolNotation, SeitzMatrix,
+    };
+
+    use super::tabulated_general_positions;
+
+    #[test]
+    fn identify_p21c() {
+        let operators =
+            SeitzMatrix::try_from_xyz_list("x,y,z; -x,y+1/2,-z+1/2; -x,-y,-z; x,-y+1/2,z+1/2")
+                .unwrap();
+        let found = identify_space_group(&operators).unwrap();
+        assert_eq!(SpaceGroupHallSymbol::MP_2ybc, found.symbol());
+        assert_eq!("14:b1", found.symbol().get_space_group_number_code());
+        assert_eq!(ChangeOfBasis::identity(), found.change_of_basis());
+    }
+
+    #[test]
+    fn identify_with_origin_shift() {
+        // P 2/m 2/m 2/m with the origin moved to 1/4,1/4,1/4
+        let operators = SeitzMatrix::try_from_xyz_list(
+            "x,y,z; -x+1/2,-y+1/2,z; -x+1/2,y,-z+1/2; x,-y+1/2,-z+1/2;
+             -x+1/2,-y+1/2,-z+1/2; x,y,-z+1/2; x,-y+1/2,z; -x+1/2,y,z",
+        )
+        .unwrap();
+        let found = identify_space_group(&operators).unwrap();
+        assert_eq!("47", found.symbol().get_space_group_number_code());
+        assert_eq!(
+            Vector3::new(0.25, 0.25, 0.25),
+            found.change_of_basis().origin_shift()
+        );
+        assert_eq!(Matrix3::identity(), found.change_of_basis().matrix());
+    }
+
+    #[test]
+    fn identify_full_centred_list() {
+        let positions = HallSymbolNotation::try_from_str("-F 4vw 2vw 3")
+            .unwrap()
+            .general_positions();
+        let operators: Vec<SeitzMatrix> = positions.derive_full_sets().concat();
+        let found = identify_space_group(&operators).unwrap();
+        assert_eq!("227:2", found.symbol().get_space_group_number_code());
+        assert_eq!(ChangeOfBasis::identity(), found.change_of_basis());
+    }
+
+    #[test]
+    fn identify_i212121() {
+        // I 21 21 21 has no intersecting 2-fold axes, unlike I 2 2 2
+        let operators = SeitzMatrix::try_from_xyz_list(
+            "x,y,z; -x,-y+1/2,z; x,-y,-z+1/2; -x+1/2,y,-z;
+             x+1/2,y+1/2,z+1/2; -x+1/2,-y,z+1/2; x+1/2,-y+1/2,-z; -x,y+1/2,-z+1/2",
+        )
+        .unwrap();
+        let found = identify_space_group(&operators).unwrap();
+        assert_eq!("24", found.symbol().get_space_group_number_code());
+        assert_eq!(ChangeOfBasis::identity(), found.change_of_basis());
+        let shifted = HallSymbolNotation::try_from_str("I 2ac 2bc")
+            .unwrap()
+            .general_positions();
+        let found = identify_space_group(&shifted.derive_full_sets().concat()).unwrap();
+        assert_eq!("23", found.symbol().get_space_group_number_code());
+    }
+
+    #[test]
+    fn identify_with_whole_lattice_translation() {
+        let operators = SeitzMatrix::try_from_xyz_list("x,y,z; x,y,z-1; -x,-y,-z").unwrap();
+        let found = identify_space_group(&operators).unwrap();
+        assert_eq!("2", found.symbol().get_space_group_number_code());
+    }
+
+    #[test]
+    fn identify_not_a_group() {
+        let operators = SeitzMatrix::try_from_xyz_list("x,y,z; -x,-y,z; y,x,z").unwrap();
+        assert!(identify_space_group(&operators).is_none());
+    }
+
+    #[test]
+    fn identify_in_other_basis() {
+        // P n m a in the basis (a + b, b, c) with the origin moved to 0,0,1/4
+        let tabulated = HallSymbolNotation::try_from_str("-P 2ac 2n")
+            .unwrap()
+            .general_positions();
+        let to_other = ChangeOfBasis::try_from_str("a+b,b,c;0,0,1/4").unwrap();
+        let input = to_other.transform_general_positions(&tabulated).unwrap();
+        let found = identify_space_group(&input.derive_full_sets().concat()).unwrap();
+        assert_eq!(62, found.symbol().entry().number());
+        let reference = &tabulated_general_positions()[found.symbol() as usize];
+        assert!(found
+            .change_of_basis()
+            .transform_general_positions(&input)
+            .unwrap()
+            .is_same_group(reference));
+        assert_ne!(Matrix3::identity(), found.change_of_basis().matrix());
+    }
+
+    #[test]
+    fn index_round_trip() {
+        ALL_SPACE_GROUP_HALL_SYMBOLS
+            .iter()
+            .enumerate()
+            .for_each(|(i, &symbol)| {
+                assert_eq!(i, symbol as usize);
+                assert_eq!(Some(symbol), SpaceGroupHallSymbol::from_index(i));
+            });
+        assert_eq!(None, SpaceGroupHallSymbol::from_index(530));
+        assert!(SpaceGroupHallSymbol::all().eq(ALL_SPACE_GROUP_HALL_SYMBOLS));
+    }
+}
@@ -0,0 +1,475 @@
+use std::fmt::Display;
+
+use nalgebra::Matrix3;
+
+use crate::{hall_symbols::RotationType, HallSymbolNotation, SeitzMatrix};
+
+use super::{CrystalSystem, SpaceGroupHallSymbol};
+
+/// The 32 crystallographic point groups (crystal classes), named by their
+/// Schoenflies symbols and listed in the order of the International Tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PointGroup {
+    C1,
+    Ci,
+    C2,
+    Cs,
+    C2h,
+    D2,
+    C2v,
+    D2h,
+    C4,
+    S4,
+    C4h,
+    D4,
+    C4v,
+    D2d,
+    D4h,
+    C3,
+    C3i,
+    D3,
+    C3v,
+    D3d,
+    C6,
+    C3h,
+    C6h,
+    D6,
+    C6v,
+    D3h,
+    D6h,
+    T,
+    Th,
+    O,
+    Td,
+    Oh,
+}
+
+const ALL_POINT_GROUPS: [PointGroup; 32] = [
+    PointGroup::C1,
+    PointGroup::Ci,
+    PointGroup::C2,
+    PointGroup::Cs,
+    PointGroup::C2h,
+    PointGroup::D2,
+    PointGroup::C2v,
+    PointGroup::D2h,
+    PointGroup::C4,
+    PointGroup::S4,
+    PointGroup::C4h,
+    PointGroup::D4,
+    PointGroup::C4v,
+    PointGroup::D2d,
+    PointGroup::D4h,
+    PointGroup::C3,
+    PointGroup::C3i,
+    PointGroup::D3,
+    PointGroup::C3v,
+    PointGroup::D3d,
+    PointGroup::C6,
+    PointGroup::C3h,
+    PointGroup::C6h,
+    PointGroup::D6,
+    PointGroup::C6v,
+    PointGroup::D3h,
+    PointGroup::D6h,
+    PointGroup::T,
+    PointGroup::Th,
+    PointGroup::O,
+    PointGroup::Td,
+    PointGroup::Oh,
+];
+
+impl PointGroup {
+    /// Iterate over the 32 point groups in the order of the International Tables
+    pub fn all() -> impl Iterator<Item = Self> {
+        ALL_POINT_GROUPS.iter().copied()
+    }
+
+    /// Identify the point group formed by the rotation parts of the operations.
+    /// Translations are ignored, so the operations of a space group give its point group.
+    /// Returns `None` when the rotation parts do not form a crystallographic point group.
+    pub fn from_operations(operations: &[SeitzMatrix]) -> Option<Self> {
+        let mut rotations: Vec<Matrix3<i32>> = Vec::new();
+        let mut types: Vec<RotationType> = Vec::new();
+        for m in operations {
+            if rotations.contains(&m.rotation_part()) {
+                continue;
+            }
+            rotations.push(m.rotation_part());
+            types.push(m.rotation_type().ok()?);
+        }
+        let count = |typ: RotationType| types.iter().filter(|&&t| t == typ).count();
+        // Numbers of 2, m, 3, 4, 6, -4, -6, -1
+        let key = [
+            RotationType::N2,
+            RotationType::M,
+            RotationType::N3,
+            RotationType::N4,
+            RotationType::N6,
+            RotationType::M4,
+            RotationType::M6,
+            RotationType::I,
+        ]
+        .map(count);
+        let point_group = match key {
+            [0, 0, 0, 0, 0, 0, 0, 0] => PointGroup::C1,
+            [0, 0, 0, 0, 0, 0, 0, 1] => PointGroup::Ci,
+            [1, 0, 0, 0, 0, 0, 0, 0] => PointGroup::C2,
+            [0, 1, 0, 0, 0, 0, 0, 0] => PointGroup::Cs,
+            [1, 1, 0, 0, 0, 0, 0, 1] => PointGroup::C2h,
+            [3, 0, 0, 0, 0, 0, 0, 0] => PointGroup::D2,
+            [1, 2, 0, 0, 0, 0, 0, 0] => PointGroup::C2v,
+            [3, 3, 0, 0, 0, 0, 0, 1] => PointGroup::D2h,
+            [1, 0, 0, 2, 0, 0, 0, 0] => PointGroup::C4,
+            [1, 0, 0, 0, 0, 2, 0, 0] => PointGroup::S4,
+            [1, 1, 0, 2, 0, 2, 0, 1] => PointGroup::C4h,
+            [5, 0, 0, 2, 0, 0, 0, 0] => PointGroup::D4,
+            [1, 4, 0, 2, 0, 0, 0, 0] => PointGroup::C4v,
+            [3, 2, 0, 0, 0, 2, 0, 0] => PointGroup::D2d,
+            [5, 5, 0, 2, 0, 2, 0, 1] => PointGroup::D4h,
+            [0, 0, 2, 0, 0, 0, 0, 0] => PointGroup::C3,
+            [0, 0, 2, 0, 0, 0, 0, 1] => PointGroup::C3i,
+            [3, 0, 2, 0, 0, 0, 0, 0] => PointGroup::D3,
+            [0, 3, 2, 0, 0, 0, 0, 0] => PointGroup::C3v,
+            [3, 3, 2, 0, 0, 0, 0, 1] => PointGroup::D3d,
+            [1, 0, 2, 0, 2, 0, 0, 0] => PointGroup::C6,
+            [0, 1, 2, 0, 0, 0, 2, 0] => PointGroup::C3h,
+            [1, 1, 2, 0, 2, 0, 2, 1] => PointGroup::C6h,
+            [7, 0, 2, 0, 2, 0, 0, 0] => PointGroup::D6,
+            [1, 6, 2, 0, 2, 0, 0, 0] => PointGroup::C6v,
+            [3, 4, 2, 0, 0, 0, 2, 0] => PointGroup::D3h,
+            [7, 7, 2, 0, 2, 0, 2, 1] => PointGroup::D6h,
+            [3, 0, 8, 0, 0, 0, 0, 0] => PointGroup::T,
+            [3, 3, 8, 0, 0, 0, 0, 1] => PointGroup::Th,
+            [9, 0, 8, 6, 0, 0, 0, 0] => PointGroup::O,
+            [3, 6, 8, 0, 0, 6, 0, 0] => PointGroup::Td,
+            [9, 9, 8, 6, 0, 6, 0, 1] => PointGroup::Oh,
+            _ => return None,
+        };
+        Some(point_group)
+    }
+
+    /// Short Hermann-Mauguin symbol, e.g. `2/m`, `-42m`, `m-3m`
+    pub fn hm_symbol(&self) -> &'static str {
+        match self {
+            PointGroup::C1 => "1",
+            PointGroup::Ci => "-1",
+            PointGroup::C2 => "2",
+            PointGroup::Cs => "m",
+            PointGroup::C2h => "2/m",
+            PointGroup::D2 => "222",
+            PointGroup::C2v => "mm2",
+            PointGroup::D2h => "mmm",
+            PointGroup::C4 => "4",
+            PointGroup::S4 => "-4",
+            PointGroup::C4h => "4/m",
+            PointGroup::D4 => "422",
+            PointGroup::C4v => "4mm",
+            PointGroup::D2d => "-42m",
+            PointGroup::D4h => "4/mmm",
+            PointGroup::C3 => "3",
+            PointGroup::C3i => "-3",
+            PointGroup::D3 => "32",
+            PointGroup::C3v => "3m",
+            PointGroup::D3d => "-3m",
+            PointGroup::C6 => "6",
+            PointGroup::C3h => "-6",
+            PointGroup::C6h => "6/m",
+            PointGroup::D6 => "622",
+            PointGroup::C6v => "6mm",
+            PointGroup::D3h => "-6m2",
+            PointGroup::D6h => "6/mmm",
+            PointGroup::T => "23",
+            PointGroup::Th => "m-3",
+            PointGroup::O => "432",
+            PointGroup::Td => "-43m",
+            PointGroup::Oh => "m-3m",
+        }
+    }
+
+    /// Schoenflies symbol, e.g. `C2h`, `D2d`, `Oh`
+    pub fn schoenflies_symbol(&self) -> &'static str {
+        match self {
+            PointGroup::C1 => "C1",
+            PointGroup::Ci => "Ci",
+            PointGroup::C2 => "C2",
+            PointGroup::Cs => "Cs",
+            PointGroup::C2h => "C2h",
+            PointGroup::D2 => "D2",
+            PointGroup::C2v => "C2v",
+            PointGroup::D2h => "D2h",
+            PointGroup::C4 => "C4",
+            PointGroup::S4 => "S4",
+            PointGroup::C4h => "C4h",
+            PointGroup::D4 => "D4",
+            PointGroup::C4v => "C4v",
+            PointGroup::D2d => "D2d",
+            PointGroup::D4h => "D4h",
+            PointGroup::C3 => "C3",
+            PointGroup::C3i => "C3i",
+            PointGroup::D3 => "D3",
+            PointGroup::C3v => "C3v",
+            PointGroup::D3d => "D3d",
+            PointGroup::C6 => "C6",
+            PointGroup::C3h => "C3h",
+            PointGroup::C6h => "C6h",
+            PointGroup::D6 => "D6",
+            PointGroup::C6v => "C6v",
+            PointGroup::D3h => "D3h",
+            PointGroup::D6h => "D6h",
+            PointGroup::T => "T",
+            PointGroup::Th => "Th",
+            PointGroup::O => "O",
+            PointGroup::Td => "Td",
+            PointGroup::Oh => "Oh",
+        }
+    }
+
+    /// Number of the operations
+    pub fn order(&self) -> usize {
+        match self {
+            PointGroup::C1 => 1,
+            PointGroup::Ci | PointGroup::C2 | PointGroup::Cs => 2,
+            PointGroup::C3 => 3,
+            PointGroup::C2h
+            | PointGroup::D2
+            | PointGroup::C2v
+            | PointGroup::C4
+            | PointGroup::S4 => 4,
+            PointGroup::C3i
+            | PointGroup::D3
+            | PointGroup::C3v
+            | PointGroup::C6
+            | PointGroup::C3h => 6,
+            PointGroup::D2h
+            | PointGroup::C4h
+            | PointGroup::D4
+            | PointGroup::C4v
+            | PointGroup::D2d => 8,
+            PointGroup::D3d
+            | PointGroup::C6h
+            | PointGroup::D6
+            | PointGroup::C6v
+            | PointGroup::D3h
+            | PointGroup::T => 12,
+            PointGroup::D4h => 16,
+            PointGroup::D6h | PointGroup::Th | PointGroup::O | PointGroup::Td => 24,
+            PointGroup::Oh => 48,
+        }
+    }
+
+    /// The Laue class: the point group extended by the inversion
+    pub fn laue_class(&self) -> Self {
+        match self {
+            PointGroup::C1 | PointGroup::Ci => PointGroup::Ci,
+            PointGroup::C2 | PointGroup::Cs | PointGroup::C2h => PointGroup::C2h,
+            PointGroup::D2 | PointGroup::C2v | PointGroup::D2h => PointGroup::D2h,
+            PointGroup::C4 | PointGroup::S4 | PointGroup::C4h => PointGroup::C4h,
+            PointGroup::D4 | PointGroup::C4v | PointGroup::D2d | PointGroup::D4h => PointGroup::D4h,
+            PointGroup::C3 | PointGroup::C3i => PointGroup::C3i,
+            PointGroup::D3 | PointGroup::C3v | PointGroup::D3d => PointGroup::D3d,
+            PointGroup::C6 | PointGroup::C3h | PointGroup::C6h => PointGroup::C6h,
+            PointGroup::D6 | PointGroup::C6v | PointGroup::D3h | PointGroup::D6h => PointGroup::D6h,
+            PointGroup::T | PointGroup::Th => PointGroup::Th,
+            PointGroup::O | PointGroup::Td | PointGroup::Oh => PointGroup::Oh,
+        }
+    }
+
+    /// Whether the group contains the inversion
+    pub fn is_centrosymmetric(&self) -> bool {
+        self.laue_class() == *self
+    }
+
+    /// Whether the group leaves a direction and its opposite non-equivalent,
+    /// i.e. the crystal class allows pyroelectricity.
+    pub fn is_polar(&self) -> bool {
+        matches!(
+            self,
+            PointGroup::C1
+                | PointGroup::C2
+                | PointGroup::Cs
+                | PointGroup::C2v
+                | PointGroup::C4
+                | PointGroup::C4v
+                | PointGroup::C3
+                | PointGroup::C3v
+                | PointGroup::C6
+                | PointGroup::C6v
+        )
+    }
+
+    /// Whether the group consists of the proper rotations only (enantiomorphic class)
+    pub fn is_chiral(&self) -> bool {
+        matches!(
+            self,
+            PointGroup::C1
+                | PointGroup::C2
+                | PointGroup::D2
+                | PointGroup::C4
+                | PointGroup::D4
+                | PointGroup::C3
+                | PointGroup::D3
+                | PointGroup::C6
+                | PointGroup::D6
+                | PointGroup::T
+                | PointGroup::O
+        )
+    }
+
+    pub fn crystal_system(&self) -> CrystalSystem {
+        match self {
+            PointGroup::C1 | PointGroup::Ci => CrystalSystem::Triclinic,
+            PointGroup::C2 | PointGroup::Cs | PointGroup::C2h => CrystalSystem::Monoclinic,
+            PointGroup::D2 | PointGroup::C2v | PointGroup::D2h => CrystalSystem::Orthorhombic,
+            PointGroup::C4
+            | PointGroup::S4
+            | PointGroup::C4h
+            | PointGroup::D4
+            | PointGroup::C4v
+            | PointGroup::D2d
+            | PointGroup::D4h => CrystalSystem::Tetragonal,
+            PointGroup::C3
+            | PointGroup::C3i
+            | PointGroup::D3
+            | PointGroup::C3v
+            | PointGroup::D3d => CrystalSystem::Trigonal,
+            PointGroup::C6
+            | PointGroup::C3h
+            | PointGroup::C6h
+            | PointGroup::D6
+            | PointGroup::C6v
+            | PointGroup::D3h
+            | PointGroup::D6h => CrystalSystem::Hexagonal,
+            PointGroup::T | PointGroup::Th | PointGroup::O | PointGroup::Td | PointGroup::Oh => {
+                CrystalSystem::Cubic
+            }
+        }
+    }
+}
+
+impl Display for PointGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.hm_symbol())
+    }
+}
+
+impl HallSymbolNotation {
+    /// Point group of the space group, from the rotation parts of the general positions.
+    /// Returns `None` if the symbol does not generate a space group or the generated
+    /// operations do not form a crystallographic point group.
+    pub fn point_group(&self) -> Option<PointGroup> {
+        PointGroup::from_operations(self.try_general_positions().ok()?.core_position_set())
+    }
+}
+
+impl SpaceGroupHallSymbol {
+    pub fn point_group(&self) -> PointGroup {
+        HallSymbolNotation::from(*self)
+            .point_group()
+            .expect("Tabulated Hall symbols generate crystallographic groups")
+    }
+
+    pub fn laue_class(&self) -> PointGroup {
+        self.point_group().laue_class()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PointGroup;
+    use crate::{
+        database::{
+            LookUpSpaceGroup, SpaceGroupHallSymbol, DEFAULT_SPACE_GROUP_SYMBOLS,
+            FULL_SPACE_GROUP_SYMBOLS,
+        },
+        HallSymbolNotation,
+    };
+
+    /// Number of the first space group of each crystal class
+    const FIRST_NUMBERS: [u8; 32] = [
+        1, 2, 3, 6, 10, 16, 25, 47, 75, 81, 83, 89, 99, 111, 123, 143, 147, 149, 156, 162, 168,
+        174, 175, 177, 183, 187, 191, 195, 200, 207, 215, 221,
+    ];
+
+    #[test]
+    fn point_groups_of_space_groups() {
+        SpaceGroupHallSymbol::all().for_each(|symbol| {
+            let number = symbol.space_group_number();
+            let expected = PointGroup::all()
+                .zip(FIRST_NUMBERS)
+                .filter(|(_, first)| *first <= number)
+                .last()
+                .unwrap()
+                .0;
+            let point_group = symbol.point_group();
+            assert_eq!(expected, point_group, "{}", symbol.get_hm_symbol());
+            assert!(symbol
+                .get_schoenflies_symbol()
+                .starts_with(&format!("{}^", point_group.schoenflies_symbol())));
+            assert_eq!(
+                point_group.order(),
+                HallSymbolNotation::from(symbol)
+                    .general_positions()
+                    .num_of_general_pos()
+            );
+        });
+    }
+
+    #[test]
+    fn schoenflies_lookup() {
+        assert_eq!(
+            Some("14:b1"),
+            DEFAULT_SPACE_GROUP_SYMBOLS.get_space_group_number_by_schoenflies("C2h^5")
+        );
+        assert_eq!(
+            Some("D4h^14"),
+            DEFAULT_SPACE_GROUP_SYMBOLS.get_schoenflies_symbol(135)
+        );
+        assert_eq!(
+            Some("227:1"),
+            FULL_SPACE_GROUP_SYMBOLS.get_space_group_number_by_schoenflies("Oh^7")
+        );
+        assert_eq!(
+            "D3d^5",
+            SpaceGroupHallSymbol::try_from_hm("R -3 m:R")
+                .unwrap()
+                .get_schoenflies_symbol()
+        );
+    }
+
+    #[test]
+    fn point_group_properties() {
+        assert_eq!(
+            11,
+            PointGroup::all().filter(|p| p.is_centrosymmetric()).count()
+        );
+        assert_eq!(10, PointGroup::all().filter(|p| p.is_polar()).count());
+        assert_eq!(11, PointGroup::all().filter(|p| p.is_chiral()).count());
+        let p = SpaceGroupHallSymbol::try_from_hm("P 63 m c")
+            .unwrap()
+            .point_group();
+        assert_eq!("6mm", p.hm_symbol());
+        assert_eq!("C6v", p.schoenflies_symbol());
+        assert_eq!(PointGroup::D6h, p.laue_class());
+        assert!(p.is_polar() && !p.is_chiral());
+        assert_eq!(
+            Some(PointGroup::D2d),
+            HallSymbolNotation::try_from_str("P -4 2")
+                .unwrap()
+                .point_group()
+        );
+        assert_eq!(
+            Some(PointGroup::Td),
+            HallSymbolNotation::try_from_str("I -4 2 3")
+                .unwrap()
+                .point_group()
+        );
+        // The 4-fold and 3-fold axes together do not generate a finite group
+        assert_eq!(
+            None,
+            HallSymbolNotation::try_from_str("P 4 3")
+                .unwrap()
+                .point_group()
+        );
+    }
+}
@@ -4,9 +4,13 @@ pub trait LookUpSpaceGroup {
     fn get_space_group_number(&self, i: usize) -> Option<&str>;
     fn get_space_group_number_by_hm(&self, hm_full: &str) -> Option<&str>;
     fn get_space_group_number_by_hall(&self, hall_symbol: &str) -> Option<&str>;
+    /// Schoenflies symbol with the superscript written after `^`, e.g. `C2h^5`
+    fn get_schoenflies_symbol(&self, i: usize) -> Option<&str>;
+    /// The first tabulated number code with the Schoenflies symbol, e.g. `C2h^5`
+    fn get_space_group_number_by_schoenflies(&self, schoenflies: &str) -> Option<&str>;
 }
 
-impl LookUpSpaceGroup for [[&str; 530]; 3] {
+impl LookUpSpaceGroup for [[&str; 530]; 4] {
     fn get_hm_full_notation(&self, i: usize) -> Option<&str> {
         self.get(1).and_then(|&v| v.get(i).copied())
     }
@@ -32,9 +36,22 @@ impl LookUpSpaceGroup for [[&str; 530]; 3] {
     fn get_space_group_number(&self, i: usize) -> Option<&str> {
         self.first().and_then(|&v| v.get(i).copied())
     }
+
+    fn get_schoenflies_symbol(&self, i: usize) -> Option<&str> {
+        self.get(3).and_then(|&v| v.get(i).copied())
+    }
+
+    fn get_space_group_number_by_schoenflies(&self, schoenflies: &str) -> Option<&str> {
+        let i = self
+            .get(3)
+            .unwrap()
+            .iter()
+            .position(|&symbol| symbol == schoenflies)?;
+        self.first().unwrap().get(i).copied()
+    }
 }
 
-impl LookUpSpaceGroup for [[&str; 230]; 3] {
+impl LookUpSpaceGroup for [[&str; 230]; 4] {
     fn get_hm_full_notation(&self, i: usize) -> Option<&str> {
         self.get(1).and_then(|&v| v.get(i).copied())
     }
@@ -60,6 +77,19 @@ impl LookUpSpaceGroup for [[&str; 230]; 3] {
     fn get_space_group_number(&self, i: usize) -> Option<&str> {
         self.first().and_then(|&v| v.get(i).copied())
     }
+
+    fn get_schoenflies_symbol(&self, i: usize) -> Option<&str> {
+        self.get(3).and_then(|&v| v.get(i).copied())
+    }
+
+    fn get_space_group_number_by_schoenflies(&self, schoenflies: &str) -> Option<&str> {
+        let i = self
+            .get(3)
+            .unwrap()
+            .iter()
+            .position(|&symbol| symbol == schoenflies)?;
+        self.first().unwrap().get(i).copied()
+    }
 }
 
 const NUMBER_AND_CHOICE: [&str; 530] = [
@@ -482,7 +512,7 @@ const HM_FULL: [&str; 530] = [
     "I 41/a:1",
     "I 41/a:2",
     "P 4 2 2",
-    "P 42 1 2",
+    "P 4 21 2",
     "P 41 2 2",
     "P 41 21 2",
     "P 42 2 2",
@@ -772,7 +802,9 @@ const HALL_NAME: [&str; 530] = [
     "B 2 2",
     "F 2 2",
     "I 2 2",
-    "I 2ac 2bc",
+    // Not `I 2ac 2bc`: with the centring, its 2-fold axes along z and x intersect,
+    // so it generates `I 2 2 2` shifted by (0, 1/4, 1/4) instead of `I 21 21 21`.
+    "I 2b 2c",
     "P 2 -2",
     "P -2 2",
     "P -2 -2",
@@ -1292,7 +1324,7 @@ const DEFAULT_HM_FULL: [&str; 230] = [
     "I 4",
     "I 41",
     "P 4 2 2   ",
-    "P 42 1 2  ",
+    "P 4 21 2  ",
     "P 41 2 2  ",
     "P 41 21 2 ",
     "P 42 2 2  ",
@@ -1668,10 +1700,93 @@ const DEFAULT_HALL_NAME: [&str; 230] = [
     "-I 4bd 2c 3",
 ];
 
-pub const FULL_SPACE_GROUP_SYMBOLS: [[&str; 530]; 3] = [NUMBER_AND_CHOICE, HM_FULL, HALL_NAME];
+const SCHOENFLIES: [&str; 530] = [
+    "C1^1", "Ci^1", "C2^1", "C2^1", "C2^1", "C2^2", "C2^2", "C2^2", "C2^3", "C2^3", "C2^3", "C2^3",
+    "C2^3", "C2^3", "C2^3", "C2^3", "C2^3", "Cs^1", "Cs^1", "Cs^1", "Cs^2", "Cs^2", "Cs^2", "Cs^2",
+    "Cs^2", "Cs^2", "Cs^2", "Cs^2", "Cs^2", "Cs^3", "Cs^3", "Cs^3", "Cs^3", "Cs^3", "Cs^3", "Cs^3",
+    "Cs^3", "Cs^3", "Cs^4", "Cs^4", "Cs^4", "Cs^4", "Cs^4", "Cs^4", "Cs^4", "Cs^4", "Cs^4", "Cs^4",
+    "Cs^4", "Cs^4", "Cs^4", "Cs^4", "Cs^4", "Cs^4", "Cs^4", "Cs^4", "C2h^1", "C2h^1", "C2h^1",
+    "C2h^2", "C2h^2", "C2h^2", "C2h^3", "C2h^3", "C2h^3", "C2h^3", "C2h^3", "C2h^3", "C2h^3",
+    "C2h^3", "C2h^3", "C2h^4", "C2h^4", "C2h^4", "C2h^4", "C2h^4", "C2h^4", "C2h^4", "C2h^4",
+    "C2h^4", "C2h^5", "C2h^5", "C2h^5", "C2h^5", "C2h^5", "C2h^5", "C2h^5", "C2h^5", "C2h^5",
+    "C2h^6", "C2h^6", "C2h^6", "C2h^6", "C2h^6", "C2h^6", "C2h^6", "C2h^6", "C2h^6", "C2h^6",
+    "C2h^6", "C2h^6", "C2h^6", "C2h^6", "C2h^6", "C2h^6", "C2h^6", "C2h^6", "D2^1", "D2^2", "D2^2",
+    "D2^2", "D2^3", "D2^3", "D2^3", "D2^4", "D2^5", "D2^5", "D2^5", "D2^6", "D2^6", "D2^6", "D2^7",
+    "D2^8", "D2^9", "C2v^1", "C2v^1", "C2v^1", "C2v^2", "C2v^2", "C2v^2", "C2v^2", "C2v^2",
+    "C2v^2", "C2v^3", "C2v^3", "C2v^3", "C2v^4", "C2v^4", "C2v^4", "C2v^4", "C2v^4", "C2v^4",
+    "C2v^5", "C2v^5", "C2v^5", "C2v^5", "C2v^5", "C2v^5", "C2v^6", "C2v^6", "C2v^6", "C2v^6",
+    "C2v^6", "C2v^6", "C2v^7", "C2v^7", "C2v^7", "C2v^7", "C2v^7", "C2v^7", "C2v^8", "C2v^8",
+    "C2v^8", "C2v^9", "C2v^9", "C2v^9", "C2v^9", "C2v^9", "C2v^9", "C2v^10", "C2v^10", "C2v^10",
+    "C2v^11", "C2v^11", "C2v^11", "C2v^12", "C2v^12", "C2v^12", "C2v^12", "C2v^12", "C2v^12",
+    "C2v^13", "C2v^13", "C2v^13", "C2v^14", "C2v^14", "C2v^14", "C2v^14", "C2v^14", "C2v^14",
+    "C2v^15", "C2v^15", "C2v^15", "C2v^15", "C2v^15", "C2v^15", "C2v^16", "C2v^16", "C2v^16",
+    "C2v^16", "C2v^16", "C2v^16", "C2v^17", "C2v^17", "C2v^17", "C2v^17", "C2v^17", "C2v^17",
+    "C2v^18", "C2v^18", "C2v^18", "C2v^19", "C2v^19", "C2v^19", "C2v^20", "C2v^20", "C2v^20",
+    "C2v^21", "C2v^21", "C2v^21", "C2v^22", "C2v^22", "C2v^22", "C2v^22", "C2v^22", "C2v^22",
+    "D2h^1", "D2h^2", "D2h^2", "D2h^3", "D2h^3", "D2h^3", "D2h^4", "D2h^4", "D2h^4", "D2h^4",
+    "D2h^4", "D2h^4", "D2h^5", "D2h^5", "D2h^5", "D2h^5", "D2h^5", "D2h^5", "D2h^6", "D2h^6",
+    "D2h^6", "D2h^6", "D2h^6", "D2h^6", "D2h^7", "D2h^7", "D2h^7", "D2h^7", "D2h^7", "D2h^7",
+    "D2h^8", "D2h^8", "D2h^8", "D2h^8", "D2h^8", "D2h^8", "D2h^9", "D2h^9", "D2h^9", "D2h^10",
+    "D2h^10", "D2h^10", "D2h^11", "D2h^11", "D2h^11", "D2h^11", "D2h^11", "D2h^11", "D2h^12",
+    "D2h^12", "D2h^12", "D2h^13", "D2h^13", "D2h^13", "D2h^13", "D2h^13", "D2h^13", "D2h^14",
+    "D2h^14", "D2h^14", "D2h^14", "D2h^14", "D2h^14", "D2h^15", "D2h^15", "D2h^16", "D2h^16",
+    "D2h^16", "D2h^16", "D2h^16", "D2h^16", "D2h^17", "D2h^17", "D2h^17", "D2h^17", "D2h^17",
+    "D2h^17", "D2h^18", "D2h^18", "D2h^18", "D2h^18", "D2h^18", "D2h^18", "D2h^19", "D2h^19",
+    "D2h^19", "D2h^20", "D2h^20", "D2h^20", "D2h^21", "D2h^21", "D2h^21", "D2h^21", "D2h^21",
+    "D2h^21", "D2h^22", "D2h^22", "D2h^22", "D2h^22", "D2h^22", "D2h^22", "D2h^22", "D2h^22",
+    "D2h^22", "D2h^22", "D2h^22", "D2h^22", "D2h^23", "D2h^24", "D2h^24", "D2h^25", "D2h^26",
+    "D2h^26", "D2h^26", "D2h^27", "D2h^27", "D2h^28", "D2h^28", "D2h^28", "D2h^28", "D2h^28",
+    "D2h^28", "C4^1", "C4^2", "C4^3", "C4^4", "C4^5", "C4^6", "S4^1", "S4^2", "C4h^1", "C4h^2",
+    "C4h^3", "C4h^3", "C4h^4", "C4h^4", "C4h^5", "C4h^6", "C4h^6", "D4^1", "D4^2", "D4^3", "D4^4",
+    "D4^5", "D4^6", "D4^7", "D4^8", "D4^9", "D4^10", "C4v^1", "C4v^2", "C4v^3", "C4v^4", "C4v^5",
+    "C4v^6", "C4v^7", "C4v^8", "C4v^9", "C4v^10", "C4v^11", "C4v^12", "D2d^1", "D2d^2", "D2d^3",
+    "D2d^4", "D2d^5", "D2d^6", "D2d^7", "D2d^8", "D2d^9", "D2d^10", "D2d^11", "D2d^12", "D4h^1",
+    "D4h^2", "D4h^3", "D4h^3", "D4h^4", "D4h^4", "D4h^5", "D4h^6", "D4h^7", "D4h^7", "D4h^8",
+    "D4h^8", "D4h^9", "D4h^10", "D4h^11", "D4h^11", "D4h^12", "D4h^12", "D4h^13", "D4h^14",
+    "D4h^15", "D4h^15", "D4h^16", "D4h^16", "D4h^17", "D4h^18", "D4h^19", "D4h^19", "D4h^20",
+    "D4h^20", "C3^1", "C3^2", "C3^3", "C3^4", "C3^4", "C3i^1", "C3i^2", "C3i^2", "D3^1", "D3^2",
+    "D3^3", "D3^4", "D3^5", "D3^6", "D3^7", "D3^7", "C3v^1", "C3v^2", "C3v^3", "C3v^4", "C3v^5",
+    "C3v^5", "C3v^6", "C3v^6", "D3d^1", "D3d^2", "D3d^3", "D3d^4", "D3d^5", "D3d^5", "D3d^6",
+    "D3d^6", "C6^1", "C6^2", "C6^3", "C6^4", "C6^5", "C6^6", "C3h^1", "C6h^1", "C6h^2", "D6^1",
+    "D6^2", "D6^3", "D6^4", "D6^5", "D6^6", "C6v^1", "C6v^2", "C6v^3", "C6v^4", "D3h^1", "D3h^2",
+    "D3h^3", "D3h^4", "D6h^1", "D6h^2", "D6h^3", "D6h^4", "T^1", "T^2", "T^3", "T^4", "T^5",
+    "Th^1", "Th^2", "Th^2", "Th^3", "Th^4", "Th^4", "Th^5", "Th^6", "Th^7", "O^1", "O^2", "O^3",
+    "O^4", "O^5", "O^6", "O^7", "O^8", "Td^1", "Td^2", "Td^3", "Td^4", "Td^5", "Td^6", "Oh^1",
+    "Oh^2", "Oh^2", "Oh^3", "Oh^4", "Oh^4", "Oh^5", "Oh^6", "Oh^7", "Oh^7", "Oh^8", "Oh^8", "Oh^9",
+    "Oh^10",
+];
+
+const DEFAULT_SCHOENFLIES: [&str; 230] = [
+    "C1^1", "Ci^1", "C2^1", "C2^2", "C2^3", "Cs^1", "Cs^2", "Cs^3", "Cs^4", "C2h^1", "C2h^2",
+    "C2h^3", "C2h^4", "C2h^5", "C2h^6", "D2^1", "D2^2", "D2^3", "D2^4", "D2^5", "D2^6", "D2^7",
+    "D2^8", "D2^9", "C2v^1", "C2v^2", "C2v^3", "C2v^4", "C2v^5", "C2v^6", "C2v^7", "C2v^8",
+    "C2v^9", "C2v^10", "C2v^11", "C2v^12", "C2v^13", "C2v^14", "C2v^15", "C2v^16", "C2v^17",
+    "C2v^18", "C2v^19", "C2v^20", "C2v^21", "C2v^22", "D2h^1", "D2h^2", "D2h^3", "D2h^4", "D2h^5",
+    "D2h^6", "D2h^7", "D2h^8", "D2h^9", "D2h^10", "D2h^11", "D2h^12", "D2h^13", "D2h^14", "D2h^15",
+    "D2h^16", "D2h^17", "D2h^18", "D2h^19", "D2h^20", "D2h^21", "D2h^22", "D2h^23", "D2h^24",
+    "D2h^25", "D2h^26", "D2h^27", "D2h^28", "C4^1", "C4^2", "C4^3", "C4^4", "C4^5", "C4^6", "S4^1",
+    "S4^2", "C4h^1", "C4h^2", "C4h^3", "C4h^4", "C4h^5", "C4h^6", "D4^1", "D4^2", "D4^3", "D4^4",
+    "D4^5", "D4^6", "D4^7", "D4^8", "D4^9", "D4^10", "C4v^1", "C4v^2", "C4v^3", "C4v^4", "C4v^5",
+    "C4v^6", "C4v^7", "C4v^8", "C4v^9", "C4v^10", "C4v^11", "C4v^12", "D2d^1", "D2d^2", "D2d^3",
+    "D2d^4", "D2d^5", "D2d^6", "D2d^7", "D2d^8", "D2d^9", "D2d^10", "D2d^11", "D2d^12", "D4h^1",
+    "D4h^2", "D4h^3", "D4h^4", "D4h^5", "D4h^6", "D4h^7", "D4h^8", "D4h^9", "D4h^10", "D4h^11",
+    "D4h^12", "D4h^13", "D4h^14", "D4h^15", "D4h^16", "D4h^17", "D4h^18", "D4h^19", "D4h^20",
+    "C3^1", "C3^2", "C3^3", "C3^4", "C3i^1", "C3i^2", "D3^1", "D3^2", "D3^3", "D3^4", "D3^5",
+    "D3^6", "D3^7", "C3v^1", "C3v^2", "C3v^3", "C3v^4", "C3v^5", "C3v^6", "D3d^1", "D3d^2",
+    "D3d^3", "D3d^4", "D3d^5", "D3d^6", "C6^1", "C6^2", "C6^3", "C6^4", "C6^5", "C6^6", "C3h^1",
+    "C6h^1", "C6h^2", "D6^1", "D6^2", "D6^3", "D6^4", "D6^5", "D6^6", "C6v^1", "C6v^2", "C6v^3",
+    "C6v^4", "D3h^1", "D3h^2", "D3h^3", "D3h^4", "D6h^1", "D6h^2", "D6h^3", "D6h^4", "T^1", "T^2",
+    "T^3", "T^4", "T^5", "Th^1", "Th^2", "Th^3", "Th^4", "Th^5", "Th^6", "Th^7", "O^1", "O^2",
+    "O^3", "O^4", "O^5", "O^6", "O^7", "O^8", "Td^1", "Td^2", "Td^3", "Td^4", "Td^5", "Td^6",
+    "Oh^1", "Oh^2", "Oh^3", "Oh^4", "Oh^5", "Oh^6", "Oh^7", "Oh^8", "Oh^9", "Oh^10",
+];
+
+pub static FULL_SPACE_GROUP_SYMBOLS: [[&str; 530]; 4] =
+    [NUMBER_AND_CHOICE, HM_FULL, HALL_NAME, SCHOENFLIES];
 
-pub const DEFAULT_SPACE_GROUP_SYMBOLS: [[&str; 230]; 3] = [
+pub const DEFAULT_SPACE_GROUP_SYMBOLS: [[&str; 230]; 4] = [
     DEFAULT_NUMBER_AND_CHOICE,
     DEFAULT_HM_FULL,
     DEFAULT_HALL_NAME,
+    DEFAULT_SCHOENFLIES,
 ];
@@ -0,0 +1,168 @@
+use std::collections::HashSet;
+
+use nalgebra::{Matrix3, Vector3};
+
+use crate::{
+    hall_symbols::{change_of_basis::inverse, SEITZ_TRANSLATE_BASE_NUMBER},
+    SeitzMatrix,
+};
+
+use super::{lattice::Lattice, Rational};
+
+/// An operation `(W, w)` with the exact translation part
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) struct Operation {
+    pub(super) rotation: Matrix3<i32>,
+    pub(super) translation: Vector3<Rational>,
+}
+
+impl Operation {
+    pub(super) fn from_seitz_matrix(seitz_matrix: &SeitzMatrix) -> Self {
+        Self {
+            rotation: seitz_matrix.rotation_part(),
+            translation: seitz_matrix
+                .translation_part()
+                .map(|v| Rational::new(v as i64, SEITZ_TRANSLATE_BASE_NUMBER as i64)),
+        }
+    }
+
+    pub(super) fn translation(translation: Vector3<Rational>) -> Self {
+        Self {
+            rotation: Matrix3::identity(),
+            translation,
+        }
+    }
+
+    /// The operation with the translation in the unit of `1/12`, not reduced into the
+    /// unit cell, since the subgroups may have larger cells
+    pub(super) fn to_seitz_matrix(&self) -> SeitzMatrix {
+        let translation = self.translation.map(|v| {
+            (v * Rational::from_integer(SEITZ_TRANSLATE_BASE_NUMBER as i64)).to_integer() as i32
+        });
+        let mut mat = self.rotation.to_homogeneous();
+        mat.set_column(3, &translation.push(1));
+        SeitzMatrix::new(mat)
+    }
+
+    pub(super) fn then_translate(&self, translation: &Vector3<Rational>) -> Self {
+        Self {
+            rotation: self.rotation,
+            translation: self.translation + translation,
+        }
+    }
+
+    /// The product `self * rhs`, `(W₁W₂, W₁w₂ + w₁)`
+    pub(super) fn mul(&self, rhs: &Self) -> Self {
+        Self {
+            rotation: self.rotation * rhs.rotation,
+            translation: rational_matrix(&self.rotation) * rhs.translation + self.translation,
+        }
+    }
+
+    pub(super) fn inverse(&self) -> Self {
+        let rotation = inverse(&rational_matrix(&self.rotation));
+        Self {
+            rotation: rotation.map(|v| v.to_integer() as i32),
+            translation: -(rotation * self.translation),
+        }
+    }
+
+    /// The same operation modulo the lattice
+    pub(super) fn reduced(&self, lattice: &Lattice) -> Self {
+        Self {
+            rotation: self.rotation,
+            translation: lattice.reduce(&self.translation),
+        }
+    }
+
+    fn sort_key(&self) -> ([i32; 9], [Rational; 3]) {
+        (
+            std::array::from_fn(|i| self.rotation[i]),
+            std::array::from_fn(|i| self.translation[i]),
+        )
+    }
+}
+
+/// A subgroup of finite index, given by its operations modulo its lattice of translations
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Subgroup {
+    /// Sorted operations with the translations reduced by the lattice
+    operations: Vec<Operation>,
+    lattice: Lattice,
+}
+
+impl Subgroup {
+    /// Generate the group modulo the lattice, or `None` when more than `limit` operations
+    /// appear
+    pub(super) fn generate(
+        generators: &[Operation],
+        lattice: &Lattice,
+        limit: usize,
+    ) -> Option<Self> {
+        let identity = Operation::translation(Vector3::zeros());
+        let mut members: HashSet<Operation> = HashSet::from([identity.clone()]);
+        let mut operations = vec![identity];
+        let mut i = 0;
+        while i < operations.len() {
+            for g in generators {
+                let product = operations[i].mul(g).reduced(lattice);
+                if members.insert(product.clone()) {
+                    operations.push(product);
+                    if operations.len() > limit {
+                        return None;
+                    }
+                }
+            }
+            i += 1;
+        }
+        Some(Self::from_reduced(operations, lattice.clone()))
+    }
+
+    fn from_reduced(mut operations: Vec<Operation>, lattice: Lattice) -> Self {
+        operations.sort_by_key(|op| op.sort_key());
+        Self {
+            operations,
+            lattice,
+        }
+    }
+
+    pub(super) fn operations(&self) -> &[Operation] {
+        &self.operations
+    }
+
+    pub(super) fn lattice(&self) -> &Lattice {
+        &self.lattice
+    }
+
+    /// The conjugate subgroup `gHg⁻¹`, whose lattice is the same for the lattices
+    /// invariant under `g`
+    pub(super) fn conjugate(&self, g: &Operation) -> Self {
+        let inverse = g.inverse();
+        let operations = self
+            .operations
+            .iter()
+            .map(|h| g.mul(h).mul(&inverse).reduced(&self.lattice))
+            .collect();
+        Self::from_reduced(operations, self.lattice.clone())
+    }
+
+    /// Whether every operation of `other` is an operation of this subgroup
+    pub(super) fn contains(&self, other: &Subgroup) -> bool {
+        other.lattice.is_sublattice_of(&self.lattice)
+            && other
+                .operations
+                .iter()
+                .all(|op| self.contains_operation(op))
+    }
+
+    pub(super) fn contains_operation(&self, operation: &Operation) -> bool {
+        let reduced = operation.reduced(&self.lattice);
+        self.operations
+            .binary_search_by_key(&reduced.sort_key(), |o| o.sort_key())
+            .is_ok()
+    }
+}
+
+pub(super) fn rational_matrix(m: &Matrix3<i32>) -> Matrix3<Rational> {
+    m.map(|v| Rational::from_integer(v as i64))
+}
@@ -0,0 +1,281 @@
+use crate::SeitzMatrix;
+
+use super::SymmetryGroup;
+
+impl SymmetryGroup {
+    /// Index of the identity in `operations()`
+    pub fn identity(&self) -> usize {
+        self.position(&SeitzMatrix::identity())
+            .expect("A group has the identity")
+    }
+
+    /// Index of the product `operations()[i] * operations()[j]`
+    pub fn product(&self, i: usize, j: usize) -> usize {
+        self.position(&(self.operations[i] * self.operations[j]))
+            .expect("A group is closed")
+    }
+
+    /// The multiplication table as indices, where the row `i` and the column `j`
+    /// hold the product of the `i`-th and the `j`-th operations.
+    pub fn multiplication_table(&self) -> Vec<Vec<usize>> {
+        (0..self.order())
+            .map(|i| (0..self.order()).map(|j| self.product(i, j)).collect())
+            .collect()
+    }
+
+    /// Index of the inverse of the `i`-th operation
+    pub fn inverse(&self, i: usize) -> usize {
+        let identity = self.identity();
+        (0..self.order())
+            .find(|&j| self.product(i, j) == identity)
+            .expect("A group has the inverses")
+    }
+
+    /// Order of the `i`-th operation, the smallest `n` with `g^n` being the identity
+    pub fn element_order(&self, i: usize) -> usize {
+        let identity = self.identity();
+        let mut power = i;
+        let mut order = 1;
+        while power != identity {
+            power = self.product(power, i);
+            order += 1;
+        }
+        order
+    }
+
+    pub fn element_orders(&self) -> Vec<usize> {
+        (0..self.order()).map(|i| self.element_order(i)).collect()
+    }
+
+    /// Conjugacy classes `{g h g⁻¹}` as sorted indices, ordered by their first element
+    pub fn conjugacy_classes(&self) -> Vec<Vec<usize>> {
+        let inverses: Vec<usize> = (0..self.order()).map(|g| self.inverse(g)).collect();
+        let mut assigned = vec![false; self.order()];
+        let mut classes = Vec::new();
+        for h in 0..self.order() {
+            if assigned[h] {
+                continue;
+            }
+            let mut class: Vec<usize> = (0..self.order())
+                .map(|g| self.product(self.product(g, h), inverses[g]))
+                .collect();
+            class.sort();
+            class.dedup();
+            class.iter().for_each(|&k| assigned[k] = true);
+            classes.push(class);
+        }
+        classes
+    }
+
+    /// The subgroup generated by the operations, as sorted indices
+    pub fn generated_subgroup(&self, generators: &[usize]) -> Vec<usize> {
+        generate(self.order(), self.identity(), generators, |i, j| {
+            self.product(i, j)
+        })
+    }
+
+    /// All the subgroups as sorted indices, from the trivial group to the whole group.
+    /// They are the joins of the cyclic subgroups.
+    pub fn subgroups(&self) -> Vec<Vec<usize>> {
+        let table = self.multiplication_table();
+        let identity = self.identity();
+        // Subgroups with their generators, starting from the cyclic ones
+        let mut cyclic: Vec<(Vec<usize>, Vec<usize>)> = Vec::new();
+        for i in 0..self.order() {
+            let subgroup = generate(self.order(), identity, &[i], |i, j| table[i][j]);
+            if !cyclic.iter().any(|(c, _)| *c == subgroup) {
+                cyclic.push((subgroup, vec![i]));
+            }
+        }
+        let mut subgroups = cyclic.clone();
+        let mut i = 0;
+        while i < subgroups.len() {
+            for (c, generator) in cyclic.iter() {
+                if c.iter().all(|k| subgroups[i].0.binary_search(k).is_ok()) {
+                    continue;
+                }
+                let generators = [subgroups[i].1.as_slice(), generator].concat();
+                let join = generate(self.order(), identity, &generators, |i, j| table[i][j]);
+                if !subgroups.iter().any(|(s, _)| *s == join) {
+                    subgroups.push((join, generators));
+                }
+            }
+            i += 1;
+        }
+        let mut subgroups: Vec<Vec<usize>> = subgroups.into_iter().map(|(s, _)| s).collect();
+        subgroups.sort_by(|a, b| a.len().cmp(&b.len()).then(a.cmp(b)));
+        subgroups
+    }
+
+    /// The proper subgroups not contained in any other proper subgroup
+    pub fn maximal_subgroups(&self) -> Vec<Vec<usize>> {
+        let proper: Vec<Vec<usize>> = self
+            .subgroups()
+            .into_iter()
+            .filter(|s| s.len() < self.order())
+            .collect();
+        proper
+            .iter()
+            .filter(|s| {
+                !proper
+                    .iter()
+                    .any(|t| t.len() > s.len() && s.iter().all(|k| t.binary_search(k).is_ok()))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// The subgroup `gHg⁻¹` as sorted indices
+    pub fn conjugate_subgroup(&self, subgroup: &[usize], g: usize) -> Vec<usize> {
+        let inverse = self.inverse(g);
+        let mut conjugate: Vec<usize> = subgroup
+            .iter()
+            .map(|&h| self.product(self.product(g, h), inverse))
+            .collect();
+        conjugate.sort();
+        conjugate
+    }
+
+    /// Left cosets `gH` of the subgroup as sorted indices of this group, ordered by their
+    /// first element, which serves as the coset representative.
+    /// Returns `None` when an operation of `subgroup` is not in this group.
+    pub fn left_cosets(&self, subgroup: &SymmetryGroup) -> Option<Vec<Vec<usize>>> {
+        self.cosets(subgroup, |g, h| self.product(g, h))
+    }
+
+    /// Right cosets `Hg` of the subgroup, see `left_cosets`
+    pub fn right_cosets(&self, subgroup: &SymmetryGroup) -> Option<Vec<Vec<usize>>> {
+        self.cosets(subgroup, |g, h| self.product(h, g))
+    }
+
+    /// Index of the subgroup, `|G| / |H|`.
+    /// Returns `None` when an operation of `subgroup` is not in this group.
+    pub fn subgroup_index(&self, subgroup: &SymmetryGroup) -> Option<usize> {
+        self.left_cosets(subgroup).map(|cosets| cosets.len())
+    }
+
+    fn cosets<F>(&self, subgroup: &SymmetryGroup, multiply: F) -> Option<Vec<Vec<usize>>>
+    where
+        F: Fn(usize, usize) -> usize,
+    {
+        let members: Vec<usize> = subgroup
+            .operations()
+            .iter()
+            .map(|m| self.position(m))
+            .collect::<Option<Vec<usize>>>()?;
+        let mut assigned = vec![false; self.order()];
+        let mut cosets = Vec::new();
+        for g in 0..self.order() {
+            if assigned[g] {
+                continue;
+            }
+            let mut coset: Vec<usize> = members.iter().map(|&h| multiply(g, h)).collect();
+            coset.sort();
+            coset.iter().for_each(|&k| assigned[k] = true);
+            cosets.push(coset);
+        }
+        Some(cosets)
+    }
+}
+
+/// Closure of the generators by the multiplication, as sorted indices
+fn generate<F>(order: usize, identity: usize, generators: &[usize], multiply: F) -> Vec<usize>
+where
+    F: Fn(usize, usize) -> usize,
+{
+    let mut members = vec![false; order];
+    let mut elements = vec![identity];
+    members[identity] = true;
+    let mut i = 0;
+    while i < elements.len() {
+        for &g in generators {
+            let product = multiply(elements[i], g);
+            if !members[product] {
+                members[product] = true;
+                elements.push(product);
+            }
+        }
+        i += 1;
+    }
+    elements.sort();
+    elements
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{group::SymmetryGroup, HallSymbolNotation, SeitzMatrix};
+
+    #[test]
+    fn cayley_table_and_cosets() {
+        // P 4 m m, point group 4mm
+        let positions = HallSymbolNotation::try_from_str("P 4 -2")
+            .unwrap()
+            .general_positions();
+        let group = SymmetryGroup::try_from(&positions).unwrap();
+        let table = group.multiplication_table();
+        assert_eq!(8, table.len());
+        // Every row of the table is a permutation
+        table.iter().for_each(|row| {
+            let mut row = row.clone();
+            row.sort();
+            assert_eq!((0..8).collect::<Vec<usize>>(), row);
+        });
+        let mut orders = group.element_orders();
+        orders.sort();
+        assert_eq!(vec![1, 2, 2, 2, 2, 2, 4, 4], orders);
+        (0..8).for_each(|i| assert_eq!(i, group.inverse(group.inverse(i))));
+        let mut class_sizes: Vec<usize> =
+            group.conjugacy_classes().iter().map(|c| c.len()).collect();
+        class_sizes.sort();
+        assert_eq!(vec![1, 1, 2, 2, 2], class_sizes);
+
+        // The mirror subgroup `m` is not normal in 4mm
+        let mirror =
+            SymmetryGroup::try_new(&SeitzMatrix::try_from_xyz_list("x,y,z; -x,y,z").unwrap())
+                .unwrap();
+        let left = group.left_cosets(&mirror).unwrap();
+        let right = group.right_cosets(&mirror).unwrap();
+        assert_eq!(4, left.len());
+        assert_eq!(Some(4), group.subgroup_index(&mirror));
+        assert!(left.iter().any(|coset| !right.contains(coset)));
+        // The subgroup 4 is normal
+        let four = SymmetryGroup::try_new(
+            &SeitzMatrix::try_from_xyz_list("x,y,z; -y,x,z; -x,-y,z; y,-x,z").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            group.left_cosets(&four).unwrap(),
+            group.right_cosets(&four).unwrap()
+        );
+        let inversion =
+            SymmetryGroup::try_new(&[SeitzMatrix::identity(), SeitzMatrix::inversion()]).unwrap();
+        assert!(group.left_cosets(&inversion).is_none());
+
+        // 1, four mirrors, 2, 4, two mm2 and 4mm itself
+        let subgroups = group.subgroups();
+        assert_eq!(10, subgroups.len());
+        assert_eq!(vec![group.identity()], subgroups[0]);
+        assert_eq!(8, subgroups[9].len());
+        let mut maximal_orders: Vec<usize> =
+            group.maximal_subgroups().iter().map(|s| s.len()).collect();
+        maximal_orders.sort();
+        assert_eq!(vec![4, 4, 4], maximal_orders);
+        // The order-2 subgroups fall into the classes of 2, of m and of the diagonal m
+        let mut classes: Vec<Vec<Vec<usize>>> = subgroups
+            .iter()
+            .filter(|s| s.len() == 2)
+            .map(|s| {
+                let mut class: Vec<Vec<usize>> =
+                    (0..8).map(|g| group.conjugate_subgroup(s, g)).collect();
+                class.sort();
+                class.dedup();
+                class
+            })
+            .collect();
+        classes.sort();
+        classes.dedup();
+        let mut class_sizes: Vec<usize> = classes.iter().map(|c| c.len()).collect();
+        class_sizes.sort();
+        assert_eq!(vec![1, 2, 2], class_sizes);
+    }
+}
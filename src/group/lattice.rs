@@ -0,0 +1,276 @@
+use std::sync::OnceLock;
+
+use fraction::{Signed, Zero};
+use nalgebra::{Matrix3, Vector3};
+
+use crate::hall_symbols::{
+    change_of_basis::{determinant, inverse, to_units, unimodular_matrices},
+    SEITZ_TRANSLATE_BASE_NUMBER,
+};
+
+use super::{affine::rational_matrix, Rational};
+
+/// A lattice of translations, with the basis vectors as the columns in the fractional
+/// coordinates of the cell of the group
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Lattice {
+    basis: Matrix3<Rational>,
+    inverse: Matrix3<Rational>,
+}
+
+impl Lattice {
+    /// The lattice spanned by the basis vectors, which are replaced by a reduced basis
+    /// with a positive determinant
+    pub(super) fn new(basis: Matrix3<Rational>) -> Self {
+        let basis = reduced_basis(basis);
+        Self {
+            basis,
+            inverse: inverse(&basis),
+        }
+    }
+
+    /// The integer lattice with the centring translations in the unit of `1/12`
+    pub(super) fn from_centrings(centrings: &[Vector3<i32>]) -> Self {
+        let base = SEITZ_TRANSLATE_BASE_NUMBER as i64;
+        let mut generators: Vec<Vector3<i64>> = (0..3).map(|i| Vector3::ith(i, base)).collect();
+        generators.extend(centrings.iter().map(|c| c.map(|v| v as i64)));
+        Self::new(integer_basis(generators).map(|v| Rational::new(v, base)))
+    }
+
+    pub(super) fn basis(&self) -> &Matrix3<Rational> {
+        &self.basis
+    }
+
+    pub(super) fn contains(&self, translation: &Vector3<Rational>) -> bool {
+        (self.inverse * translation).iter().all(|v| v.is_integer())
+    }
+
+    pub(super) fn is_sublattice_of(&self, other: &Lattice) -> bool {
+        self.basis
+            .column_iter()
+            .all(|column| other.contains(&column.into_owned()))
+    }
+
+    /// The translation reduced into the primitive cell spanned by the basis
+    pub(super) fn reduce(&self, translation: &Vector3<Rational>) -> Vector3<Rational> {
+        let coordinates = self.inverse * translation;
+        self.basis * coordinates.map(|v| v - v.floor())
+    }
+
+    /// The equivalent translation with the fewest and the smallest components
+    pub(super) fn smallest_equivalent(&self, translation: &Vector3<Rational>) -> Vector3<Rational> {
+        let reduced = self.reduce(translation);
+        let mut candidates = Vec::with_capacity(27);
+        for i in -1..=1 {
+            for j in -1..=1 {
+                for k in -1..=1 {
+                    let shift = Vector3::new(i, j, k).map(Rational::from_integer);
+                    candidates.push(reduced - self.basis * shift);
+                }
+            }
+        }
+        candidates
+            .into_iter()
+            .min_by_key(|v| {
+                (
+                    v.iter().filter(|x| !x.is_zero()).count(),
+                    v.iter().map(|x| x.abs()).sum::<Rational>(),
+                    v.iter().filter(|x| **x < Rational::zero()).count(),
+                )
+            })
+            .unwrap()
+    }
+
+    /// Coordinates of the translation in the basis
+    pub(super) fn coordinates(&self, translation: &Vector3<Rational>) -> Vector3<Rational> {
+        self.inverse * translation
+    }
+
+    /// The rotation in the basis, `B⁻¹WB`, or `None` when the lattice is not invariant
+    pub(super) fn rotation_in_basis(&self, rotation: &Matrix3<i32>) -> Option<Matrix3<i32>> {
+        let w = rotation.map(|v| Rational::from_integer(v as i64));
+        to_units(&(self.inverse * w * self.basis), 1)
+    }
+
+    /// The sublattices of the given index. Their bases are `BM` with the matrices `M`
+    /// in the Hermite normal form.
+    pub(super) fn sublattices(&self, index: i64) -> Vec<Lattice> {
+        let mut sublattices = Vec::new();
+        for a in (1..=index).filter(|a| index % a == 0) {
+            for c in (1..=index / a).filter(|c| (index / a) % c == 0) {
+                let f = index / a / c;
+                for b in 0..a {
+                    for d in 0..a {
+                        for e in 0..c {
+                            let m =
+                                Matrix3::new(a, b, d, 0, c, e, 0, 0, f).map(Rational::from_integer);
+                            sublattices.push(Lattice::new(self.basis * m));
+                        }
+                    }
+                }
+            }
+        }
+        sublattices
+    }
+
+    /// Representatives of the translations of this lattice modulo the sublattice,
+    /// the zero vector first
+    pub(super) fn coset_translations(&self, sublattice: &Lattice) -> Vec<Vector3<Rational>> {
+        let mut translations = vec![Vector3::zeros()];
+        let mut i = 0;
+        while i < translations.len() {
+            for column in self.basis.column_iter() {
+                let t = sublattice.reduce(&(translations[i] + column));
+                if !translations.contains(&t) {
+                    translations.push(t);
+                }
+            }
+            i += 1;
+        }
+        translations
+    }
+}
+
+/// Three independent vectors spanning the same lattice as the generators, found by the
+/// Euclidean algorithm on one coordinate after another
+fn integer_basis(mut generators: Vec<Vector3<i64>>) -> Matrix3<i64> {
+    let mut basis = Vec::with_capacity(3);
+    for row in 0..3 {
+        loop {
+            let nonzero: Vec<usize> = (0..generators.len())
+                .filter(|&i| generators[i][row] != 0)
+                .collect();
+            if nonzero.len() <= 1 {
+                if let Some(&i) = nonzero.first() {
+                    basis.push(generators.remove(i));
+                }
+                break;
+            }
+            let pivot = *nonzero
+                .iter()
+                .min_by_key(|&&i| generators[i][row].abs())
+                .unwrap();
+            let pivot_vector = generators[pivot];
+            for &i in nonzero.iter().filter(|&&i| i != pivot) {
+                let q = generators[i][row] / pivot_vector[row];
+                generators[i] -= pivot_vector * q;
+            }
+        }
+    }
+    Matrix3::from_columns(&basis)
+}
+
+/// Shorten the basis vectors by adding and subtracting the others while any gets shorter
+/// in the fractional coordinates, and make the determinant positive.
+fn reduced_basis(basis: Matrix3<Rational>) -> Matrix3<Rational> {
+    let norm = |v: &Vector3<Rational>| v.iter().map(|x| *x * *x).sum::<Rational>();
+    let mut vectors: Vec<Vector3<Rational>> = basis.column_iter().map(|c| c.into_owned()).collect();
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for i in 0..3 {
+            for j in (0..3).filter(|&j| j != i) {
+                for candidate in [vectors[i] - vectors[j], vectors[i] + vectors[j]] {
+                    if norm(&candidate) < norm(&vectors[i]) {
+                        vectors[i] = candidate;
+                        improved = true;
+                    }
+                }
+            }
+        }
+    }
+    vectors.sort_by_key(norm);
+    let mut reduced = Matrix3::from_columns(&vectors);
+    if determinant(&reduced) < Rational::zero() {
+        reduced.set_column(2, &-reduced.column(2).into_owned());
+    }
+    reduced
+}
+
+/// The unimodular matrices `U` with their inverses, prepared once on demand
+pub(super) fn unimodular_pairs() -> &'static [(Matrix3<i32>, Matrix3<i32>)] {
+    static PAIRS: OnceLock<Vec<(Matrix3<i32>, Matrix3<i32>)>> = OnceLock::new();
+    PAIRS.get_or_init(|| {
+        unimodular_matrices()
+            .into_iter()
+            .map(|u| {
+                (
+                    u,
+                    inverse(&rational_matrix(&u)).map(|v| v.to_integer() as i32),
+                )
+            })
+            .collect()
+    })
+}
+
+/// Solutions `q` modulo the integer lattice of the congruences `Aq ≡ b (mod 1)`.
+/// `A` is brought into the diagonal form by the unimodular row and column operations, and
+/// the directions left free are fixed to zero.
+pub(super) fn solve_congruences(
+    mut rows: Vec<[i64; 3]>,
+    mut constants: Vec<Rational>,
+) -> Vec<Vector3<Rational>> {
+    let m = rows.len();
+    let mut columns = Matrix3::<i64>::identity();
+    let mut rank = 0;
+    while rank < 3 {
+        let t = rank;
+        let pivot = (t..m)
+            .flat_map(|i| (t..3).map(move |j| (i, j)))
+            .filter(|&(i, j)| rows[i][j] != 0)
+            .min_by_key(|&(i, j)| rows[i][j].abs());
+        let Some((i, j)) = pivot else {
+            break;
+        };
+        rows.swap(t, i);
+        constants.swap(t, i);
+        rows.iter_mut().for_each(|row| row.swap(t, j));
+        columns.swap_columns(t, j);
+        let mut reduced = true;
+        for i in t + 1..m {
+            let q = rows[i][t] / rows[t][t];
+            if q != 0 {
+                let pivot_row = rows[t];
+                (0..3).for_each(|j| rows[i][j] -= q * pivot_row[j]);
+                constants[i] = constants[i] - constants[t] * Rational::from_integer(q);
+            }
+            reduced &= rows[i][t] == 0;
+        }
+        for j in t + 1..3 {
+            let q = rows[t][j] / rows[t][t];
+            if q != 0 {
+                rows.iter_mut().for_each(|row| row[j] -= q * row[t]);
+                let column = columns.column(t) * q;
+                columns
+                    .column_mut(j)
+                    .iter_mut()
+                    .zip(column.iter())
+                    .for_each(|(c, v)| *c -= v);
+            }
+            reduced &= rows[t][j] == 0;
+        }
+        // Otherwise a smaller remainder becomes the next pivot at the same position
+        if reduced {
+            rank += 1;
+        }
+    }
+    if constants[rank..].iter().any(|c| !c.is_integer()) {
+        return Vec::new();
+    }
+    let mut solutions = vec![Vector3::<Rational>::zeros()];
+    for t in 0..rank {
+        let (d, c) = (rows[t][t], constants[t]);
+        solutions = solutions
+            .iter()
+            .flat_map(|y| {
+                (0..d.abs()).map(move |k| {
+                    let mut y = *y;
+                    y[t] = (c + Rational::from_integer(k)) / Rational::from_integer(d);
+                    y
+                })
+            })
+            .collect();
+    }
+    let columns = columns.map(Rational::from_integer);
+    solutions.iter().map(|y| columns * y).collect()
+}
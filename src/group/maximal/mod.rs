@@ -0,0 +1,556 @@
+//! Maximal subgroups and minimal supergroups of the space groups.
+//!
+//! The maximal subgroups are either translationengleiche (t), keeping all the
+//! translations and losing a part of the point group, or klassengleiche (k), keeping the
+//! point group and losing a part of the translations. The k-subgroups are enumerated
+//! up to the index 4, so the infinite series of the isomorphic subgroups of larger
+//! indices are not listed.
+
+use std::sync::OnceLock;
+
+use nalgebra::Matrix3;
+
+use crate::{
+    database::{tabulated_general_positions, PointGroup, SpaceGroupEntry, SpaceGroupHallSymbol},
+    ChangeOfBasis, GeneralPositions, GroupGenerationError, HallSymbolNotation, SeitzMatrix,
+};
+
+use self::standard::identify_subgroup;
+
+use super::{
+    affine::{Operation, Subgroup},
+    lattice::Lattice,
+    SymmetryGroup,
+};
+
+mod standard;
+
+/// The indices of the sublattices searched for the k-subgroups
+const KLASSENGLEICHE_INDICES: [i64; 3] = [2, 3, 4];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SubgroupKind {
+    /// Same translations, smaller point group
+    Translationengleiche,
+    /// Same point group, fewer translations
+    Klassengleiche,
+}
+
+/// A conjugacy class of maximal subgroups, represented by one of them
+#[derive(Debug, Clone)]
+pub struct MaximalSubgroup {
+    kind: SubgroupKind,
+    index: usize,
+    conjugates: usize,
+    operations: Vec<SeitzMatrix>,
+    translation_basis: Matrix3<f64>,
+    standard: Option<(SpaceGroupHallSymbol, ChangeOfBasis)>,
+}
+
+impl MaximalSubgroup {
+    pub fn kind(&self) -> SubgroupKind {
+        self.kind
+    }
+
+    /// Index of the subgroup in the group
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Number of the subgroups in the conjugacy class
+    pub fn conjugates(&self) -> usize {
+        self.conjugates
+    }
+
+    /// Representatives of the operations modulo the translations of the subgroup,
+    /// in the basis of the group
+    pub fn operations(&self) -> &[SeitzMatrix] {
+        &self.operations
+    }
+
+    /// Primitive basis vectors of the translations of the subgroup as the columns,
+    /// in the basis of the group
+    pub fn translation_basis(&self) -> Matrix3<f64> {
+        self.translation_basis
+    }
+
+    /// The space group in its default setting.
+    /// Returns `None` when the transformation to it is not found.
+    pub fn symbol(&self) -> Option<SpaceGroupHallSymbol> {
+        self.standard.map(|(symbol, _)| symbol)
+    }
+
+    /// The change of basis from the group to the default setting of the subgroup
+    pub fn change_of_basis(&self) -> Option<ChangeOfBasis> {
+        self.standard.map(|(_, cob)| cob)
+    }
+}
+
+/// A space group in which the group is a maximal subgroup
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinimalSupergroup {
+    kind: SubgroupKind,
+    index: usize,
+    symbol: SpaceGroupHallSymbol,
+    change_of_basis: ChangeOfBasis,
+}
+
+impl MinimalSupergroup {
+    pub fn kind(&self) -> SubgroupKind {
+        self.kind
+    }
+
+    /// Index of the group in the supergroup
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The supergroup in its default setting
+    pub fn symbol(&self) -> SpaceGroupHallSymbol {
+        self.symbol
+    }
+
+    /// The change of basis from the group to the default setting of the supergroup
+    pub fn change_of_basis(&self) -> ChangeOfBasis {
+        self.change_of_basis
+    }
+}
+
+impl GeneralPositions {
+    /// The conjugacy classes of the maximal t-subgroups, followed by those of the
+    /// maximal k-subgroups up to the index 4
+    pub fn maximal_subgroups(&self) -> Vec<MaximalSubgroup> {
+        subgroup_classes(self, true, true)
+            .into_iter()
+            .map(|(kind, index, class)| {
+                let representative = &class[0];
+                let basis = representative.lattice().basis();
+                MaximalSubgroup {
+                    kind,
+                    index,
+                    conjugates: class.len(),
+                    operations: representative
+                        .operations()
+                        .iter()
+                        .map(|op| op.to_seitz_matrix())
+                        .collect(),
+                    translation_basis: basis.map(|v| *v.numer() as f64 / *v.denom() as f64),
+                    standard: identify_subgroup(representative, None),
+                }
+            })
+            .collect()
+    }
+}
+
+impl HallSymbolNotation {
+    /// The maximal subgroups of the group, see `GeneralPositions::maximal_subgroups`.
+    /// Fails when the symbol does not generate a group.
+    pub fn maximal_subgroups(&self) -> Result<Vec<MaximalSubgroup>, GroupGenerationError> {
+        Ok(self.try_general_positions()?.maximal_subgroups())
+    }
+}
+
+impl SpaceGroupHallSymbol {
+    /// The maximal subgroups of the space group in this setting, see
+    /// `GeneralPositions::maximal_subgroups`
+    pub fn maximal_subgroups(&self) -> Vec<MaximalSubgroup> {
+        HallSymbolNotation::from(*self)
+            .general_positions()
+            .maximal_subgroups()
+    }
+
+    /// The space groups in which this group is a maximal subgroup, found from the maximal
+    /// subgroups of the default settings. The change of basis is from this setting.
+    /// The supergroups are searched once for each default setting.
+    pub fn minimal_supergroups(&self) -> Vec<MinimalSupergroup> {
+        let default = self.entry().default_setting();
+        let Some(to_default) = self.change_of_basis_to(&default) else {
+            return Vec::new();
+        };
+        default_minimal_supergroups(default)
+            .iter()
+            .filter_map(|supergroup| {
+                Some(MinimalSupergroup {
+                    change_of_basis: to_default.then(&supergroup.change_of_basis)?,
+                    ..*supergroup
+                })
+            })
+            .collect()
+    }
+}
+
+/// The minimal supergroups of a default setting, with the change of basis from it
+fn default_minimal_supergroups(default: SpaceGroupHallSymbol) -> &'static [MinimalSupergroup] {
+    static SUPERGROUPS: OnceLock<Vec<OnceLock<Vec<MinimalSupergroup>>>> = OnceLock::new();
+    SUPERGROUPS.get_or_init(|| {
+        SpaceGroupHallSymbol::all()
+            .map(|_| OnceLock::new())
+            .collect()
+    })[default as usize]
+        .get_or_init(|| {
+            let core = tabulated_general_positions()[default as usize].core_position_set();
+            let point_group = PointGroup::from_operations(core);
+            let mut supergroups = Vec::new();
+            for entry in SpaceGroupEntry::all().filter(|entry| entry.is_default_setting()) {
+                let candidate =
+                    tabulated_general_positions()[entry.symbol() as usize].core_position_set();
+                // The point group is kept by the k-subgroups and reduced by the t-subgroups
+                let klassengleiche = candidate.len() == core.len()
+                    && PointGroup::from_operations(candidate) == point_group;
+                let translationengleiche = candidate.len().is_multiple_of(core.len())
+                    && (2..=4).contains(&(candidate.len() / core.len()));
+                if !klassengleiche && !translationengleiche {
+                    continue;
+                }
+                let kinds = [
+                    (SubgroupKind::Translationengleiche, translationengleiche),
+                    (SubgroupKind::Klassengleiche, klassengleiche),
+                ];
+                for kind in kinds
+                    .into_iter()
+                    .filter_map(|(kind, wanted)| wanted.then_some(kind))
+                {
+                    for (index, subgroup) in default_subgroup_classes(entry.symbol(), kind) {
+                        let Some((_, cob)) = identify_subgroup(subgroup, Some(default)) else {
+                            continue;
+                        };
+                        let Some(change_of_basis) = cob.inverse() else {
+                            continue;
+                        };
+                        supergroups.push(MinimalSupergroup {
+                            kind,
+                            index: *index,
+                            symbol: entry.symbol(),
+                            change_of_basis,
+                        });
+                    }
+                }
+            }
+            supergroups
+        })
+}
+
+/// A representative of each conjugacy class of the maximal subgroups of a kind of a
+/// default setting, kept for the searches of the supergroups of the other groups
+fn default_subgroup_classes(
+    default: SpaceGroupHallSymbol,
+    kind: SubgroupKind,
+) -> &'static [(usize, Subgroup)] {
+    type Classes = [OnceLock<Vec<(usize, Subgroup)>>; 2];
+    static CLASSES: OnceLock<Vec<Classes>> = OnceLock::new();
+    let translationengleiche = kind == SubgroupKind::Translationengleiche;
+    CLASSES.get_or_init(|| {
+        SpaceGroupHallSymbol::all()
+            .map(|_| Default::default())
+            .collect()
+    })[default as usize][translationengleiche as usize]
+        .get_or_init(|| {
+            subgroup_classes(
+                &tabulated_general_positions()[default as usize],
+                translationengleiche,
+                !translationengleiche,
+            )
+            .into_iter()
+            .map(|(_, index, mut class)| (index, class.swap_remove(0)))
+            .collect()
+        })
+}
+
+/// The conjugacy classes of the maximal t- and k-subgroups of the requested kinds
+fn subgroup_classes(
+    positions: &GeneralPositions,
+    translationengleiche: bool,
+    klassengleiche: bool,
+) -> Vec<(SubgroupKind, usize, Vec<Subgroup>)> {
+    let quotient = SymmetryGroup::try_from(positions).expect("General positions form a group");
+    let lattice = Lattice::from_centrings(positions.lattice_translations());
+    let operations: Vec<Operation> = quotient
+        .operations()
+        .iter()
+        .map(Operation::from_seitz_matrix)
+        .collect();
+    let mut classes = Vec::new();
+    if translationengleiche {
+        classes.extend(translationengleiche_classes(
+            &quotient,
+            &operations,
+            &lattice,
+        ));
+    }
+    if klassengleiche {
+        classes.extend(klassengleiche_classes(&quotient, &operations, &lattice));
+    }
+    classes
+}
+
+/// Maximal subgroups of the factor group by the translations, grouped by the conjugacy
+fn translationengleiche_classes(
+    quotient: &SymmetryGroup,
+    operations: &[Operation],
+    lattice: &Lattice,
+) -> Vec<(SubgroupKind, usize, Vec<Subgroup>)> {
+    let mut classes: Vec<Vec<Vec<usize>>> = Vec::new();
+    for subgroup in quotient.maximal_subgroups() {
+        if classes.iter().any(|class| class.contains(&subgroup)) {
+            continue;
+        }
+        let mut class: Vec<Vec<usize>> = (0..quotient.order())
+            .map(|g| quotient.conjugate_subgroup(&subgroup, g))
+            .collect();
+        class.sort();
+        class.dedup();
+        // The subgroup itself represents the class
+        class.retain(|c| *c != subgroup);
+        class.insert(0, subgroup);
+        classes.push(class);
+    }
+    classes
+        .into_iter()
+        .map(|class| {
+            let index = quotient.order() / class[0].len();
+            let subgroups = class
+                .iter()
+                .map(|members| {
+                    let generators: Vec<Operation> =
+                        members.iter().map(|&i| operations[i].clone()).collect();
+                    Subgroup::generate(&generators, lattice, members.len())
+                        .expect("Subgroups of the factor group are closed")
+                })
+                .collect();
+            (SubgroupKind::Translationengleiche, index, subgroups)
+        })
+        .collect()
+}
+
+/// Subgroups with the full point group and the translations of the sublattices of small
+/// indices invariant under the point group, grouped by the conjugacy. The operations of a
+/// subgroup are the generators of the point group shifted by the translations of the
+/// lattice modulo the sublattice, such that no further translation is generated.
+fn klassengleiche_classes(
+    quotient: &SymmetryGroup,
+    operations: &[Operation],
+    lattice: &Lattice,
+) -> Vec<(SubgroupKind, usize, Vec<Subgroup>)> {
+    let generators = generating_set(quotient);
+    // Conjugation by the generators of the group and the translations of the lattice
+    let conjugators: Vec<Operation> = generators
+        .iter()
+        .map(|&g| operations[g].clone())
+        .chain(
+            lattice
+                .basis()
+                .column_iter()
+                .map(|column| Operation::translation(column.into_owned())),
+        )
+        .collect();
+    // (index, class, whether maximal)
+    let mut found: Vec<(i64, Vec<Subgroup>, bool)> = Vec::new();
+    for index in KLASSENGLEICHE_INDICES {
+        for sublattice in lattice.sublattices(index) {
+            if !operations
+                .iter()
+                .all(|op| sublattice.rotation_in_basis(&op.rotation).is_some())
+            {
+                continue;
+            }
+            let translations = lattice.coset_translations(&sublattice);
+            for choice in 0..translations.len().pow(generators.len() as u32) {
+                let mut code = choice;
+                let shifted: Vec<Operation> = generators
+                    .iter()
+                    .map(|&g| {
+                        let t = &translations[code % translations.len()];
+                        code /= translations.len();
+                        operations[g].then_translate(t)
+                    })
+                    .collect();
+                let Some(subgroup) = Subgroup::generate(&shifted, &sublattice, quotient.order())
+                else {
+                    continue;
+                };
+                if found.iter().any(|(_, class, _)| class.contains(&subgroup)) {
+                    continue;
+                }
+                // The subgroups of a prime index are maximal, the others are unless they
+                // lie in a subgroup of a smaller index
+                let maximal = !found
+                    .iter()
+                    .any(|(i, class, _)| *i < index && class.iter().any(|s| s.contains(&subgroup)));
+                found.push((index, conjugacy_class(subgroup, &conjugators), maximal));
+            }
+        }
+    }
+    found
+        .into_iter()
+        .filter(|(_, _, maximal)| *maximal)
+        .map(|(index, class, _)| (SubgroupKind::Klassengleiche, index as usize, class))
+        .collect()
+}
+
+/// The subgroups `gHg⁻¹` for the products `g` of the conjugators
+fn conjugacy_class(subgroup: Subgroup, conjugators: &[Operation]) -> Vec<Subgroup> {
+    let mut class = vec![subgroup];
+    let mut i = 0;
+    while i < class.len() {
+        for g in conjugators {
+            let conjugate = class[i].conjugate(g);
+            if !class.contains(&conjugate) {
+                class.push(conjugate);
+            }
+        }
+        i += 1;
+    }
+    class
+}
+
+/// Few operations generating the group, each adding the most new elements
+fn generating_set(group: &SymmetryGroup) -> Vec<usize> {
+    let mut generators = Vec::new();
+    let mut generated = group.generated_subgroup(&generators);
+    while generated.len() < group.order() {
+        let best = (0..group.order())
+            .filter(|i| generated.binary_search(i).is_err())
+            .max_by_key(|&i| {
+                let mut candidate = generators.clone();
+                candidate.push(i);
+                group.generated_subgroup(&candidate).len()
+            })
+            .expect("A proper subgroup misses some element");
+        generators.push(best);
+        generated = group.generated_subgroup(&generators);
+    }
+    generators
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        database::{SpaceGroupEntry, SpaceGroupHallSymbol},
+        HallSymbolNotation,
+    };
+
+    use super::SubgroupKind;
+
+    fn default_setting(number: u8) -> SpaceGroupHallSymbol {
+        SpaceGroupEntry::settings_of(number)
+            .find(|entry| entry.is_default_setting())
+            .unwrap()
+            .symbol()
+    }
+
+    #[test]
+    fn maximal_subgroups_of_pm3m() {
+        let group = default_setting(221);
+        let subgroups = group.maximal_subgroups();
+        // (kind, index, number of conjugates, space group number)
+        let mut found: Vec<(SubgroupKind, usize, usize, u8)> = subgroups
+            .iter()
+            .map(|s| {
+                (
+                    s.kind(),
+                    s.index(),
+                    s.conjugates(),
+                    s.symbol().unwrap().entry().number(),
+                )
+            })
+            .collect();
+        found.sort_by_key(|(kind, index, _, number)| {
+            (*kind == SubgroupKind::Klassengleiche, *index, *number)
+        });
+        use SubgroupKind::*;
+        assert_eq!(
+            vec![
+                (Translationengleiche, 2, 1, 200),
+                (Translationengleiche, 2, 1, 207),
+                (Translationengleiche, 2, 1, 215),
+                (Translationengleiche, 3, 3, 123),
+                (Translationengleiche, 4, 4, 166),
+                (Klassengleiche, 2, 1, 225),
+                (Klassengleiche, 2, 1, 225),
+                (Klassengleiche, 2, 1, 226),
+                (Klassengleiche, 2, 1, 226),
+                (Klassengleiche, 4, 4, 229),
+                (Klassengleiche, 4, 4, 229),
+            ],
+            found
+        );
+
+        // The operations of each subgroup are those of the default setting
+        for subgroup in subgroups.iter() {
+            let cob = subgroup.change_of_basis().unwrap();
+            let standard = HallSymbolNotation::from(subgroup.symbol().unwrap()).general_positions();
+            assert_eq!(standard.num_of_general_pos(), subgroup.operations().len());
+            assert!(
+                subgroup.operations().iter().all(|m| cob
+                    .transform_seitz_matrix(m)
+                    .is_some_and(|transformed| standard.contains(&transformed))),
+                "{cob}"
+            );
+        }
+        let r3m = subgroups
+            .iter()
+            .find(|s| s.index() == 4 && s.kind() == Translationengleiche)
+            .unwrap();
+        assert_eq!(12, r3m.operations().len());
+        let fm3m = subgroups
+            .iter()
+            .find(|s| s.kind() == Klassengleiche)
+            .unwrap();
+        assert_eq!("2a,2b,2c", format!("{}", fm3m.change_of_basis().unwrap()));
+        assert_eq!(8.0, fm3m.translation_basis().determinant().abs() * 4.0);
+    }
+
+    #[test]
+    fn maximal_subgroups_of_monoclinic_and_trigonal() {
+        // P 1 21/c 1: P 1 21 1, P 1 c 1 and P -1, then the isomorphic ones
+        let subgroups = default_setting(14).maximal_subgroups();
+        let t: Vec<u8> = subgroups
+            .iter()
+            .filter(|s| s.kind() == SubgroupKind::Translationengleiche)
+            .map(|s| s.symbol().unwrap().entry().number())
+            .collect();
+        assert_eq!(vec![4, 7, 2], t);
+        assert!(subgroups
+            .iter()
+            .filter(|s| s.kind() == SubgroupKind::Klassengleiche)
+            .all(|s| s.symbol().unwrap().entry().number() == 14));
+        // R -3 m: the decentred P -3 m 1 and R -3 c with the doubled c axis
+        let subgroups = default_setting(166).maximal_subgroups();
+        let k: Vec<(usize, u8, String)> = subgroups
+            .iter()
+            .filter(|s| s.kind() == SubgroupKind::Klassengleiche)
+            .map(|s| {
+                (
+                    s.index(),
+                    s.symbol().unwrap().entry().number(),
+                    format!("{}", s.change_of_basis().unwrap()),
+                )
+            })
+            .collect();
+        assert!(k.contains(&(3, 164, "a,b,c".to_string())));
+        assert!(k.contains(&(2, 167, "-a,-b,2c".to_string())));
+    }
+
+    #[test]
+    fn minimal_supergroups_of_pm3m() {
+        let supergroups = default_setting(221).minimal_supergroups();
+        let found: Vec<(SubgroupKind, usize, u8, String)> = supergroups
+            .iter()
+            .map(|s| {
+                (
+                    s.kind(),
+                    s.index(),
+                    s.symbol().entry().number(),
+                    format!("{}", s.change_of_basis()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                (SubgroupKind::Klassengleiche, 4, 225, "a,b,c".to_string()),
+                (SubgroupKind::Klassengleiche, 2, 229, "a,b,c".to_string()),
+            ],
+            found
+        );
+    }
+}
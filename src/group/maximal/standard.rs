@@ -0,0 +1,188 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::OnceLock,
+};
+
+use fraction::Integer;
+use nalgebra::{Matrix3, Vector3};
+
+use crate::{
+    database::{tabulated_general_positions, PointGroup, SpaceGroupEntry, SpaceGroupHallSymbol},
+    hall_symbols::{
+        change_of_basis::{inverse, to_units},
+        CHANGE_OF_BASIS_BASE_NUMBER, SEITZ_TRANSLATE_BASE_NUMBER,
+    },
+    ChangeOfBasis, SeitzMatrix,
+};
+
+use crate::group::{
+    affine::{rational_matrix, Subgroup},
+    lattice::{solve_congruences, unimodular_pairs, Lattice},
+    matrix_simplicity, simplicity, Rational,
+};
+
+/// A space group in the default setting, with its rotations in a primitive basis
+struct Standard {
+    symbol: SpaceGroupHallSymbol,
+    point_group: Option<PointGroup>,
+    lattice: Lattice,
+    /// Rotations in the primitive basis of the lattice
+    primitive_rotations: HashSet<Matrix3<i32>>,
+    /// Translation of the operation with the rotation, in the conventional basis
+    translations: HashMap<Matrix3<i32>, Vector3<Rational>>,
+    /// Positive integer multiple of the inverse of the primitive basis
+    inverse_basis_multiple: Matrix3<i64>,
+}
+
+/// The 230 space groups in the default settings, prepared once on demand
+fn standards() -> &'static [Standard] {
+    static STANDARDS: OnceLock<Vec<Standard>> = OnceLock::new();
+    STANDARDS.get_or_init(|| {
+        SpaceGroupEntry::all()
+            .filter(|entry| entry.is_default_setting())
+            .map(|entry| {
+                let positions = &tabulated_general_positions()[entry.symbol() as usize];
+                let lattice = Lattice::from_centrings(positions.lattice_translations());
+                let core = positions.core_position_set();
+                Standard {
+                    symbol: entry.symbol(),
+                    point_group: PointGroup::from_operations(core),
+                    primitive_rotations: core
+                        .iter()
+                        .filter_map(|m| lattice.rotation_in_basis(&m.rotation_part()))
+                        .collect(),
+                    translations: core
+                        .iter()
+                        .map(|m| {
+                            let t = m.translation_part().map(|v| {
+                                Rational::new(v as i64, SEITZ_TRANSLATE_BASE_NUMBER as i64)
+                            });
+                            (m.rotation_part(), t)
+                        })
+                        .collect(),
+                    inverse_basis_multiple: integer_multiple(&inverse(lattice.basis())),
+                    lattice,
+                }
+            })
+            .collect()
+    })
+}
+
+/// Find the space group type of the subgroup and the change of basis into its default
+/// setting, with the simplest matrix `P` and then the smallest origin shift `p`.
+///
+/// The matrices `P = B U B'⁻¹` map a primitive basis `B'` of the default setting onto the
+/// primitive basis `B` of the subgroup, through the unimodular matrices `U` with entries
+/// in `-1..=1`. The origin shift solves the congruences of the translations.
+/// The search is restricted to the `target` group when it is given.
+pub(super) fn identify_subgroup(
+    subgroup: &Subgroup,
+    target: Option<SpaceGroupHallSymbol>,
+) -> Option<(SpaceGroupHallSymbol, ChangeOfBasis)> {
+    let operations = subgroup.operations();
+    let lattice = subgroup.lattice();
+    let rotations: Vec<SeitzMatrix> = operations
+        .iter()
+        .map(|op| SeitzMatrix::new(op.rotation.to_homogeneous()))
+        .collect();
+    let point_group = PointGroup::from_operations(&rotations);
+    let in_basis: Vec<Matrix3<i32>> = operations
+        .iter()
+        .map(|op| lattice.rotation_in_basis(&op.rotation))
+        .collect::<Option<_>>()?;
+    let candidates: Vec<&Standard> = standards()
+        .iter()
+        .filter(|s| s.point_group == point_group && s.translations.len() == operations.len())
+        .filter(|s| target.is_none_or(|target| s.symbol == target))
+        .collect();
+    if candidates.is_empty() {
+        return None;
+    }
+    let basis_multiple = integer_multiple(lattice.basis());
+    let mut found: Option<(&Standard, ChangeOfBasis)> = None;
+    for (u, u_inv) in unimodular_pairs() {
+        let mut transformed: Vec<Matrix3<i32>> = Vec::with_capacity(in_basis.len());
+        for w in in_basis.iter() {
+            let w = u_inv * w * u;
+            if !candidates
+                .iter()
+                .any(|standard| standard.primitive_rotations.contains(&w))
+            {
+                break;
+            }
+            transformed.push(w);
+        }
+        if transformed.len() < in_basis.len() {
+            continue;
+        }
+        for standard in candidates.iter() {
+            if found.is_some_and(|(s, _)| s.symbol != standard.symbol) {
+                continue;
+            }
+            if !transformed
+                .iter()
+                .all(|w| standard.primitive_rotations.contains(w))
+            {
+                continue;
+            }
+            // The origin shift only decides between the equally simple matrices, and a
+            // positive multiple of `P` has the same simplicity
+            let p_matrix = basis_multiple * u.map(|v| v as i64) * standard.inverse_basis_multiple;
+            if found.is_some_and(|(_, best)| {
+                matrix_simplicity(&p_matrix) > matrix_simplicity(&best.matrix())
+            }) {
+                continue;
+            }
+            let Some(cob) = change_of_basis(subgroup, &transformed, u, standard) else {
+                continue;
+            };
+            if found.is_none_or(|(_, best)| simplicity(&cob) < simplicity(&best)) {
+                found = Some((standard, cob));
+            }
+        }
+    }
+    found.map(|(standard, cob)| (standard.symbol, cob))
+}
+
+/// The change of basis `(P, p)` with `P = B U B'⁻¹` and the smallest origin shift, if any
+fn change_of_basis(
+    subgroup: &Subgroup,
+    transformed: &[Matrix3<i32>],
+    u: &Matrix3<i32>,
+    standard: &Standard,
+) -> Option<ChangeOfBasis> {
+    let lattice = subgroup.lattice();
+    let bu = lattice.basis() * rational_matrix(u);
+    let p_matrix = bu * inverse(standard.lattice.basis());
+    let bu_inv = inverse(&bu);
+    // (W_p - I) q ≡ s_p - w_p (mod 1) in the primitive basis of the default setting
+    let mut rows = Vec::with_capacity(3 * transformed.len());
+    let mut constants = Vec::with_capacity(3 * transformed.len());
+    for (op, w_p) in subgroup.operations().iter().zip(transformed) {
+        let conventional = to_units(
+            &(standard.lattice.basis() * rational_matrix(w_p) * inverse(standard.lattice.basis())),
+            1,
+        )?;
+        let s = standard.translations.get(&conventional)?;
+        let constant = standard.lattice.coordinates(s) - bu_inv * op.translation;
+        let a = w_p - Matrix3::identity();
+        for i in 0..3 {
+            rows.push([a[(i, 0)], a[(i, 1)], a[(i, 2)]].map(|v| v as i64));
+            constants.push(constant[i]);
+        }
+    }
+    let p_units = to_units(&p_matrix, SEITZ_TRANSLATE_BASE_NUMBER)?;
+    solve_congruences(rows, constants)
+        .iter()
+        .filter_map(|q| {
+            let shift = lattice.smallest_equivalent(&(bu * q));
+            ChangeOfBasis::new(p_units, to_units(&shift, CHANGE_OF_BASIS_BASE_NUMBER)?)
+        })
+        .min_by_key(simplicity)
+}
+
+/// The matrix multiplied by the least common multiple of the denominators
+fn integer_multiple(m: &Matrix3<Rational>) -> Matrix3<i64> {
+    let multiple = m.iter().fold(1, |lcm, v| lcm.lcm(v.denom()));
+    m.map(|v| (v * Rational::from_integer(multiple)).to_integer())
+}
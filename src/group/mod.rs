@@ -0,0 +1,311 @@
+//! Finite groups of symmetry operations taken modulo the lattice translations.
+//!
+//! A list of operators, e.g. edited by hand, is checked against the group axioms
+//! before it is used, and the violated axioms are reported one by one.
+
+use std::{collections::HashMap, error::Error, fmt::Display};
+
+use fraction::Zero;
+use nalgebra::{Matrix3, Vector3};
+
+use crate::{
+    hall_symbols::{CHANGE_OF_BASIS_BASE_NUMBER, SEITZ_TRANSLATE_BASE_NUMBER},
+    utils::Rational,
+    ChangeOfBasis, GeneralPositions, SeitzMatrix,
+};
+
+pub use self::maximal::{MaximalSubgroup, MinimalSupergroup, SubgroupKind};
+pub use self::relation::{SubgroupRelation, SubgroupRelationError, MAX_LATTICE_INDEX};
+
+mod affine;
+mod cayley;
+mod lattice;
+mod maximal;
+mod relation;
+mod symmorphic;
+
+/// Rotation part and the canonical translation part of an operation
+type OperationKey = (Matrix3<i32>, Vector3<i32>);
+
+/// A group of `SeitzMatrix`, in which the operations differing by a lattice translation
+/// are the same element.
+#[derive(Debug, Clone)]
+pub struct SymmetryGroup {
+    operations: Vec<SeitzMatrix>,
+    /// Translations of the lattice within the unit cell, including the zero vector
+    lattice_translations: Vec<Vector3<i32>>,
+    index: HashMap<OperationKey, usize>,
+}
+
+/// A group axiom violated by a list of operations. The indices refer to the list.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GroupAxiomViolation {
+    MissingIdentity,
+    /// The two operations are the same modulo the lattice translations
+    Duplicate {
+        first: usize,
+        second: usize,
+    },
+    MissingInverse {
+        element: usize,
+    },
+    /// The product `left * right` falls outside the list
+    NotClosed {
+        left: usize,
+        right: usize,
+        product: SeitzMatrix,
+    },
+}
+
+impl Display for GroupAxiomViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GroupAxiomViolation::MissingIdentity => f.write_str("The identity is missing"),
+            GroupAxiomViolation::Duplicate { first, second } => {
+                write!(f, "Operations {first} and {second} are the same")
+            }
+            GroupAxiomViolation::MissingInverse { element } => {
+                write!(f, "The inverse of operation {element} is missing")
+            }
+            GroupAxiomViolation::NotClosed {
+                left,
+                right,
+                product,
+            } => write!(
+                f,
+                "The product of operations {left} and {right}, {}, is missing",
+                product.jones_faithful_repr()
+            ),
+        }
+    }
+}
+
+/// The operations do not form a group
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupAxiomError {
+    violations: Vec<GroupAxiomViolation>,
+}
+
+impl GroupAxiomError {
+    pub fn violations(&self) -> &[GroupAxiomViolation] {
+        &self.violations
+    }
+}
+
+impl Display for GroupAxiomError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The operations do not form a group ({} violations)",
+            self.violations.len()
+        )?;
+        self.violations
+            .iter()
+            .try_for_each(|violation| write!(f, "\n{violation}"))
+    }
+}
+
+impl Error for GroupAxiomError {}
+
+impl SymmetryGroup {
+    /// Build the group modulo the integer lattice translations, checking the closure,
+    /// the identity and the inverses.
+    pub fn try_new(operations: &[SeitzMatrix]) -> Result<Self, GroupAxiomError> {
+        Self::try_with_lattice(operations, &[Vector3::zeros()])
+    }
+
+    /// Build the group modulo the lattice with the centring translations, in the unit
+    /// of `1/12`, e.g. the core position set of a centred space group.
+    pub fn try_with_lattice(
+        operations: &[SeitzMatrix],
+        lattice_translations: &[Vector3<i32>],
+    ) -> Result<Self, GroupAxiomError> {
+        let mut lattice_translations = lattice_translations.to_vec();
+        if !lattice_translations.contains(&Vector3::zeros()) {
+            lattice_translations.insert(0, Vector3::zeros());
+        }
+        let mut group = Self {
+            operations: Vec::with_capacity(operations.len()),
+            lattice_translations,
+            index: HashMap::new(),
+        };
+        let mut violations = Vec::new();
+        operations.iter().enumerate().for_each(|(i, m)| {
+            let key = group.key(m);
+            match group.index.get(&key) {
+                Some(&first) => {
+                    violations.push(GroupAxiomViolation::Duplicate { first, second: i })
+                }
+                None => {
+                    group.index.insert(key, i);
+                }
+            }
+            let mut canonical = *m;
+            canonical.set_translation_part(key.1);
+            group.operations.push(canonical);
+        });
+        let identity = group.position(&SeitzMatrix::identity());
+        if identity.is_none() {
+            violations.push(GroupAxiomViolation::MissingIdentity);
+        }
+        let mut has_inverse = vec![false; operations.len()];
+        for (left, a) in group.operations.iter().enumerate() {
+            for (right, b) in group.operations.iter().enumerate() {
+                let product = *a * *b;
+                match group.position(&product) {
+                    Some(k) => {
+                        if Some(k) == identity {
+                            has_inverse[left] = true;
+                        }
+                    }
+                    None => violations.push(GroupAxiomViolation::NotClosed {
+                        left,
+                        right,
+                        product: group.canonical(&product),
+                    }),
+                }
+            }
+        }
+        // Without the identity the inverses are undefined
+        if identity.is_some() {
+            has_inverse
+                .iter()
+                .enumerate()
+                .filter(|(_, &found)| !found)
+                .for_each(|(element, _)| {
+                    violations.push(GroupAxiomViolation::MissingInverse { element })
+                });
+        }
+        if violations.is_empty() {
+            Ok(group)
+        } else {
+            Err(GroupAxiomError { violations })
+        }
+    }
+
+    /// The operations with the translations reduced to the canonical representatives
+    /// modulo the lattice
+    pub fn operations(&self) -> &[SeitzMatrix] {
+        &self.operations
+    }
+
+    pub fn lattice_translations(&self) -> &[Vector3<i32>] {
+        &self.lattice_translations
+    }
+
+    pub fn order(&self) -> usize {
+        self.operations.len()
+    }
+
+    /// Index of the operation in `operations()`, modulo the lattice translations
+    pub fn position(&self, seitz_matrix: &SeitzMatrix) -> Option<usize> {
+        self.index.get(&self.key(seitz_matrix)).copied()
+    }
+
+    pub fn contains(&self, seitz_matrix: &SeitzMatrix) -> bool {
+        self.position(seitz_matrix).is_some()
+    }
+
+    /// The rotation part with the smallest translation equivalent modulo the lattice
+    fn key(&self, seitz_matrix: &SeitzMatrix) -> OperationKey {
+        let t = seitz_matrix.translation_part();
+        let translation = self
+            .lattice_translations
+            .iter()
+            .map(|c| (t + c).map(|v| v.rem_euclid(SEITZ_TRANSLATE_BASE_NUMBER)))
+            .min_by_key(|v| (v.x, v.y, v.z))
+            .unwrap_or(t);
+        (seitz_matrix.rotation_part(), translation)
+    }
+
+    fn canonical(&self, seitz_matrix: &SeitzMatrix) -> SeitzMatrix {
+        let mut canonical = *seitz_matrix;
+        canonical.set_translation_part(self.key(seitz_matrix).1);
+        canonical
+    }
+}
+
+impl TryFrom<&GeneralPositions> for SymmetryGroup {
+    type Error = GroupAxiomError;
+
+    /// The core position set modulo the lattice of the general positions
+    fn try_from(value: &GeneralPositions) -> Result<Self, Self::Error> {
+        Self::try_with_lattice(value.core_position_set(), value.lattice_translations())
+    }
+}
+
+/// Order of the preference of the changes of basis: the simplest matrix `P`, then the
+/// smallest origin shift
+fn simplicity(cob: &ChangeOfBasis) -> ((usize, usize, usize), usize, i64) {
+    let p_vector = cob.origin_shift();
+    (
+        matrix_simplicity(&cob.matrix()),
+        p_vector.iter().filter(|v| **v != 0.0).count(),
+        (p_vector.abs().sum() * CHANGE_OF_BASIS_BASE_NUMBER as f64).round() as i64,
+    )
+}
+
+/// The fewest non-zero, off-diagonal and negative entries
+fn matrix_simplicity<T: PartialOrd + Zero + nalgebra::Scalar>(
+    m: &Matrix3<T>,
+) -> (usize, usize, usize) {
+    let zero = T::zero();
+    (
+        m.iter().filter(|v| **v != zero).count(),
+        (0..3)
+            .flat_map(|i| (0..3).map(move |j| (i, j)))
+            .filter(|&(i, j)| i != j && m[(i, j)] != zero)
+            .count(),
+        m.iter().filter(|v| **v < zero).count(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{HallSymbolNotation, SeitzMatrix};
+
+    use super::{GroupAxiomViolation, SymmetryGroup};
+
+    #[test]
+    fn group_axioms() {
+        let positions = HallSymbolNotation::try_from_str("-F 4vw 2vw 3")
+            .unwrap()
+            .general_positions();
+        let group = SymmetryGroup::try_from(&positions).unwrap();
+        assert_eq!(48, group.order());
+        let full = SymmetryGroup::try_new(&positions.derive_full_sets().concat()).unwrap();
+        assert_eq!(192, full.order());
+        assert!(full.contains(&SeitzMatrix::try_from_xyz("x+1,y+1/2,z-1/2").unwrap()));
+        // The core positions alone are not closed modulo the integer lattice
+        assert!(SymmetryGroup::try_new(positions.core_position_set()).is_err());
+
+        let operations = SeitzMatrix::try_from_xyz_list("x,y,z; -y,x,z+1/4; -x,-y,z+1/2").unwrap();
+        let error = SymmetryGroup::try_new(&operations).unwrap_err();
+        assert!(error
+            .violations()
+            .contains(&GroupAxiomViolation::MissingInverse { element: 1 }));
+        assert!(error.violations().iter().any(|v| matches!(
+            v,
+            GroupAxiomViolation::NotClosed { left: 1, right: 2, product }
+                if product.jones_faithful_repr() == "y,-x,z+3/4"
+        )));
+
+        // The identity with a lattice translation
+        let operations = SeitzMatrix::try_from_xyz_list("-x,-y,-z; x,y,z+1").unwrap();
+        assert_eq!(2, SymmetryGroup::try_new(&operations).unwrap().order());
+        let operations = SeitzMatrix::try_from_xyz_list("-x,-y,-z; x,y,z; -x,-y,-z+1").unwrap();
+        let error = SymmetryGroup::try_new(&operations).unwrap_err();
+        assert_eq!(
+            vec![GroupAxiomViolation::Duplicate {
+                first: 0,
+                second: 2
+            }],
+            error.violations()
+        );
+        let operations = SeitzMatrix::try_from_xyz_list("-x,-y,-z").unwrap();
+        let error = SymmetryGroup::try_new(&operations).unwrap_err();
+        assert!(error
+            .violations()
+            .contains(&GroupAxiomViolation::MissingIdentity));
+    }
+}
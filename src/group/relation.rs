@@ -0,0 +1,387 @@
+//! The relation between a space group and a subgroup given in its own setting.
+//!
+//! The subgroup is brought into the basis of the group by the matrices `P = B U B'⁻¹`,
+//! which map the primitive basis `B'` of the subgroup onto a basis `B` of a sublattice of
+//! the group, as for the identification of the maximal subgroups. The sublattices are
+//! searched up to the index `MAX_LATTICE_INDEX`.
+
+use std::{collections::HashMap, error::Error, fmt::Display};
+
+use nalgebra::{Matrix3, Vector3};
+
+use crate::{
+    hall_symbols::{
+        change_of_basis::{inverse, to_units},
+        CHANGE_OF_BASIS_BASE_NUMBER, SEITZ_TRANSLATE_BASE_NUMBER,
+    },
+    ChangeOfBasis, GeneralPositions, GroupGenerationError, HallSymbolNotation, SeitzMatrix,
+};
+
+use super::{
+    affine::{rational_matrix, Operation, Subgroup},
+    lattice::{solve_congruences, unimodular_pairs, Lattice},
+    matrix_simplicity, simplicity, Rational,
+};
+
+/// The largest index of the translations of the subgroup in those of the group, enough
+/// for the 16 of `Im-3m` in `Fm-3m`
+pub const MAX_LATTICE_INDEX: i64 = 16;
+
+/// Reason why no `SubgroupRelation` is found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubgroupRelationError {
+    /// Either symbol does not generate a space group
+    GroupGeneration(GroupGenerationError),
+    /// The point group, the operations or the lattice of the subgroup do not fit into the
+    /// group in any setting
+    NotSubgroup,
+    /// No change of basis is found with the translations of the subgroup forming a
+    /// sublattice of index up to `MAX_LATTICE_INDEX`, so a subgroup of a larger index is
+    /// not excluded
+    BeyondSearchBound,
+}
+
+impl Display for SubgroupRelationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubgroupRelationError::GroupGeneration(e) => write!(f, "{e}"),
+            SubgroupRelationError::NotSubgroup => f.write_str("Not a subgroup of the group"),
+            SubgroupRelationError::BeyondSearchBound => write!(
+                f,
+                "No subgroup relation with a lattice index up to {MAX_LATTICE_INDEX}"
+            ),
+        }
+    }
+}
+
+impl Error for SubgroupRelationError {}
+
+impl From<GroupGenerationError> for SubgroupRelationError {
+    fn from(value: GroupGenerationError) -> Self {
+        SubgroupRelationError::GroupGeneration(value)
+    }
+}
+
+/// A space group `H` as a subgroup of a space group `G`
+#[derive(Debug, Clone)]
+pub struct SubgroupRelation {
+    index: usize,
+    change_of_basis: ChangeOfBasis,
+    coset_representatives: Vec<SeitzMatrix>,
+}
+
+impl SubgroupRelation {
+    /// Index `[G : H]`
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The change of basis from the setting of the group to the setting of the subgroup,
+    /// which transforms the subgroup operations in the basis of the group into those of
+    /// the subgroup
+    pub fn change_of_basis(&self) -> ChangeOfBasis {
+        self.change_of_basis
+    }
+
+    /// Representatives `g` of the left cosets `gH`, in the basis of the group and with the
+    /// identity first. The translations are not reduced into the unit cell, since the
+    /// subgroup may have a larger cell.
+    pub fn coset_representatives(&self) -> &[SeitzMatrix] {
+        &self.coset_representatives
+    }
+}
+
+impl HallSymbolNotation {
+    /// Whether the group of `subgroup` is a subgroup of this group, possibly after a change
+    /// of basis and an origin shift, see `GeneralPositions::subgroup_relation`.
+    pub fn subgroup_relation(
+        &self,
+        subgroup: &HallSymbolNotation,
+    ) -> Result<SubgroupRelation, SubgroupRelationError> {
+        let group = self.try_general_positions()?;
+        let subgroup = subgroup.try_general_positions()?;
+        group.subgroup_relation(&subgroup)
+    }
+}
+
+impl GeneralPositions {
+    /// Whether `subgroup` is a subgroup of this group, possibly after a change of basis and
+    /// an origin shift, with the index and the coset representatives.
+    ///
+    /// When every operation of `subgroup` is already an operation of this group, the
+    /// identity is returned as the change of basis. Otherwise the change of basis with the
+    /// fewest lost translations, then the simplest matrix `P` and the smallest origin shift
+    /// `p` is searched.
+    ///
+    /// The translations of the subgroup are searched among the sublattices of index up to
+    /// `MAX_LATTICE_INDEX`. Beyond it `SubgroupRelationError::BeyondSearchBound` is
+    /// returned, which unlike `SubgroupRelationError::NotSubgroup` does not exclude a
+    /// subgroup of a larger index.
+    pub fn subgroup_relation(
+        &self,
+        subgroup: &GeneralPositions,
+    ) -> Result<SubgroupRelation, SubgroupRelationError> {
+        if !self
+            .core_position_set()
+            .len()
+            .is_multiple_of(subgroup.core_position_set().len())
+        {
+            return Err(SubgroupRelationError::NotSubgroup);
+        }
+        let group = Group::new(self);
+        if subgroup
+            .derive_full_sets()
+            .iter()
+            .flatten()
+            .all(|m| self.contains(m))
+        {
+            let lattice = Lattice::from_centrings(subgroup.lattice_translations());
+            let operations: Vec<Operation> = subgroup
+                .core_position_set()
+                .iter()
+                .map(Operation::from_seitz_matrix)
+                .collect();
+            return group
+                .relation(&operations, &lattice, ChangeOfBasis::identity())
+                .ok_or(SubgroupRelationError::NotSubgroup);
+        }
+        let sub_lattice = Lattice::from_centrings(subgroup.lattice_translations());
+        let sub_operations: Vec<Operation> = subgroup
+            .core_position_set()
+            .iter()
+            .map(Operation::from_seitz_matrix)
+            .collect();
+        let sub_rotations: Vec<Matrix3<i32>> = sub_operations
+            .iter()
+            .map(|op| sub_lattice.rotation_in_basis(&op.rotation))
+            .collect::<Option<_>>()
+            .ok_or(SubgroupRelationError::NotSubgroup)?;
+        // The conjugation keeps the determinant and the trace of the rotations
+        let invariants = |w: &Matrix3<i32>| (w.map(|v| v as f64).determinant().round(), w.trace());
+        if !sub_operations.iter().all(|op| {
+            group
+                .operations
+                .iter()
+                .any(|g| invariants(&g.rotation) == invariants(&op.rotation))
+        }) {
+            return Err(SubgroupRelationError::NotSubgroup);
+        }
+        let sub_basis_inverse = inverse(sub_lattice.basis());
+        for k in 1..=MAX_LATTICE_INDEX {
+            let mut found: Option<(ChangeOfBasis, Lattice)> = None;
+            for sublattice in group.lattice.sublattices(k) {
+                // The rotations of the group keeping the sublattice, in its basis
+                let in_basis: HashMap<Matrix3<i32>, Matrix3<i32>> = group
+                    .operations
+                    .iter()
+                    .filter_map(|g| Some((sublattice.rotation_in_basis(&g.rotation)?, g.rotation)))
+                    .collect();
+                if in_basis.len() < sub_rotations.len() {
+                    continue;
+                }
+                for (u, u_inv) in unimodular_pairs() {
+                    // The rotations `U W' U⁻¹` in the basis of the sublattice
+                    let Some(rotations) = sub_rotations
+                        .iter()
+                        .map(|w| in_basis.get(&(u * w * u_inv)).copied())
+                        .collect::<Option<Vec<Matrix3<i32>>>>()
+                    else {
+                        continue;
+                    };
+                    let p_matrix = sublattice.basis() * rational_matrix(u) * sub_basis_inverse;
+                    if found.as_ref().is_some_and(|(best, _)| {
+                        matrix_simplicity(&p_matrix) > matrix_simplicity(&best.matrix())
+                    }) {
+                        continue;
+                    }
+                    let Some(cob) = group.change_of_basis(&sub_operations, &rotations, &p_matrix)
+                    else {
+                        continue;
+                    };
+                    if found
+                        .as_ref()
+                        .is_none_or(|(best, _)| simplicity(&cob) < simplicity(best))
+                    {
+                        found = Some((cob, sublattice.clone()));
+                    }
+                }
+            }
+            if let Some((cob, sublattice)) = found {
+                let operations: Vec<Operation> = sub_operations
+                    .iter()
+                    .map(|op| transform_back(op, &cob))
+                    .collect();
+                return group
+                    .relation(&operations, &sublattice, cob)
+                    .ok_or(SubgroupRelationError::NotSubgroup);
+            }
+        }
+        Err(SubgroupRelationError::BeyondSearchBound)
+    }
+}
+
+/// The operations and the lattice of the group
+struct Group {
+    lattice: Lattice,
+    operations: Vec<Operation>,
+    /// Translation of the operation with the rotation
+    translations: HashMap<Matrix3<i32>, Vector3<Rational>>,
+}
+
+impl Group {
+    fn new(positions: &GeneralPositions) -> Self {
+        let operations: Vec<Operation> = positions
+            .core_position_set()
+            .iter()
+            .map(Operation::from_seitz_matrix)
+            .collect();
+        Self {
+            lattice: Lattice::from_centrings(positions.lattice_translations()),
+            translations: operations
+                .iter()
+                .map(|op| (op.rotation, op.translation))
+                .collect(),
+            operations,
+        }
+    }
+
+    /// The change of basis `(P, p)` with the smallest origin shift, with which the
+    /// subgroup operations `(P W' P⁻¹, P w' - (W - I)p)` belong to the group
+    fn change_of_basis(
+        &self,
+        sub_operations: &[Operation],
+        rotations: &[Matrix3<i32>],
+        p_matrix: &Matrix3<Rational>,
+    ) -> Option<ChangeOfBasis> {
+        // (W_B - I) q ≡ B⁻¹(P w' - s(W)) (mod 1) in the primitive basis of the group
+        let mut rows = Vec::with_capacity(3 * rotations.len());
+        let mut constants = Vec::with_capacity(3 * rotations.len());
+        for (op, w) in sub_operations.iter().zip(rotations) {
+            let s = self.translations.get(w)?;
+            let constant = self.lattice.coordinates(&(p_matrix * op.translation - s));
+            let a = self.lattice.rotation_in_basis(w)? - Matrix3::identity();
+            for i in 0..3 {
+                rows.push([a[(i, 0)], a[(i, 1)], a[(i, 2)]].map(|v| v as i64));
+                constants.push(constant[i]);
+            }
+        }
+        let p_units = to_units(p_matrix, SEITZ_TRANSLATE_BASE_NUMBER)?;
+        solve_congruences(rows, constants)
+            .iter()
+            .filter_map(|q| {
+                let shift = self
+                    .lattice
+                    .smallest_equivalent(&(self.lattice.basis() * q));
+                ChangeOfBasis::new(p_units, to_units(&shift, CHANGE_OF_BASIS_BASE_NUMBER)?)
+            })
+            .min_by_key(simplicity)
+    }
+
+    /// The relation to the subgroup with the operations and the lattice in the basis of
+    /// the group
+    fn relation(
+        &self,
+        sub_operations: &[Operation],
+        sub_lattice: &Lattice,
+        change_of_basis: ChangeOfBasis,
+    ) -> Option<SubgroupRelation> {
+        let subgroup = Subgroup::generate(sub_operations, sub_lattice, sub_operations.len())?;
+        let translations = self.lattice.coset_translations(sub_lattice);
+        let index = self.operations.len() / sub_operations.len() * translations.len();
+        let mut representatives: Vec<Operation> = Vec::with_capacity(index);
+        for op in self.operations.iter() {
+            for t in translations.iter() {
+                let candidate = op.then_translate(t);
+                if !representatives
+                    .iter()
+                    .any(|r| subgroup.contains_operation(&r.inverse().mul(&candidate)))
+                {
+                    representatives.push(candidate);
+                }
+            }
+        }
+        Some(SubgroupRelation {
+            index,
+            change_of_basis,
+            coset_representatives: representatives
+                .iter()
+                .map(|op| op.to_seitz_matrix())
+                .collect(),
+        })
+    }
+}
+
+/// The operation of the subgroup in the basis of the group, `(PW'P⁻¹, Pw' - (W - I)p)`
+fn transform_back(operation: &Operation, cob: &ChangeOfBasis) -> Operation {
+    let p_matrix = cob.matrix_rational();
+    let rotation = p_matrix * rational_matrix(&operation.rotation) * inverse(&p_matrix);
+    let rotation = to_units(&rotation, 1).expect("The rotation belongs to the group");
+    let translation = p_matrix * operation.translation
+        - (rational_matrix(&rotation) - Matrix3::identity()) * cob.vector_rational();
+    Operation {
+        rotation,
+        translation,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ChangeOfBasis, HallSymbolNotation};
+
+    use super::{SubgroupRelation, SubgroupRelationError};
+
+    fn relation(group: &str, subgroup: &str) -> Result<SubgroupRelation, SubgroupRelationError> {
+        let group = HallSymbolNotation::try_from_str(group).unwrap();
+        let subgroup = HallSymbolNotation::try_from_str(subgroup).unwrap();
+        let relation = group.subgroup_relation(&subgroup)?;
+        // The operations of the subgroup brought back into the basis of the group
+        let back = relation.change_of_basis().inverse().unwrap();
+        let group = group.general_positions();
+        subgroup
+            .general_positions()
+            .core_position_set()
+            .iter()
+            .for_each(|m| assert!(group.contains(&back.transform_seitz_matrix(m).unwrap())));
+        assert_eq!(relation.index(), relation.coset_representatives().len());
+        Ok(relation)
+    }
+
+    #[test]
+    fn subgroup_relations() {
+        // P-1 in P2/m in the same setting
+        let p2m = relation("-P 2y", "-P 1").unwrap();
+        assert_eq!(2, p2m.index());
+        assert_eq!(ChangeOfBasis::identity(), p2m.change_of_basis());
+
+        // P2₁/c and P2₁/n are the same group in different settings
+        let p21c = relation("-P 2ybc", "-P 2yn").unwrap();
+        assert_eq!(1, p21c.index());
+        assert_eq!(1.0, p21c.change_of_basis().volume_ratio());
+
+        // The t-subgroup Cmmm of P4/mmm in the cell with the doubled volume
+        let cmmm = relation("-P 4 2", "-C 2 2").unwrap();
+        assert_eq!(2, cmmm.index());
+        assert_eq!(2.0, cmmm.change_of_basis().volume_ratio());
+
+        // The k-subgroups lose the centring, P4₂ in I4 with an origin shift
+        let p42 = relation("I 4", "P 4c").unwrap();
+        assert_eq!(2, p42.index());
+        assert_eq!(
+            ChangeOfBasis::try_from_str("a,b,c; 1/2,0,0").unwrap(),
+            p42.change_of_basis()
+        );
+        assert_eq!(2, relation("-I 4 2", "-P 4 2").unwrap().index());
+        assert_eq!(3, relation("P 3", "R 3").unwrap().index());
+
+        // Im-3m in Fm-3m with the doubled cell, of the lattice index 16
+        let im3m = relation("-F 4 2 3", "-I 4 2 3").unwrap();
+        assert_eq!(16, im3m.index());
+        assert_eq!(8.0, im3m.change_of_basis().volume_ratio());
+
+        // No inversion in P2
+        assert_eq!(
+            SubgroupRelationError::NotSubgroup,
+            relation("P 2y", "-P 1").unwrap_err()
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use nalgebra::Matrix3;
+
+use crate::{GeneralPositions, SeitzMatrix};
+
+use super::{
+    affine::Operation,
+    lattice::{solve_congruences, Lattice},
+};
+
+impl GeneralPositions {
+    /// The operators of all the centred sets which are screw rotations or glide reflections
+    /// wherever they are shifted by the integer lattice translations, i.e. whose intrinsic
+    /// part is not removed by any of them. E.g. `C2` has the screw rotation
+    /// `-x+1/2,y+1/2,-z` besides the rotation `-x,y,-z`.
+    pub fn screw_and_glide_operations(&self) -> Vec<SeitzMatrix> {
+        let lattice = Lattice::from_centrings(&[]);
+        self.derive_full_sets()
+            .concat()
+            .into_iter()
+            .filter(|m| m.rotation_part() != Matrix3::identity())
+            .filter(|m| !has_fixed_point(&[*m], &lattice))
+            .collect()
+    }
+
+    pub fn has_screw_axes(&self) -> bool {
+        self.screw_and_glide_operations()
+            .iter()
+            .any(|m| m.rotation_part().map(|v| v as f64).determinant() > 0.0)
+    }
+
+    pub fn has_glide_planes(&self) -> bool {
+        self.screw_and_glide_operations()
+            .iter()
+            .any(|m| m.rotation_part().map(|v| v as f64).determinant() < 0.0)
+    }
+
+    /// Whether an origin exists at which every coset representative is free of the
+    /// translation modulo the lattice. The group may be symmorphic and still contain
+    /// screw axes or glide planes, e.g. `C2`.
+    pub fn is_symmorphic(&self) -> bool {
+        let lattice = Lattice::from_centrings(self.lattice_translations());
+        has_fixed_point(self.core_position_set(), &lattice)
+    }
+}
+
+/// Whether a common point `p` is fixed by all the operations modulo the lattice,
+/// `(W - I)p ≡ -w`
+fn has_fixed_point(operations: &[SeitzMatrix], lattice: &Lattice) -> bool {
+    let mut rows = Vec::with_capacity(3 * operations.len());
+    let mut constants = Vec::with_capacity(3 * operations.len());
+    for m in operations {
+        let op = Operation::from_seitz_matrix(m);
+        let Some(rotation) = lattice.rotation_in_basis(&op.rotation) else {
+            return false;
+        };
+        let a = rotation - Matrix3::identity();
+        let constant = lattice.coordinates(&-op.translation);
+        for i in 0..3 {
+            rows.push([a[(i, 0)], a[(i, 1)], a[(i, 2)]].map(|v| v as i64));
+            constants.push(constant[i]);
+        }
+    }
+    !solve_congruences(rows, constants).is_empty()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::HallSymbolNotation;
+
+    #[test]
+    fn symmorphic_groups() {
+        let cases = [
+            // P2₁/c, I4₁/amd, P2₁2₁2₁: non-symmorphic
+            ("-P 2ybc", false, true, true),
+            ("-I 4bd 2", false, true, true),
+            ("P 2ac 2ab", false, true, false),
+            // Pm-3m with the origin shifted, and C2, I4 and Cmm2 with screws or glides
+            ("-P 4 2 3 (x+1/4,y,z)", true, false, false),
+            ("C 2y", true, true, false),
+            ("I 4", true, true, false),
+            ("C 2 -2", true, false, true),
+            ("P 1", true, false, false),
+        ];
+        for (symbol, symmorphic, screws, glides) in cases {
+            let positions = HallSymbolNotation::try_from_str(symbol)
+                .unwrap()
+                .general_positions();
+            assert_eq!(symmorphic, positions.is_symmorphic(), "{symbol}");
+            assert_eq!(screws, positions.has_screw_axes(), "{symbol}");
+            assert_eq!(glides, positions.has_glide_planes(), "{symbol}");
+        }
+    }
+}
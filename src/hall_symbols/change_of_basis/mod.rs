@@ -0,0 +1,642 @@
+use std::{collections::HashSet, fmt::Display};
+
+use fraction::Zero;
+use nalgebra::{Matrix3, Vector3};
+use winnow::ModalResult;
+
+use crate::{
+    database::{Setting, SpaceGroupHallSymbol},
+    utils::{positive_mod_stbn_i32, Rational},
+    wyckoff::affine::{integer, reduce_into_cell, to_f64},
+};
+
+use self::parser::parse_change_of_basis;
+
+use super::{
+    matrix_symbol::SeitzMatrix, GeneralPositions, HallSymbolNotation, CHANGE_OF_BASIS_BASE_NUMBER,
+    SEITZ_TRANSLATE_BASE_NUMBER,
+};
+
+mod parser;
+mod symbol;
+
+pub use symbol::ChangeOfBasisSymbol;
+
+/// A change of basis `(P, p)` as in the International Tables: the new basis is
+/// `(a', b', c') = (a, b, c)P` and the new origin is `O' = O + p`, so that the
+/// coordinates transform as `x' = P⁻¹(x - p)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+pub struct ChangeOfBasis {
+    /// `P` in the unit of `1/SEITZ_TRANSLATE_BASE_NUMBER`
+    p_matrix: Matrix3<i32>,
+    /// `p` in the unit of `1/CHANGE_OF_BASIS_BASE_NUMBER`
+    p_vector: Vector3<i32>,
+}
+
+impl Default for ChangeOfBasis {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl ChangeOfBasis {
+    /// `p_matrix` is in the unit of `1/SEITZ_TRANSLATE_BASE_NUMBER` and `p_vector` in the
+    /// unit of `1/CHANGE_OF_BASIS_BASE_NUMBER`. Returns `None` for a singular matrix.
+    pub fn new(p_matrix: Matrix3<i32>, p_vector: Vector3<i32>) -> Option<Self> {
+        let change_of_basis = Self { p_matrix, p_vector };
+        (!determinant(&change_of_basis.matrix_rational()).is_zero()).then_some(change_of_basis)
+    }
+
+    pub fn identity() -> Self {
+        Self {
+            p_matrix: Matrix3::identity() * SEITZ_TRANSLATE_BASE_NUMBER,
+            p_vector: Vector3::zeros(),
+        }
+    }
+
+    /// Parse the notation like `-b,a,c`, `a-b,a+b,c` or `a,b,c; 0,0,1/4`
+    pub fn try_from_str(input: &str) -> ModalResult<Self> {
+        let mut input = input;
+        parse_change_of_basis(&mut input)
+    }
+
+    /// The matrix `P`
+    pub fn matrix(&self) -> Matrix3<f64> {
+        self.p_matrix
+            .map(|v| v as f64 / SEITZ_TRANSLATE_BASE_NUMBER as f64)
+    }
+
+    /// The origin shift `p`
+    pub fn origin_shift(&self) -> Vector3<f64> {
+        self.p_vector
+            .map(|v| v as f64 / CHANGE_OF_BASIS_BASE_NUMBER as f64)
+    }
+
+    /// Ratio of the volumes of the new and the old cells, `det(P)`
+    pub fn volume_ratio(&self) -> f64 {
+        to_f64(determinant(&self.matrix_rational()))
+    }
+
+    /// The change of basis back to the old setting, `(P⁻¹, -P⁻¹p)`.
+    /// Returns `None` when it cannot be represented in the integer units.
+    pub fn inverse(&self) -> Option<Self> {
+        let inverse = inverse(&self.matrix_rational());
+        let shift = -(inverse * self.vector_rational());
+        Some(Self {
+            p_matrix: to_units(&inverse, SEITZ_TRANSLATE_BASE_NUMBER)?,
+            p_vector: to_units(&shift, CHANGE_OF_BASIS_BASE_NUMBER)?,
+        })
+    }
+
+    /// This change of basis followed by `other`, i.e. `(PQ, p + Pq)`.
+    /// Returns `None` when the result cannot be represented in the integer units.
+    pub fn then(&self, other: &Self) -> Option<Self> {
+        let p = self.matrix_rational();
+        let matrix = p * other.matrix_rational();
+        let shift = self.vector_rational() + p * other.vector_rational();
+        Self::new(
+            to_units(&matrix, SEITZ_TRANSLATE_BASE_NUMBER)?,
+            to_units(&shift, CHANGE_OF_BASIS_BASE_NUMBER)?,
+        )
+    }
+
+    /// Transform an operation into the new setting: `W' = P⁻¹WP`, `w' = P⁻¹(w + (W - I)p)`.
+    /// The translation is reduced into `[0, 1)`. Returns `None` when the result
+    /// is not an operation with translations in multiples of `1/12`.
+    pub fn transform_seitz_matrix(&self, seitz_matrix: &SeitzMatrix) -> Option<SeitzMatrix> {
+        let p = self.matrix_rational();
+        let p_inv = inverse(&p);
+        let w = seitz_matrix.rotation_part().map(|v| integer(v as i64));
+        let t = seitz_matrix
+            .translation_part()
+            .map(|v| Rational::new(v as i64, SEITZ_TRANSLATE_BASE_NUMBER as i64));
+        let rotation = p_inv * w * p;
+        let translation = p_inv * (t + (w - Matrix3::identity()) * self.vector_rational());
+        let rotation = to_units(&rotation, 1)?;
+        let translation = to_units(&translation, SEITZ_TRANSLATE_BASE_NUMBER)?;
+        let mut mat = rotation.to_homogeneous();
+        mat.set_column(3, &translation.map(positive_mod_stbn_i32).push(1));
+        Some(SeitzMatrix::new(mat))
+    }
+
+    /// Transform the whole group into the new setting. The lattice translations of the
+    /// new cell are found from the old lattice, so the change to a larger or a smaller
+    /// cell gives the centring translations of the new cell.
+    pub fn transform_general_positions(
+        &self,
+        general_positions: &GeneralPositions,
+    ) -> Option<GeneralPositions> {
+        let p_inv = inverse(&self.matrix_rational());
+        let old_translations: Vec<Vector3<Rational>> = general_positions
+            .lattice_translations()
+            .iter()
+            .map(|t| t.map(|v| Rational::new(v as i64, SEITZ_TRANSLATE_BASE_NUMBER as i64)))
+            .collect();
+        // The old lattice points inside the new cell
+        let bound = |i: usize| {
+            (0..3)
+                .map(|j| self.p_matrix[(i, j)].abs() + SEITZ_TRANSLATE_BASE_NUMBER - 1)
+                .sum::<i32>()
+                / SEITZ_TRANSLATE_BASE_NUMBER
+        };
+        let [bx, by, bz] = [bound(0), bound(1), bound(2)];
+        let mut operators: Vec<SeitzMatrix> = Vec::new();
+        for x in -bx..=bx {
+            for y in -by..=by {
+                for z in -bz..=bz {
+                    let n = Vector3::new(x, y, z).map(|v| integer(v as i64));
+                    for t in old_translations.iter() {
+                        let new_t = (p_inv * (n + t)).map(reduce_into_cell);
+                        let new_t = to_units(&new_t, SEITZ_TRANSLATE_BASE_NUMBER)?;
+                        let translation = SeitzMatrix::identity() + new_t;
+                        if !operators.contains(&translation) {
+                            operators.push(translation);
+                        }
+                    }
+                }
+            }
+        }
+        for m in general_positions.derive_full_sets().concat().iter() {
+            operators.push(self.transform_seitz_matrix(m)?);
+        }
+        Some(GeneralPositions::from_operators(&operators))
+    }
+
+    /// Transform fractional coordinates into the new setting, `x' = P⁻¹(x - p)`
+    pub fn transform_coordinates(&self, coordinates: &Vector3<f64>) -> Vector3<f64> {
+        let p_inv = inverse(&self.matrix_rational()).map(to_f64);
+        p_inv * (coordinates - self.origin_shift())
+    }
+
+    /// Transform the cell parameters `[a, b, c, alpha, beta, gamma]`, with the angles in
+    /// degrees, through the metric tensor `G' = PᵀGP`.
+    pub fn transform_cell_parameters(&self, cell: &[f64; 6]) -> [f64; 6] {
+        let [a, b, c, alpha, beta, gamma] = *cell;
+        let [cos_alpha, cos_beta, cos_gamma] = [alpha, beta, gamma].map(|v| v.to_radians().cos());
+        let metric = Matrix3::new(
+            a * a,
+            a * b * cos_gamma,
+            a * c * cos_beta,
+            a * b * cos_gamma,
+            b * b,
+            b * c * cos_alpha,
+            a * c * cos_beta,
+            b * c * cos_alpha,
+            c * c,
+        );
+        let p = self.matrix();
+        let g = p.transpose() * metric * p;
+        let [a, b, c] = [0, 1, 2].map(|i| g[(i, i)].sqrt());
+        // Rounding may push the cosine slightly out of [-1, 1] for the flat angles
+        let angle = |i: usize, j: usize, li: f64, lj: f64| {
+            (g[(i, j)] / (li * lj)).clamp(-1.0, 1.0).acos().to_degrees()
+        };
+        [
+            a,
+            b,
+            c,
+            angle(1, 2, b, c),
+            angle(0, 2, a, c),
+            angle(0, 1, a, b),
+        ]
+    }
+
+    /// The transformation of the coordinates `x' = P⁻¹(x - p)` written as a triplet
+    /// like `x-y,x,z+1/4`
+    pub fn coordinate_triplet(&self) -> String {
+        let p_inv = inverse(&self.matrix_rational());
+        let shift = -(p_inv * self.vector_rational());
+        (0..3)
+            .map(|i| {
+                let coefficients = [p_inv[(i, 0)], p_inv[(i, 1)], p_inv[(i, 2)]];
+                affine_expression(&coefficients, shift[i], ['x', 'y', 'z'])
+            })
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+
+    pub(crate) fn matrix_rational(&self) -> Matrix3<Rational> {
+        self.p_matrix
+            .map(|v| Rational::new(v as i64, SEITZ_TRANSLATE_BASE_NUMBER as i64))
+    }
+
+    pub(crate) fn vector_rational(&self) -> Vector3<Rational> {
+        self.p_vector
+            .map(|v| Rational::new(v as i64, CHANGE_OF_BASIS_BASE_NUMBER as i64))
+    }
+}
+
+impl Display for ChangeOfBasis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let p = self.matrix_rational();
+        let basis: Vec<String> = (0..3)
+            .map(|j| linear_expression(&[p[(0, j)], p[(1, j)], p[(2, j)]], ['a', 'b', 'c']))
+            .collect();
+        write!(f, "{}", basis.join(","))?;
+        if self.p_vector != Vector3::zeros() {
+            let shift: Vec<String> = self
+                .vector_rational()
+                .iter()
+                .map(|v| format!("{v}"))
+                .collect();
+            write!(f, ";{}", shift.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+/// Expression like `a-b` or `1/3a+2/3b` from the coefficients
+pub(crate) fn linear_expression(coefficients: &[Rational; 3], symbols: [char; 3]) -> String {
+    let mut expr = String::new();
+    coefficients
+        .iter()
+        .zip(symbols)
+        .filter(|(c, _)| !c.is_zero())
+        .for_each(|(c, symbol)| {
+            if *c < Rational::zero() {
+                expr.push('-');
+            } else if !expr.is_empty() {
+                expr.push('+');
+            }
+            let magnitude = if *c < Rational::zero() { -c } else { *c };
+            if magnitude != integer(1) {
+                expr.push_str(&format!("{magnitude}"));
+            }
+            expr.push(symbol);
+        });
+    if expr.is_empty() {
+        expr.push('0');
+    }
+    expr
+}
+
+/// Expression like `z+1/4` or `-x+1/2` with a constant term
+pub(crate) fn affine_expression(
+    coefficients: &[Rational; 3],
+    constant: Rational,
+    symbols: [char; 3],
+) -> String {
+    let expr = linear_expression(coefficients, symbols);
+    if constant.is_zero() {
+        expr
+    } else if expr == "0" {
+        format!("{constant}")
+    } else if constant < Rational::zero() {
+        format!("{expr}-{}", -constant)
+    } else {
+        format!("{expr}+{constant}")
+    }
+}
+
+impl SpaceGroupHallSymbol {
+    /// The change of basis from this setting to another tabulated setting of the same
+    /// space group, e.g. from `14:b1` to `14:c2`, such that the operations of this setting
+    /// are transformed into the operations of the other one.
+    /// The simplest matrix and the smallest origin shift are searched.
+    /// Returns `None` for the settings of different space groups.
+    pub fn change_of_basis_to(&self, other: &SpaceGroupHallSymbol) -> Option<ChangeOfBasis> {
+        let source = HallSymbolNotation::from(*self).general_positions();
+        let target = HallSymbolNotation::from(*other).general_positions();
+        let found = basis_changes(settings_base(self, other)?, &source, &target)
+            .find_map(|cob| origin_shift_between(&cob, &source, &target));
+        found
+    }
+
+    /// The simplest change of basis, without origin shift, transforming the rotation parts
+    /// of this setting into the ones of another tabulated setting of the same space group.
+    /// Enough when the translations do not matter, and much faster than
+    /// `change_of_basis_to`.
+    pub(crate) fn basis_change_to(&self, other: &SpaceGroupHallSymbol) -> Option<ChangeOfBasis> {
+        if self == other {
+            return Some(ChangeOfBasis::identity());
+        }
+        let source = HallSymbolNotation::from(*self).general_positions();
+        let target = HallSymbolNotation::from(*other).general_positions();
+        let found = basis_changes(settings_base(self, other)?, &source, &target).next();
+        found
+    }
+}
+
+/// The change of basis between the lattices of two tabulated settings, from the
+/// rhombohedral axes to the hexagonal ones or back. Returns `None` for the settings
+/// of different space groups.
+fn settings_base(from: &SpaceGroupHallSymbol, to: &SpaceGroupHallSymbol) -> Option<ChangeOfBasis> {
+    if from.entry().number() != to.entry().number() {
+        return None;
+    }
+    let is_rhombohedral =
+        |s: &SpaceGroupHallSymbol| s.entry().setting() == Setting::RhombohedralAxes;
+    // (a_h, b_h, c_h) = (a_r - b_r, b_r - c_r, a_r + b_r + c_r)
+    let rhombohedral_to_hexagonal = ChangeOfBasis::new(
+        Matrix3::new(1, 0, 1, -1, 1, 1, 0, -1, 1) * SEITZ_TRANSLATE_BASE_NUMBER,
+        Vector3::zeros(),
+    )?;
+    match (is_rhombohedral(from), is_rhombohedral(to)) {
+        (true, false) => Some(rhombohedral_to_hexagonal),
+        (false, true) => rhombohedral_to_hexagonal.inverse(),
+        _ => Some(ChangeOfBasis::identity()),
+    }
+}
+
+/// The changes of basis `(PU, 0)`, with `P` of `base` and the unimodular matrices `U`,
+/// transforming the rotation parts of the source into the ones of the target,
+/// the simplest first
+pub(crate) fn basis_changes<'a>(
+    base: ChangeOfBasis,
+    source: &'a GeneralPositions,
+    target: &'a GeneralPositions,
+) -> impl Iterator<Item = ChangeOfBasis> + 'a {
+    unimodular_matrices()
+        .into_iter()
+        .filter_map(move |u| {
+            base.then(&ChangeOfBasis::new(
+                u * SEITZ_TRANSLATE_BASE_NUMBER,
+                Vector3::zeros(),
+            )?)
+        })
+        .filter(move |cob| {
+            let p = cob.matrix_rational();
+            let p_inv = inverse(&p);
+            source.core_position_set().iter().all(|m| {
+                transformed_rotation(&p, &p_inv, m).is_some_and(|w| {
+                    target
+                        .core_position_set()
+                        .iter()
+                        .any(|g| g.rotation_part() == w)
+                })
+            })
+        })
+}
+
+/// Rotation part `P⁻¹WP` of the transformed operation, if integral
+fn transformed_rotation(
+    p: &Matrix3<Rational>,
+    p_inv: &Matrix3<Rational>,
+    seitz_matrix: &SeitzMatrix,
+) -> Option<Matrix3<i32>> {
+    to_units(
+        &(p_inv * seitz_matrix.rotation_part().map(|v| integer(v as i64)) * p),
+        1,
+    )
+}
+
+/// Search the smallest origin shift with which the transformed source group is the target.
+/// The target is brought back into the basis of the source, where the shift `p` only
+/// changes the translations into `w + (W - I)p`.
+fn origin_shift_between(
+    cob: &ChangeOfBasis,
+    source: &GeneralPositions,
+    target: &GeneralPositions,
+) -> Option<ChangeOfBasis> {
+    /// Origin shifts are searched in the unit of `1/SHIFT_BASE`
+    const SHIFT_BASE: i32 = 2 * SEITZ_TRANSLATE_BASE_NUMBER;
+    let target_in_source = cob.inverse()?.transform_general_positions(target)?;
+    // The centring translations do not depend on the origin
+    let centrings = |positions: &GeneralPositions| -> HashSet<Vector3<i32>> {
+        positions
+            .lattice_translations()
+            .iter()
+            .map(|t| t.map(|v| v.rem_euclid(SEITZ_TRANSLATE_BASE_NUMBER)))
+            .collect()
+    };
+    if centrings(&target_in_source) != centrings(source) {
+        return None;
+    }
+    let scale = SHIFT_BASE / SEITZ_TRANSLATE_BASE_NUMBER;
+    // Source operations with the allowed translations of the target, in `1/SHIFT_BASE`
+    let conditions: Vec<_> = source
+        .core_position_set()
+        .iter()
+        .map(|m| {
+            let w = m.rotation_part();
+            let allowed: Vec<Vector3<i32>> = target_in_source
+                .derive_full_sets()
+                .concat()
+                .iter()
+                .filter(|g| g.rotation_part() == w)
+                .map(|g| g.translation_part() * scale)
+                .collect();
+            (w, m.translation_part() * scale, allowed)
+        })
+        .collect();
+    let mut components: Vec<i32> = (-SHIFT_BASE / 2 + 1..=SHIFT_BASE / 2).collect();
+    components.sort_by_key(|v| (v.abs(), *v < 0));
+    let mut shifts: Vec<Vector3<i32>> = Vec::new();
+    for &x in components.iter() {
+        for &y in components.iter() {
+            for &z in components.iter() {
+                shifts.push(Vector3::new(x, y, z));
+            }
+        }
+    }
+    shifts.sort_by_key(|v| (v.iter().filter(|c| **c != 0).count(), v.abs().sum()));
+    shifts
+        .iter()
+        .filter(|shift| {
+            conditions.iter().all(|(w, t, allowed)| {
+                let shifted = t + (w - Matrix3::identity()) * *shift;
+                allowed
+                    .iter()
+                    .any(|a| (shifted - a).iter().all(|v| v % SHIFT_BASE == 0))
+            })
+        })
+        .find_map(|shift| {
+            let candidate = ChangeOfBasis::new(
+                cob.p_matrix,
+                shift * (CHANGE_OF_BASIS_BASE_NUMBER / SHIFT_BASE),
+            )?;
+            candidate
+                .transform_general_positions(source)
+                .filter(|transformed| transformed.is_same_group(target))
+                .map(|_| candidate)
+        })
+}
+
+/// Integer matrices with entries in `-1..=1` and determinant `1`, the identity first
+/// and then the others with fewer non-zero entries.
+pub(crate) fn unimodular_matrices() -> Vec<Matrix3<i32>> {
+    let mut matrices: Vec<Matrix3<i32>> = (0..3_i32.pow(9))
+        .map(|code| {
+            let mut code = code;
+            Matrix3::from_fn(|_, _| {
+                let v = code % 3 - 1;
+                code /= 3;
+                v
+            })
+        })
+        .filter(|m| m.map(|v| v as f64).determinant().round() as i32 == 1)
+        .collect();
+    matrices.sort_by_key(|m| {
+        (
+            *m != Matrix3::identity(),
+            m.iter().filter(|v| **v != 0).count(),
+            m.iter().filter(|v| **v < 0).count(),
+        )
+    });
+    matrices
+}
+
+/// Express the rational entries as integers in the unit of `1/base`
+pub(crate) fn to_units<const R: usize, const C: usize>(
+    m: &nalgebra::SMatrix<Rational, R, C>,
+    base: i32,
+) -> Option<nalgebra::SMatrix<i32, R, C>> {
+    let scaled = m.map(|v| v * Rational::from_integer(base as i64));
+    scaled
+        .iter()
+        .all(|v| v.is_integer())
+        .then(|| scaled.map(|v| v.to_integer() as i32))
+}
+
+pub(crate) fn determinant(m: &Matrix3<Rational>) -> Rational {
+    m[(0, 0)] * (m[(1, 1)] * m[(2, 2)] - m[(1, 2)] * m[(2, 1)])
+        - m[(0, 1)] * (m[(1, 0)] * m[(2, 2)] - m[(1, 2)] * m[(2, 0)])
+        + m[(0, 2)] * (m[(1, 0)] * m[(2, 1)] - m[(1, 1)] * m[(2, 0)])
+}
+
+pub(crate) fn inverse(m: &Matrix3<Rational>) -> Matrix3<Rational> {
+    let det = determinant(m);
+    let cofactor = |i: usize, j: usize| {
+        let rows: Vec<usize> = (0..3).filter(|&r| r != i).collect();
+        let cols: Vec<usize> = (0..3).filter(|&c| c != j).collect();
+        let minor = m[(rows[0], cols[0])] * m[(rows[1], cols[1])]
+            - m[(rows[0], cols[1])] * m[(rows[1], cols[0])];
+        if (i + j).is_multiple_of(2) {
+            minor
+        } else {
+            -minor
+        }
+    };
+    // The inverse is the transposed cofactor matrix over the determinant
+    Matrix3::from_fn(|i, j| cofactor(j, i) / det)
+}
+
+#[cfg(test)]
+mod test {
+    use nalgebra::Vector3;
+
+    use crate::{database::SpaceGroupHallSymbol, HallSymbolNotation, SeitzMatrix};
+
+    use super::ChangeOfBasis;
+
+    #[test]
+    fn parse_and_display() {
+        let cob = ChangeOfBasis::try_from_str("-b,a,c").unwrap();
+        assert_eq!("-b,a,c", format!("{cob}"));
+        let cob = ChangeOfBasis::try_from_str("a, b, c; 0, 0, 1/4").unwrap();
+        assert_eq!("a,b,c;0,0,1/4", format!("{cob}"));
+        let cob =
+            ChangeOfBasis::try_from_str("2/3a+1/3b+1/3c,-1/3a+1/3b+1/3c,-1/3a-2/3b+1/3c").unwrap();
+        assert!((cob.volume_ratio() - 1.0 / 3.0).abs() < 1e-12);
+        assert!(ChangeOfBasis::try_from_str("a,b").is_err());
+        assert!(ChangeOfBasis::try_from_str("a,a,c").is_err());
+    }
+
+    #[test]
+    fn transform_operations_and_coordinates() {
+        let cob = ChangeOfBasis::try_from_str("a,b,c;1/4,1/4,1/4").unwrap();
+        let m = SeitzMatrix::try_from_xyz("-x,-y,z").unwrap();
+        assert_eq!(
+            "-x+1/2,-y+1/2,z",
+            cob.transform_seitz_matrix(&m)
+                .unwrap()
+                .jones_faithful_repr()
+        );
+        let x = cob.transform_coordinates(&Vector3::new(0.25, 0.25, 0.25));
+        assert!(x.norm() < 1e-12);
+        let back = cob.inverse().unwrap().then(&cob).unwrap();
+        assert_eq!(ChangeOfBasis::identity(), back);
+        let cell = ChangeOfBasis::try_from_str("-b,a,c")
+            .unwrap()
+            .transform_cell_parameters(&[3.0, 4.0, 5.0, 90.0, 100.0, 90.0]);
+        assert!((cell[0] - 4.0).abs() < 1e-9 && (cell[1] - 3.0).abs() < 1e-9);
+        assert!((cell[3] - 100.0).abs() < 1e-9 && (cell[4] - 90.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn transform_hexagonal_cell() {
+        // The obverse rhombohedral cell of a hexagonal one and back
+        let cob =
+            ChangeOfBasis::try_from_str("2/3a+1/3b+1/3c,-1/3a+1/3b+1/3c,-1/3a-2/3b+1/3c").unwrap();
+        let hexagonal = [5.0, 5.0, 12.0, 90.0, 90.0, 120.0];
+        let rhombohedral = cob.transform_cell_parameters(&hexagonal);
+        let a = (25.0 / 3.0 + 144.0 / 9.0_f64).sqrt();
+        for i in 0..3 {
+            assert!((rhombohedral[i] - a).abs() < 1e-9);
+            assert!((rhombohedral[i + 3] - rhombohedral[3]).abs() < 1e-9);
+        }
+        let back = cob
+            .inverse()
+            .unwrap()
+            .transform_cell_parameters(&rhombohedral);
+        for (v, expected) in back.iter().zip(hexagonal) {
+            assert!(v.is_finite() && (v - expected).abs() < 1e-9, "{back:?}");
+        }
+        // The cosine of the flat angle between a and a+b computes beyond 1
+        let cell = ChangeOfBasis::try_from_str("a,a+b,c")
+            .unwrap()
+            .transform_cell_parameters(&[0.3, 0.61, 1.0, 90.0, 90.0, 0.0]);
+        assert_eq!(0.0, cell[5]);
+    }
+
+    #[test]
+    fn transform_centred_cell() {
+        // F m -3 m to its primitive rhombohedral cell and back
+        let positions = HallSymbolNotation::try_from_str("-F 4 2 3")
+            .unwrap()
+            .general_positions();
+        let cob = ChangeOfBasis::try_from_str("1/2b+1/2c,1/2a+1/2c,1/2a+1/2b").unwrap();
+        let primitive = cob.transform_general_positions(&positions).unwrap();
+        assert_eq!(1, primitive.lattice_translations().len());
+        assert_eq!(48, primitive.num_of_general_pos());
+        let back = cob
+            .inverse()
+            .unwrap()
+            .transform_general_positions(&primitive)
+            .unwrap();
+        assert!(back.is_same_group(&positions));
+    }
+
+    #[test]
+    fn between_settings() {
+        let pairs = [
+            ("P 1 21/c 1", "P 1 1 21/n"),
+            ("F d d d:1", "F d d d:2"),
+            ("R -3 m:H", "R -3 m:R"),
+            ("P b c a", "P c a b"),
+        ];
+        for (from, to) in pairs {
+            let from = SpaceGroupHallSymbol::try_from_hm(from).unwrap();
+            let to = SpaceGroupHallSymbol::try_from_hm(to).unwrap();
+            let cob = from.change_of_basis_to(&to).unwrap();
+            let transformed = cob
+                .transform_general_positions(&HallSymbolNotation::from(from).general_positions())
+                .unwrap();
+            assert!(transformed.is_same_group(&HallSymbolNotation::from(to).general_positions()));
+        }
+        let by_code = |code: &str| {
+            SpaceGroupHallSymbol::all()
+                .find(|s| s.get_space_group_number_code() == code)
+                .unwrap()
+        };
+        let cob = by_code("14:b1")
+            .change_of_basis_to(&by_code("14:c2"))
+            .unwrap();
+        let operator = SeitzMatrix::try_from_xyz("-x,y+1/2,-z+1/2").unwrap();
+        assert!(HallSymbolNotation::from(by_code("14:c2"))
+            .general_positions()
+            .contains(&cob.transform_seitz_matrix(&operator).unwrap()));
+        let fddd = SpaceGroupHallSymbol::try_from_hm("F d d d:1").unwrap();
+        let cob = fddd
+            .change_of_basis_to(&SpaceGroupHallSymbol::try_from_hm("F d d d:2").unwrap())
+            .unwrap();
+        // Origin choice 2 is at -1/8,-1/8,-1/8, or 1/8,1/8,1/8 by the Euclidean normalizer
+        assert!(cob
+            .origin_shift()
+            .iter()
+            .all(|v| (v.abs() - 0.125).abs() < 1e-12));
+        assert!(fddd
+            .change_of_basis_to(&SpaceGroupHallSymbol::try_from_hm("P 1").unwrap())
+            .is_none());
+    }
+}
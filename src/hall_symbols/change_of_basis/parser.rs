@@ -0,0 +1,111 @@
+use nalgebra::{Matrix3, Vector3};
+use winnow::{
+    ascii::{multispace0, space0},
+    combinator::{alt, delimited, eof, opt, preceded, separated, terminated},
+    error::StrContext,
+    ModalResult, Parser,
+};
+
+use crate::hall_symbols::{
+    matrix_symbol::{coordinate_rows, cut_error, linear_combination, signed_number},
+    CHANGE_OF_BASIS_BASE_NUMBER, SEITZ_TRANSLATE_BASE_NUMBER,
+};
+
+use super::{ChangeOfBasis, ChangeOfBasisSymbol, Rational};
+
+/// Parse the whole input like `-b,a,c` or `a,b,c; 0,0,1/4` into a `ChangeOfBasis`
+pub fn parse_change_of_basis(input: &mut &str) -> ModalResult<ChangeOfBasis> {
+    terminated(change_of_basis, (multispace0, eof)).parse_next(input)
+}
+
+/// The new basis vectors in terms of `a`, `b`, `c`, optionally followed by `;`
+/// and the origin shift
+pub(crate) fn change_of_basis(input: &mut &str) -> ModalResult<ChangeOfBasis> {
+    let columns: Vec<[Rational; 3]> = separated(
+        3,
+        linear_combination(['a', 'b', 'c']),
+        (space0, ',', space0),
+    )
+    .context(StrContext::Label("Basis vectors"))
+    .parse_next(input)?;
+    let shift: Option<Vec<Rational>> = opt(preceded(
+        (space0, ';', space0),
+        separated(3, preceded(space0, signed_number), (space0, ',', space0)),
+    ))
+    .context(StrContext::Label("Origin shift"))
+    .parse_next(input)?;
+    let p_matrix = Matrix3::from_fn(|i, j| columns[j][i]);
+    let p_vector = shift
+        .map(|v| Vector3::new(v[0], v[1], v[2]))
+        .unwrap_or_else(Vector3::zeros);
+    build(input, p_matrix, p_vector)
+}
+
+/// Parse the operator in the parentheses of a Hall symbol, either the new basis
+/// vectors `(-a+b,a,c)` or the transformed coordinates `(x-y,x,z)`
+pub(crate) fn parse_change_of_basis_symbol(input: &mut &str) -> ModalResult<ChangeOfBasisSymbol> {
+    preceded(
+        space0,
+        delimited(
+            ('(', space0),
+            alt((
+                change_of_basis.map(ChangeOfBasisSymbol::Basis),
+                coordinate_triplet.map(ChangeOfBasisSymbol::Coordinates),
+            )),
+            (space0, ')'),
+        ),
+    )
+    .context(StrContext::Label("Change-of-basis operator"))
+    .parse_next(input)
+}
+
+/// The new coordinates in terms of `x`, `y`, `z` like `x-y,x,z+1/4`, i.e. the inverse
+/// of the change of basis
+fn coordinate_triplet(input: &mut &str) -> ModalResult<ChangeOfBasis> {
+    let rows = coordinate_rows(input)?;
+    let q_matrix = Matrix3::from_fn(|i, j| rows[i].0[j]);
+    let q_vector = Vector3::new(rows[0].1, rows[1].1, rows[2].1);
+    match build(input, q_matrix, q_vector)?.inverse() {
+        Some(change_of_basis) => Ok(change_of_basis),
+        None => Err(cut_error(
+            input,
+            "a transformation whose inverse is in multiples of 1/12 and 1/72",
+        )),
+    }
+}
+
+/// Check the units and the determinant of the parsed `(P, p)`
+pub(crate) fn build(
+    input: &mut &str,
+    p_matrix: Matrix3<Rational>,
+    p_vector: Vector3<Rational>,
+) -> ModalResult<ChangeOfBasis> {
+    let p_matrix = super::to_units(&p_matrix, SEITZ_TRANSLATE_BASE_NUMBER);
+    let p_vector = super::to_units(&p_vector, CHANGE_OF_BASIS_BASE_NUMBER);
+    match (p_matrix, p_vector) {
+        (Some(p_matrix), Some(p_vector)) => match ChangeOfBasis::new(p_matrix, p_vector) {
+            Some(change_of_basis) => Ok(change_of_basis),
+            None => Err(cut_error(input, "linearly independent basis vectors")),
+        },
+        _ => Err(cut_error(
+            input,
+            "a matrix in multiples of 1/12 and a shift in multiples of 1/72",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use nalgebra::Vector3;
+
+    use super::parse_change_of_basis;
+
+    #[test]
+    fn parse_shift_only() {
+        let mut input = "a,b,c;-1/8, -1/8, -1/8";
+        let cob = parse_change_of_basis(&mut input).unwrap();
+        assert_eq!(Vector3::new(-0.125, -0.125, -0.125), cob.origin_shift());
+        let mut input = "a,b,c;1/5,0,0";
+        assert!(parse_change_of_basis(&mut input).is_err());
+    }
+}
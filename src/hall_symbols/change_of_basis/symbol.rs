@@ -0,0 +1,39 @@
+use std::fmt::Display;
+
+use winnow::ModalResult;
+
+use super::{parser::parse_change_of_basis_symbol, ChangeOfBasis};
+
+/// The change-of-basis operator in the parentheses of a Hall symbol in the extended
+/// notation, written either as the new basis vectors like `(-a+b,a,c)` or as the
+/// transformed coordinates like `(x-y,x,z)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd)]
+pub enum ChangeOfBasisSymbol {
+    Basis(ChangeOfBasis),
+    Coordinates(ChangeOfBasis),
+}
+
+impl ChangeOfBasisSymbol {
+    /// Parse the parenthesized operator, e.g. ` (-a+b,a,c)` or ` (x,y,z+1/4)`
+    pub fn try_from_str(input: &mut &str) -> ModalResult<Self> {
+        parse_change_of_basis_symbol(input)
+    }
+
+    /// The change of basis `(P, p)` regardless of the notation
+    pub fn change_of_basis(&self) -> ChangeOfBasis {
+        match self {
+            Self::Basis(change_of_basis) | Self::Coordinates(change_of_basis) => *change_of_basis,
+        }
+    }
+}
+
+impl Display for ChangeOfBasisSymbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Basis(change_of_basis) => write!(f, "({change_of_basis})"),
+            Self::Coordinates(change_of_basis) => {
+                write!(f, "({})", change_of_basis.coordinate_triplet())
+            }
+        }
+    }
+}
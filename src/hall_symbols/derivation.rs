@@ -0,0 +1,572 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::OnceLock,
+};
+
+use nalgebra::{Matrix3, Vector3};
+
+use crate::{database::SpaceGroupHallSymbol, utils::positive_mod_stbn_i32};
+
+use super::{
+    lattice_symbol::{LatticeSymbol, Lattices},
+    matrix_symbol::{MatrixSymbol, NFold, NFoldDiag, NFoldSub, RotationAxis},
+    origin_shift::OriginShift,
+    parser::implied_axis,
+    translation_symbol::TranslationSymbol,
+    GeneralPositions, HallSymbolNotation, SeitzMatrix, SEITZ_TRANSLATE_BASE_NUMBER,
+};
+
+/// Translation symbols in the order they are written in a Hall symbol.
+const TRANSLATION_SYMBOLS: [TranslationSymbol; 8] = [
+    TranslationSymbol::A,
+    TranslationSymbol::B,
+    TranslationSymbol::C,
+    TranslationSymbol::N,
+    TranslationSymbol::U,
+    TranslationSymbol::V,
+    TranslationSymbol::W,
+    TranslationSymbol::D,
+];
+
+/// Lattice symbols in the order they are tried
+const LATTICES: [Lattices; 7] = [
+    Lattices::P,
+    Lattices::A,
+    Lattices::B,
+    Lattices::C,
+    Lattices::I,
+    Lattices::R,
+    Lattices::F,
+];
+
+/// Range of the searched origin shift components, in the unit of `1/SEITZ_TRANSLATE_BASE_NUMBER`
+const MAX_ORIGIN_SHIFT: i32 = SEITZ_TRANSLATE_BASE_NUMBER / 2;
+
+impl HallSymbolNotation {
+    /// Derive the shortest Hall symbol which regenerates exactly the given group of operators,
+    /// in the way of `sginfo`.
+    /// The lattice symbol is read from the centring translations and the inversion, and the
+    /// generators are searched among the matrix symbols whose rotation parts belong to the
+    /// group, including `-1` for the inversion off the origin. The screw subscripts,
+    /// translation symbols and the origin shift are then searched for the shortest
+    /// notation, where the tabulated symbol is preferred among the equally short ones.
+    /// Returns `None` when the operators do not form a group expressible by a Hall symbol.
+    pub fn try_from_operators(operators: &[SeitzMatrix]) -> Option<Self> {
+        let target = GeneralPositions::from_operators(operators);
+        let generators: Vec<Generator> = skeletons()
+            .iter()
+            .filter_map(|skeleton| Generator::new(skeleton, &target))
+            .collect();
+        let mut sets: Vec<(usize, LatticeSymbol, Vec<usize>)> = lattice_symbols_of(&target)?
+            .into_iter()
+            .flat_map(|lattice_symbol| {
+                generator_sets(&generators, &target, lattice_symbol)
+                    .into_iter()
+                    .map(move |(length, set)| (length, lattice_symbol, set))
+            })
+            .collect();
+        sets.sort_by_key(|(length, _, _)| *length);
+        let tabulated: HashSet<String> = SpaceGroupHallSymbol::all()
+            .map(|symbol| symbol.get_hall_symbol())
+            .collect();
+        let shifts = origin_shifts();
+        let mut best: Option<((usize, bool), HallSymbolNotation)> = None;
+        for (skeleton_length, lattice_symbol, set) in sets {
+            // The translations and the origin shift only make the notation longer
+            if best
+                .as_ref()
+                .is_some_and(|((len, _), _)| skeleton_length > *len)
+            {
+                break;
+            }
+            let set: Vec<&Generator> = set.iter().map(|&i| &generators[i]).collect();
+            let mut symbols: Vec<(usize, HallSymbolNotation)> = shifts
+                .iter()
+                .filter_map(|&v| derive_with_origin_shift(&target, lattice_symbol, &set, v))
+                .map(|(length, symbol)| (skeleton_length + length, symbol))
+                .collect();
+            // Stable sort keeps the smaller origin shifts first among the equally short symbols.
+            symbols.sort_by_key(|(length, _)| *length);
+            let found = symbols
+                .chunk_by(|(a, _), (b, _)| a == b)
+                // Only a shorter symbol, or a tabulated one as short as the best, is better
+                .take_while(|chunk| {
+                    best.as_ref().is_none_or(|((len, untabulated), _)| {
+                        chunk[0].0 < *len || (chunk[0].0 == *len && *untabulated)
+                    })
+                })
+                .find_map(|chunk| {
+                    let mut chunk: Vec<(bool, String)> = chunk
+                        .iter()
+                        .map(|(_, symbol)| {
+                            let symbol = format!("{symbol}");
+                            (!tabulated.contains(&symbol), symbol)
+                        })
+                        .collect();
+                    chunk.sort_by_key(|(untabulated, _)| *untabulated);
+                    chunk.into_iter().find_map(|(untabulated, symbol)| {
+                        // The notation has to be read back into the same group
+                        let parsed = HallSymbolNotation::try_from_str(&symbol).ok()?;
+                        parsed
+                            .try_general_positions()
+                            .is_ok_and(|positions| positions.is_same_group(&target))
+                            .then_some((untabulated, parsed))
+                    })
+                });
+            if let Some((untabulated, symbol)) = found {
+                let key = (notation_length(&symbol), untabulated);
+                if best.as_ref().is_none_or(|(best_key, _)| key < *best_key) {
+                    best = Some((key, symbol));
+                }
+            }
+        }
+        best.map(|(_, symbol)| symbol)
+    }
+}
+
+/// A matrix symbol without translations, with its variants of the translations
+struct Skeleton {
+    symbol: MatrixSymbol,
+    rotation: Matrix3<i32>,
+    /// Variants of the symbol keyed by their translation parts, see `translation_candidates`
+    candidates: HashMap<Vector3<i32>, (usize, MatrixSymbol)>,
+}
+
+/// The skeletons of `skeleton_symbols`, built once
+fn skeletons() -> &'static [Skeleton] {
+    static SKELETONS: OnceLock<Vec<Skeleton>> = OnceLock::new();
+    SKELETONS.get_or_init(|| {
+        skeleton_symbols()
+            .into_iter()
+            .filter_map(|symbol| {
+                let rotation = symbol.seitz_matrix().ok()?.rotation_part();
+                Some(Skeleton {
+                    candidates: translation_candidates(&symbol),
+                    symbol,
+                    rotation,
+                })
+            })
+            .collect()
+    })
+}
+
+/// A skeleton whose rotation part belongs to the target group
+struct Generator {
+    skeleton: &'static Skeleton,
+    /// Translation part of the target operation with the rotation part
+    translation: Vector3<i32>,
+}
+
+impl Generator {
+    fn new(skeleton: &'static Skeleton, target: &GeneralPositions) -> Option<Self> {
+        let translation = target
+            .core_position_set()
+            .iter()
+            .find(|g| g.rotation_part() == skeleton.rotation)?
+            .translation_part();
+        Some(Self {
+            skeleton,
+            translation,
+        })
+    }
+}
+
+/// The lattice symbols with the centring translations of the group. A centrosymmetric
+/// group is written either with the minus sign or with the inversion `-1` as a generator.
+fn lattice_symbols_of(target: &GeneralPositions) -> Option<Vec<LatticeSymbol>> {
+    let centrosymmetric = target
+        .core_position_set()
+        .iter()
+        .any(|m| m.rotation_part() == -Matrix3::identity());
+    let reduced = |translations: &[Vector3<i32>]| {
+        let mut reduced: Vec<[i32; 3]> = translations
+            .iter()
+            .map(|t| t.map(positive_mod_stbn_i32).into())
+            .collect();
+        reduced.sort();
+        reduced.dedup();
+        reduced
+    };
+    let lattice = reduced(target.lattice_translations());
+    let char = LATTICES
+        .iter()
+        .copied()
+        .find(|&char| reduced(&LatticeSymbol::new(false, char).get_translations()) == lattice)?;
+    Some(if centrosymmetric {
+        vec![
+            LatticeSymbol::new(true, char),
+            LatticeSymbol::new(false, char),
+        ]
+    } else {
+        vec![LatticeSymbol::new(false, char)]
+    })
+}
+
+/// The matrix symbols without translations, `1`, `-1` and the ones with all the axis and
+/// diagonal symbols recognised by the notation
+fn skeleton_symbols() -> Vec<MatrixSymbol> {
+    let axes = [RotationAxis::X, RotationAxis::Y, RotationAxis::Z];
+    let mut skeletons: Vec<(NFold, NFoldDiag, RotationAxis)> = Vec::new();
+    for nfold in [NFold::N2, NFold::N3, NFold::N4, NFold::N6] {
+        axes.iter()
+            .for_each(|&axis| skeletons.push((nfold, NFoldDiag::None, axis)));
+    }
+    for diag in [NFoldDiag::SingleQuote, NFoldDiag::DoubleQuote] {
+        axes.iter()
+            .for_each(|&axis| skeletons.push((NFold::N2, diag, axis)));
+    }
+    skeletons.push((NFold::N3, NFoldDiag::Asterisk, RotationAxis::Z));
+    let identity = (NFold::N1, NFoldDiag::None, RotationAxis::Z);
+    [(false, identity), (true, identity)]
+        .into_iter()
+        .chain([false, true].iter().flat_map(|&minus_sign| {
+            skeletons
+                .iter()
+                .map(move |&skeleton| (minus_sign, skeleton))
+        }))
+        .filter_map(|(minus_sign, (nfold, diag, axis))| {
+            MatrixSymbol::new_builder()
+                .set_minus_sign(minus_sign)
+                .set_nfold_body(nfold)
+                .set_nfold_diag(diag)
+                .set_rotation_axis(axis)
+                .build()
+                .ok()
+        })
+        .collect()
+}
+
+/// The sequences of up to four generators in the order of the Hall symbols, each adding
+/// a new rotation, which generate the rotation parts of the group together with the
+/// lattice symbol, with the length
+/// of their notations without translations. A group of the lattice symbol alone is
+/// generated by the symbol `1`. Without the minus sign of the lattice symbol the inversion
+/// `-1` may close the sequence as an extra generator.
+/// Of the orderings of the same generators only the shortest ones are kept, as the
+/// translations needed do not depend on the order. The shortest sequences come first.
+fn generator_sets(
+    generators: &[Generator],
+    target: &GeneralPositions,
+    lattice_symbol: LatticeSymbol,
+) -> Vec<(usize, Vec<usize>)> {
+    let order = target.core_position_set().len();
+    let mut initial = vec![Matrix3::identity()];
+    if lattice_symbol.minus_sign() {
+        initial.push(-Matrix3::identity());
+    }
+    let mut sets: Vec<Vec<usize>> = Vec::new();
+    if initial.len() == order {
+        sets.extend(
+            generators
+                .iter()
+                .position(|g| matches!(g.skeleton.symbol.nfold_body(), NFold::N1))
+                .map(|i| vec![i]),
+        );
+    }
+    let mut stack: Vec<(Vec<usize>, Vec<Matrix3<i32>>)> = vec![(Vec::new(), initial)];
+    while let Some((set, closure)) = stack.pop() {
+        if closure.len() == order {
+            continue;
+        }
+        generators
+            .iter()
+            .enumerate()
+            .filter(|(_, g)| !closure.contains(&g.skeleton.rotation))
+            // The inversion of the lattice symbol makes the rotoinversions redundant
+            .filter(|(_, g)| !(lattice_symbol.minus_sign() && g.skeleton.symbol.minus_sign()))
+            // As in the tabulated symbols, the principal axis is followed by a 2-fold axis
+            // and then by the 3-fold axis along the body diagonal
+            .filter(|(_, g)| {
+                is_inversion(g)
+                    || match set.len() {
+                        0 => true,
+                        1 => matches!(g.skeleton.symbol.nfold_body(), NFold::N2),
+                        2 => matches!(g.skeleton.symbol.nfold_diag(), NFoldDiag::Asterisk),
+                        _ => false,
+                    }
+            })
+            // The diagonal axes `'` and `"` refer to the axis of the preceding symbol, and
+            // are written only with the implied axis
+            .filter(|(_, g)| {
+                let symbol = &g.skeleton.symbol;
+                matches!(symbol.nfold_diag(), NFoldDiag::None | NFoldDiag::Asterisk)
+                    || set.last().is_some_and(|&j| {
+                        let preceding = &generators[j].skeleton.symbol;
+                        let (axis, _) = implied_axis(
+                            set.len(),
+                            symbol.nfold_body(),
+                            Some(preceding.nfold_body()),
+                        );
+                        preceding.rotation_axis() == symbol.rotation_axis()
+                            && axis == symbol.rotation_axis()
+                    })
+            })
+            .for_each(|(i, g)| {
+                let extended = rotation_closure(&closure, &g.skeleton.rotation);
+                let mut next = set.clone();
+                next.push(i);
+                if extended.len() == order {
+                    sets.push(next);
+                } else if next.len() < 4 && !is_inversion(g) {
+                    stack.push((next, extended));
+                }
+            });
+    }
+    let mut shortest: HashMap<Vec<usize>, (usize, Vec<Vec<usize>>)> = HashMap::new();
+    sets.into_iter().for_each(|set| {
+        let symbols = set
+            .iter()
+            .map(|&i| generators[i].skeleton.symbol.clone())
+            .collect();
+        let skeleton = HallSymbolNotation::new(lattice_symbol, symbols, OriginShift::default());
+        let length = format!("{skeleton}").len();
+        let mut key = set.clone();
+        key.sort();
+        let entry = shortest.entry(key).or_insert((length, Vec::new()));
+        if length < entry.0 {
+            *entry = (length, Vec::new());
+        }
+        if length == entry.0 {
+            entry.1.push(set);
+        }
+    });
+    let mut sets: Vec<(usize, Vec<usize>)> = shortest
+        .into_values()
+        .flat_map(|(length, sets)| sets.into_iter().map(move |set| (length, set)))
+        .collect();
+    sets.sort();
+    sets
+}
+
+/// Whether the generator is the inversion `-1`
+fn is_inversion(generator: &Generator) -> bool {
+    generator.skeleton.rotation == -Matrix3::identity()
+}
+
+/// The group generated by the rotation parts of a group and a new one
+fn rotation_closure(group: &[Matrix3<i32>], generator: &Matrix3<i32>) -> Vec<Matrix3<i32>> {
+    let mut closure = group.to_vec();
+    closure.push(*generator);
+    let mut i = 0;
+    while i < closure.len() {
+        let products: Vec<Matrix3<i32>> = std::iter::once(generator)
+            .chain(group.iter())
+            .map(|g| closure[i] * g)
+            .collect();
+        products.into_iter().for_each(|m| {
+            if !closure.contains(&m) {
+                closure.push(m);
+            }
+        });
+        i += 1;
+    }
+    closure
+}
+
+/// Origin shifts in the unit of `1/SEITZ_TRANSLATE_BASE_NUMBER`, the ones with fewer
+/// and smaller non-zero components first
+fn origin_shifts() -> Vec<Vector3<i32>> {
+    let mut shifts: Vec<Vector3<i32>> = (-MAX_ORIGIN_SHIFT + 1..=MAX_ORIGIN_SHIFT)
+        .flat_map(|x| {
+            (-MAX_ORIGIN_SHIFT + 1..=MAX_ORIGIN_SHIFT).flat_map(move |y| {
+                (-MAX_ORIGIN_SHIFT + 1..=MAX_ORIGIN_SHIFT).map(move |z| Vector3::new(x, y, z))
+            })
+        })
+        .collect();
+    shifts.sort_by_key(|v| {
+        (
+            v.iter().filter(|&&c| c != 0).count(),
+            v.abs().sum(),
+            v.iter().filter(|&&c| c < 0).count(),
+        )
+    });
+    shifts
+}
+
+/// Length of the notation, where any origin shift is counted as the length
+/// of `" (0 0 1)"`, so that smaller shifts are not penalised by their signs.
+fn notation_length(symbol: &HallSymbolNotation) -> usize {
+    let unshifted = HallSymbolNotation::new(
+        symbol.lattice_symbol,
+        symbol.matrix_symbols.clone(),
+        OriginShift::default(),
+    );
+    let shift_length = if symbol.origin_shift != OriginShift::default() {
+        " (0 0 1)".len()
+    } else {
+        0
+    };
+    format!("{unshifted}").len() + shift_length
+}
+
+/// Build the Hall symbol whose generators, shifted by `origin_shift`, match the target group,
+/// with the number of characters the translations and the origin shift add to the skeletons.
+fn derive_with_origin_shift(
+    target: &GeneralPositions,
+    lattice_symbol: LatticeSymbol,
+    generators: &[&Generator],
+    origin_shift: Vector3<i32>,
+) -> Option<(usize, HallSymbolNotation)> {
+    let lattice_translations = lattice_symbol.get_translations();
+    // The operator `(W, w)` of the target group is generated by `(W, w + (W - I)v)`
+    // in the shifted basis.
+    let required_translation = |rotation: &Matrix3<i32>, translation: &Vector3<i32>| {
+        translation + (rotation - Matrix3::identity()) * origin_shift
+    };
+    if lattice_symbol.minus_sign() {
+        let inversion = target
+            .core_position_set()
+            .iter()
+            .find(|g| g.rotation_part() == -Matrix3::identity())?;
+        let w = required_translation(&inversion.rotation_part(), &inversion.translation_part());
+        let centred = lattice_translations
+            .iter()
+            .any(|t| (w + t).iter().all(|v| v % SEITZ_TRANSLATE_BASE_NUMBER == 0));
+        if !centred {
+            return None;
+        }
+    }
+    let matrix_symbols = generators
+        .iter()
+        .map(|g| {
+            let w = required_translation(&g.skeleton.rotation, &g.translation);
+            lattice_translations
+                .iter()
+                .filter_map(|t| {
+                    g.skeleton
+                        .candidates
+                        .get(&(w + t).map(positive_mod_stbn_i32))
+                })
+                .min_by_key(|(length, _)| *length)
+                .cloned()
+        })
+        .collect::<Option<Vec<(usize, MatrixSymbol)>>>()?;
+    let shift_length = if origin_shift != Vector3::zeros() {
+        " (0 0 1)".len()
+    } else {
+        0
+    };
+    let length = matrix_symbols
+        .iter()
+        .map(|(length, _)| length)
+        .sum::<usize>()
+        + shift_length;
+    let [va, vb, vc] = [origin_shift.x, origin_shift.y, origin_shift.z];
+    Some((
+        length,
+        HallSymbolNotation::new(
+            lattice_symbol,
+            matrix_symbols
+                .into_iter()
+                .map(|(_, symbol)| symbol)
+                .collect(),
+            OriginShift::new(va, vb, vc),
+        ),
+    ))
+}
+
+/// All the variants of a matrix symbol with the same rotation part but different
+/// screw and translation symbols, keyed by the translation part they produce, with the
+/// number of characters they add to the symbol.
+/// The shortest variant is kept for each translation.
+fn translation_candidates(symbol: &MatrixSymbol) -> HashMap<Vector3<i32>, (usize, MatrixSymbol)> {
+    let subs: Vec<NFoldSub> = if symbol.minus_sign()
+        || !matches!(symbol.nfold_diag(), NFoldDiag::None)
+        || matches!(symbol.rotation_axis(), RotationAxis::Omitted)
+    {
+        vec![NFoldSub::None]
+    } else {
+        match symbol.nfold_body() {
+            NFold::N3 => vec![NFoldSub::None, NFoldSub::N1, NFoldSub::N2],
+            NFold::N4 => vec![NFoldSub::None, NFoldSub::N1, NFoldSub::N3],
+            NFold::N6 => vec![
+                NFoldSub::None,
+                NFoldSub::N1,
+                NFoldSub::N2,
+                NFoldSub::N4,
+                NFoldSub::N5,
+            ],
+            _ => vec![NFoldSub::None],
+        }
+    };
+    let mut candidates: HashMap<Vector3<i32>, (usize, MatrixSymbol)> = HashMap::new();
+    subs.iter().for_each(|&sub| {
+        (0_usize..1 << TRANSLATION_SYMBOLS.len()).for_each(|mask| {
+            let translations: Vec<TranslationSymbol> = TRANSLATION_SYMBOLS
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| mask & (1 << i) != 0)
+                .map(|(_, &t)| t)
+                .collect();
+            let Ok(variant) = MatrixSymbol::new_builder()
+                .set_minus_sign(symbol.minus_sign())
+                .set_nfold_body(symbol.nfold_body())
+                .set_nfold_sub(sub)
+                .set_nfold_diag(symbol.nfold_diag())
+                .set_rotation_axis(symbol.rotation_axis())
+                .set_translation_symbols((!translations.is_empty()).then_some(translations))
+                .build()
+            else {
+                return;
+            };
+            let Ok(seitz_matrix) = variant.seitz_matrix() else {
+                return;
+            };
+            let key = seitz_matrix.translation_part().map(positive_mod_stbn_i32);
+            let length = format!("{variant}").len() - format!("{symbol}").len();
+            if candidates
+                .get(&key)
+                .is_none_or(|(existed, _)| length < *existed)
+            {
+                candidates.insert(key, (length, variant));
+            }
+        })
+    });
+    candidates
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{GeneralPositions, HallSymbolNotation, SeitzMatrix};
+
+    fn derive(symbol: &str) -> HallSymbolNotation {
+        let positions = HallSymbolNotation::try_from_str(symbol)
+            .unwrap()
+            .general_positions();
+        let operators = positions.derive_full_sets().concat();
+        let derived = HallSymbolNotation::try_from_operators(&operators).unwrap();
+        assert!(derived.general_positions().is_same_group(&positions));
+        derived
+    }
+
+    #[test]
+    fn derive_tabulated() {
+        for symbol in [
+            "P 2yb",
+            "-P 2ybc",
+            "C 2c -2",
+            "P 61 2 (0 0 -1)",
+            "P 2 2 -1n",
+            "F 2 2 -1d",
+            "-F 4vw 2vw 3",
+        ] {
+            assert_eq!(symbol, format!("{}", derive(symbol)));
+        }
+    }
+
+    #[test]
+    fn derive_shifted() {
+        // P 2/m 2/m 2/m with the origin moved to 1/4,1/4,1/4, where the inversion written as
+        // a generator is shorter than the origin shift
+        let operators = SeitzMatrix::try_from_xyz_list(
+            "x,y,z; -x+1/2,-y+1/2,z; -x+1/2,y,-z+1/2; x,-y+1/2,-z+1/2;
+             -x+1/2,-y+1/2,-z+1/2; x,y,-z+1/2; x,-y+1/2,z; -x+1/2,y,z",
+        )
+        .unwrap();
+        let derived = HallSymbolNotation::try_from_operators(&operators).unwrap();
+        assert_eq!("P 2ab 2bc -1n", format!("{derived}"));
+        assert!(derived
+            .general_positions()
+            .is_same_group(&GeneralPositions::from_operators(&operators)));
+    }
+}
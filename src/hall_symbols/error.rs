@@ -0,0 +1,272 @@
+use std::{error::Error, fmt::Display, ops::Range};
+
+use winnow::error::{ContextError, ErrMode, StrContext};
+
+/// Error of parsing a Hall symbol, locating the offending part of the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HallSymbolError {
+    input: String,
+    span: Range<usize>,
+    expected: Vec<String>,
+    message: String,
+}
+
+impl HallSymbolError {
+    /// Build the error from the parser failure, where `remaining` is the input left
+    /// unparsed at the failure.
+    pub(crate) fn from_parser(input: &str, remaining: &str, error: ErrMode<ContextError>) -> Self {
+        let offset = input.len() - remaining.len();
+        let context = match error {
+            ErrMode::Backtrack(e) | ErrMode::Cut(e) => e,
+            ErrMode::Incomplete(_) => ContextError::new(),
+        };
+        let labels: Vec<&str> = context
+            .context()
+            .filter_map(|c| match c {
+                StrContext::Label(label) => Some(*label),
+                _ => None,
+            })
+            .collect();
+        let expected: Vec<String> = context
+            .context()
+            .filter_map(|c| match c {
+                StrContext::Expected(value) => Some(format!("{value}")),
+                _ => None,
+            })
+            .collect();
+        // The innermost label is the most specific one
+        let message = match labels.first() {
+            Some(label) => format!("Invalid {} at position {offset}", label.to_lowercase()),
+            None => format!("Invalid Hall symbol at position {offset}"),
+        };
+        Self {
+            input: input.to_string(),
+            span: offset..token_end(input, offset),
+            expected,
+            message,
+        }
+    }
+
+    /// The error of the characters left after a complete symbol in the strict mode
+    pub(crate) fn trailing(input: &str, offset: usize) -> Self {
+        Self {
+            input: input.to_string(),
+            span: offset..input.trim_end().len(),
+            expected: vec!["a matrix symbol, an origin shift or the end of the symbol".to_string()],
+            message: format!(
+                "Unexpected `{}` at position {offset}",
+                input[offset..].trim_end()
+            ),
+        }
+    }
+
+    /// The error of a symbol without any matrix symbol in the strict mode
+    pub(crate) fn missing_matrix_symbol(input: &str, offset: usize) -> Self {
+        Self {
+            input: input.to_string(),
+            span: offset..offset,
+            expected: vec!["at least one matrix symbol like `1` or `2y`".to_string()],
+            message: format!("Missing matrix symbol at position {offset}"),
+        }
+    }
+
+    /// The whole parsed input
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Byte range of the offending part of the input
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Descriptions of what the parser expected at the span
+    pub fn expected(&self) -> &[String] {
+        &self.expected
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// End of the token starting at `offset`, at least one character long when any is left
+fn token_end(input: &str, offset: usize) -> usize {
+    let rest = &input[offset..];
+    let length = rest
+        .char_indices()
+        .skip(1)
+        .find(|(_, c)| c.is_whitespace())
+        .map(|(i, _)| i)
+        .unwrap_or(rest.len());
+    offset + length
+}
+
+impl Display for HallSymbolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)?;
+        if !self.expected.is_empty() {
+            write!(f, ", expected {}", self.expected.join(" or "))?;
+        }
+        let marker_length = (self.span.end - self.span.start).max(1);
+        write!(
+            f,
+            "\n{}\n{}{}",
+            self.input,
+            " ".repeat(self.input[..self.span.start].chars().count()),
+            "^".repeat(marker_length)
+        )
+    }
+}
+
+impl Error for HallSymbolError {}
+
+/// Error of generating the group from a parsed Hall symbol
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GroupGenerationError {
+    /// The matrix symbol has no rotation matrix, e.g. `4'`
+    InvalidMatrixSymbol(String),
+    /// The symbol has only the lattice part
+    NoMatrixSymbol,
+    /// The generators produce more rotations than any crystallographic point group
+    InfiniteGroup,
+    /// A generated rotation, in the Jones-faithful representation, is missing from the
+    /// tabulated rotations of the crystal family
+    UnknownRotation(String),
+    /// The change-of-basis operator gives translations out of the `1/12` units
+    InvalidChangeOfBasis(String),
+}
+
+impl Display for GroupGenerationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GroupGenerationError::InvalidMatrixSymbol(symbol) => {
+                write!(f, "Matrix symbol `{symbol}` has no rotation matrix")
+            }
+            GroupGenerationError::NoMatrixSymbol => f.write_str("No matrix symbol is given"),
+            GroupGenerationError::InfiniteGroup => {
+                f.write_str("The generators do not form a crystallographic point group")
+            }
+            GroupGenerationError::UnknownRotation(rotation) => {
+                write!(f, "Rotation `{rotation}` is not a tabulated rotation")
+            }
+            GroupGenerationError::InvalidChangeOfBasis(symbol) => {
+                write!(
+                    f,
+                    "Change of basis `{symbol}` cannot be applied to the group"
+                )
+            }
+        }
+    }
+}
+
+impl Error for GroupGenerationError {}
+
+#[cfg(test)]
+mod test {
+    use nalgebra::Vector3;
+
+    use crate::{database::SpaceGroupHallSymbol, group::SubgroupRelationError, HallSymbolNotation};
+
+    use super::GroupGenerationError;
+
+    #[test]
+    fn malformed_symbols() {
+        // (symbol, start of the span, a word of the message)
+        let cases = [
+            ("", 0, "lattice"),
+            ("Q 2", 0, "lattice"),
+            ("-", 1, "lattice"),
+            ("P 2 (1 2", 8, "origin shift"),
+            ("P 2 (1 2 x)", 9, "origin shift"),
+            ("P 61 2 (0 0 -1", 14, "origin shift"),
+            ("P 2 2 (a,a,c)", 12, "change-of-basis"),
+            ("P 2 2 (a,b,c;1/5,0,0)", 20, "change-of-basis"),
+            ("P 2 (x,y,z+1/0)", 14, "change-of-basis"),
+        ];
+        for (symbol, start, word) in cases {
+            let error = HallSymbolNotation::try_from_str(symbol).unwrap_err();
+            assert_eq!(start, error.span().start, "{symbol}: {error}");
+            assert!(error.message().contains(word), "{symbol}: {error}");
+            assert!(!error.expected().is_empty(), "{symbol}: {error}");
+            let strict = HallSymbolNotation::try_from_str_strict(symbol).unwrap_err();
+            assert_eq!(error.span(), strict.span(), "{symbol}: {strict}");
+        }
+    }
+
+    #[test]
+    fn strict_mode() {
+        // Only the strict mode rejects the trailing characters and the lone lattice
+        assert!(HallSymbolNotation::try_from_str("P 2 xyz").is_ok());
+        assert!(HallSymbolNotation::try_from_str("-P 2ybc ;").is_ok());
+        assert!(HallSymbolNotation::try_from_str("-P").is_ok());
+        let error = HallSymbolNotation::try_from_str_strict("P 2 xyz").unwrap_err();
+        assert_eq!(4..7, error.span());
+        assert_eq!(
+            "P 2 xyz\n    ^^^",
+            format!("{error}")
+                .lines()
+                .skip(1)
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+        assert!(error.message().contains("xyz"));
+        let error = HallSymbolNotation::try_from_str_strict("-P 2ybc ;").unwrap_err();
+        assert_eq!(8..9, error.span());
+        assert!(HallSymbolNotation::try_from_str_strict("P 2 2 (0 0 1) 3").is_err());
+        assert!(HallSymbolNotation::try_from_str_strict("-P 2ybc \n").is_ok());
+        let error = HallSymbolNotation::try_from_str_strict("-P").unwrap_err();
+        assert_eq!(2..2, error.span());
+        assert!(HallSymbolNotation::try_from_str_strict("P 61 2 (0 0 -1) ").is_ok());
+        SpaceGroupHallSymbol::all().for_each(|symbol| {
+            assert!(HallSymbolNotation::try_from_str_strict(&symbol.get_hall_symbol()).is_ok())
+        });
+    }
+
+    #[test]
+    fn generation_errors() {
+        let generate = |symbol: &str| {
+            HallSymbolNotation::try_from_str(symbol)
+                .unwrap()
+                .try_general_positions()
+        };
+        assert_eq!(Err(GroupGenerationError::NoMatrixSymbol), generate("P"));
+        assert!(matches!(
+            generate("P 4'"),
+            Err(GroupGenerationError::InvalidMatrixSymbol(_))
+        ));
+        // 4-fold and 3-fold rotations about the same axis
+        assert_eq!(Err(GroupGenerationError::InfiniteGroup), generate("P 4 3"));
+        assert!(matches!(
+            generate("-P 1 (a,b,c;1/72,0,0)"),
+            Err(GroupGenerationError::InvalidChangeOfBasis(_))
+        ));
+        assert!(generate("-P 4 2 3").is_ok());
+    }
+
+    #[test]
+    fn entry_points_without_group() {
+        // None of the entry points taking a user-supplied symbol panics
+        let symbol = HallSymbolNotation::try_from_str("P 4 3").unwrap();
+        let hkl = Vector3::new(1, 0, 0);
+        let error = Err(GroupGenerationError::InfiniteGroup);
+        assert_eq!(None, symbol.point_group());
+        assert_eq!(error, symbol.wyckoff_positions().map(|_| ()));
+        assert_eq!(
+            error,
+            symbol
+                .assign_wyckoff_position(&Vector3::new(0.1, 0.2, 0.3), 0.01)
+                .map(|_| ())
+        );
+        assert_eq!(error, symbol.reflection_conditions().map(|_| ()));
+        assert_eq!(error, symbol.is_systematically_absent(&hkl).map(|_| ()));
+        assert_eq!(error, symbol.equivalent_reflections(&hkl).map(|_| ()));
+        assert_eq!(error, symbol.maximal_subgroups().map(|_| ()));
+        assert_eq!(
+            Err(SubgroupRelationError::GroupGeneration(
+                GroupGenerationError::InfiniteGroup
+            )),
+            symbol.subgroup_relation(&symbol).map(|_| ())
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use std::fmt::Display;
 
 use fraction::GenericFraction;
-use nalgebra::Vector3;
+use nalgebra::{Matrix3, Vector3};
 
 use crate::hall_symbols::SEITZ_TRANSLATE_BASE_NUMBER;
 
@@ -23,6 +23,46 @@ impl GeneralPositions {
             core_position_set,
         }
     }
+    /// Split a list of operators, e.g. read from a CIF file, into the lattice
+    /// translations (operators with identity rotation) and the representative
+    /// operators of each rotation part. Translations are reduced into `[0, 1)`.
+    pub fn from_operators(operators: &[SeitzMatrix]) -> Self {
+        let normalized: Vec<SeitzMatrix> = operators
+            .iter()
+            .map(|&m| {
+                let mut m = m;
+                m.set_translation_part(
+                    m.translation_part()
+                        .map(|v| v.rem_euclid(SEITZ_TRANSLATE_BASE_NUMBER)),
+                );
+                m
+            })
+            .collect();
+        let mut lattice_translations: Vec<Vector3<i32>> = vec![Vector3::zeros()];
+        normalized
+            .iter()
+            .filter(|m| m.rotation_part() == Matrix3::identity())
+            .for_each(|m| {
+                if !lattice_translations.contains(&m.translation_part()) {
+                    lattice_translations.push(m.translation_part());
+                }
+            });
+        let mut core_position_set: Vec<SeitzMatrix> = vec![SeitzMatrix::identity()];
+        normalized.iter().for_each(|m| {
+            if core_position_set
+                .iter()
+                .all(|c| c.rotation_part() != m.rotation_part())
+            {
+                core_position_set.push(*m);
+            }
+        });
+        Self::new(lattice_translations, core_position_set)
+    }
+
+    pub fn lattice_translations(&self) -> &[Vector3<i32>] {
+        &self.lattice_translations
+    }
+
     pub fn derive_full_sets(&self) -> Vec<Vec<SeitzMatrix>> {
         self.lattice_translations
             .iter()
@@ -34,6 +74,63 @@ impl GeneralPositions {
         &self.core_position_set
     }
 
+    /// Whether the operator belongs to the group, modulo the lattice translations.
+    pub fn contains(&self, seitz_matrix: &SeitzMatrix) -> bool {
+        self.core_position_set
+            .iter()
+            .filter(|m| m.rotation_part() == seitz_matrix.rotation_part())
+            .any(|m| {
+                self.lattice_translations.iter().any(|&t| {
+                    (seitz_matrix.translation_part() - m.translation_part() - t)
+                        .iter()
+                        .all(|v| v % SEITZ_TRANSLATE_BASE_NUMBER == 0)
+                })
+            })
+    }
+
+    /// Whether the two sets of general positions describe the same group,
+    /// regardless of the chosen coset representatives and their order.
+    pub fn is_same_group(&self, other: &Self) -> bool {
+        self.lattice_translations.len() == other.lattice_translations.len()
+            && self.core_position_set.len() == other.core_position_set.len()
+            && self
+                .lattice_translations
+                .iter()
+                .all(|&t| other.contains(&(SeitzMatrix::identity() + t)))
+            && self.core_position_set.iter().all(|m| other.contains(m))
+    }
+
+    /// Orbit of a point in fractional coordinates: the images under every operator of
+    /// `derive_full_sets()` wrapped into `[0, 1)`, with the images closer than `tolerance`
+    /// (modulo the lattice) merged, and the operators fixing the point.
+    pub fn orbit(&self, point: Vector3<f64>, tolerance: f64) -> Orbit {
+        let mut positions: Vec<Vector3<f64>> = Vec::new();
+        let mut stabilizer: Vec<SeitzMatrix> = Vec::new();
+        self.derive_full_sets().concat().iter().for_each(|m| {
+            let image = m.transform_point(&point);
+            let t = (point - image).map(|v| v.round());
+            if (image + t - point).norm() < tolerance {
+                let mut fixing = *m;
+                fixing.set_translation_part(
+                    m.translation_part() + t.map(|v| v as i32 * SEITZ_TRANSLATE_BASE_NUMBER),
+                );
+                stabilizer.push(fixing);
+            }
+            let wrapped = image.map(wrap_into_cell);
+            let duplicated = positions.iter().any(|p| {
+                let d = wrapped - p;
+                (d - d.map(|v| v.round())).norm() < tolerance
+            });
+            if !duplicated {
+                positions.push(wrapped);
+            }
+        });
+        Orbit {
+            positions,
+            stabilizer,
+        }
+    }
+
     pub fn num_of_general_pos(&self) -> usize {
         self.core_position_set.len()
     }
@@ -75,6 +172,42 @@ impl GeneralPositions {
     }
 }
 
+/// Wrap a fractional coordinate into `[0, 1)`
+fn wrap_into_cell(v: f64) -> f64 {
+    let wrapped = v.rem_euclid(1.0);
+    // `rem_euclid` rounds tiny negative values up to 1.0
+    if wrapped >= 1.0 {
+        0.0
+    } else {
+        wrapped
+    }
+}
+
+/// Equivalent positions of a point and its site-symmetry operations,
+/// see `GeneralPositions::orbit`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Orbit {
+    positions: Vec<Vector3<f64>>,
+    stabilizer: Vec<SeitzMatrix>,
+}
+
+impl Orbit {
+    /// Unique equivalent positions in `[0, 1)`, in the order of the operators
+    pub fn positions(&self) -> &[Vector3<f64>] {
+        &self.positions
+    }
+
+    /// Operators fixing the point, with the lattice translations needed to fix it
+    pub fn stabilizer(&self) -> &[SeitzMatrix] {
+        &self.stabilizer
+    }
+
+    /// Number of the equivalent positions in the cell
+    pub fn multiplicity(&self) -> usize {
+        self.positions.len()
+    }
+}
+
 impl Display for GeneralPositions {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let full_sets = self.derive_full_sets();
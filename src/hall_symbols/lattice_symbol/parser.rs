@@ -1,5 +1,5 @@
 use winnow::{
-    error::{ContextError, StrContext},
+    error::{ContextError, StrContext, StrContextValue},
     token::one_of,
     ModalResult, Parser,
 };
@@ -21,7 +21,12 @@ fn parse_minus_sign<'s>(input: &mut &'s str) -> ModalResult<&'s str> {
 }
 
 fn parse_symbol_char(input: &mut &str) -> ModalResult<Lattices> {
-    let symbol_char = one_of(['P', 'A', 'B', 'C', 'I', 'R', 'F']).parse_next(input)?;
+    let symbol_char = one_of(['P', 'A', 'B', 'C', 'I', 'R', 'F'])
+        .context(StrContext::Label("Lattice symbol"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "one of `P`, `A`, `B`, `C`, `I`, `R`, `F`, optionally preceded by `-`",
+        )))
+        .parse_next(input)?;
     match symbol_char {
         'P' => Ok(Lattices::P),
         'A' => Ok(Lattices::A),
@@ -0,0 +1,400 @@
+use std::fmt::Display;
+
+use fraction::Zero;
+use nalgebra::{Matrix3, Vector3};
+
+use crate::{
+    hall_symbols::{
+        change_of_basis::affine_expression, matrix_symbol::RotationType,
+        SEITZ_TRANSLATE_BASE_NUMBER,
+    },
+    utils::Rational,
+    wyckoff::affine::{to_f64, AffineSubspace, PARAMETER_NAMES},
+};
+
+use super::{SeitzMatrix, SeitzMatrixError};
+
+/// Type of a symmetry operation in its geometric interpretation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OperationKind {
+    Identity,
+    Translation,
+    Rotation,
+    ScrewRotation,
+    Inversion,
+    Rotoinversion,
+    Reflection,
+    GlideReflection,
+}
+
+/// Sense of the rotation of the axes `3`, `4` and `6` and their rotoinversions,
+/// seen from the tip of the axis direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RotationSense {
+    /// Counter-clockwise, `+`
+    Positive,
+    /// Clockwise, `-`
+    Negative,
+}
+
+impl Display for RotationSense {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RotationSense::Positive => f.write_str("+"),
+            RotationSense::Negative => f.write_str("-"),
+        }
+    }
+}
+
+/// The geometric interpretation of a `SeitzMatrix` as in the International Tables,
+/// printed like `2(0,1/2,0) 0,y,1/4`, `c x,1/4,z` or `-4+ 0,0,z; 0,0,0`
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeometricDescription {
+    kind: OperationKind,
+    /// `n` of the rotation `n` or the rotoinversion `-n`
+    order: u8,
+    sense: Option<RotationSense>,
+    direction: Option<Vector3<i32>>,
+    /// Screw or glide component, or the whole translation
+    intrinsic: [Rational; 3],
+    /// Axis or plane as the coordinates with the free parameters
+    location: Option<String>,
+    centre: Option<[Rational; 3]>,
+}
+
+impl GeometricDescription {
+    pub fn kind(&self) -> OperationKind {
+        self.kind
+    }
+
+    /// `n` of the rotation `n` or the rotoinversion `-n`, `2` for the reflections and
+    /// `1` for the identity, the translations and the inversion
+    pub fn order(&self) -> u8 {
+        self.order
+    }
+
+    pub fn sense(&self) -> Option<RotationSense> {
+        self.sense
+    }
+
+    /// Direction of the rotation or rotoinversion axis, or the normal of the plane
+    pub fn direction(&self) -> Option<Vector3<i32>> {
+        self.direction
+    }
+
+    /// The screw or glide component, or the translation vector of a pure translation
+    pub fn intrinsic_translation(&self) -> Vector3<f64> {
+        Vector3::from_fn(|i, _| to_f64(self.intrinsic[i]))
+    }
+
+    /// The axis or the plane, e.g. `0,y,1/4` or `x,x,z`
+    pub fn location(&self) -> Option<&str> {
+        self.location.as_deref()
+    }
+
+    /// The inversion centre or the inversion point on the rotoinversion axis
+    pub fn centre(&self) -> Option<Vector3<f64>> {
+        self.centre.map(|c| Vector3::from_fn(|i, _| to_f64(c[i])))
+    }
+
+    /// Symbol of the glide reflection from the glide vector and the plane normal: `a`, `b`
+    /// or `c` for half a basis vector, `n` for half a face diagonal of a plane normal to a
+    /// basis vector or half the body diagonal `(a+b+c)/2` of a diagonal plane, `d` for half
+    /// of these vectors and `g` otherwise
+    pub fn glide_symbol(&self) -> Option<char> {
+        if self.kind != OperationKind::GlideReflection {
+            return None;
+        }
+        let nonzero: Vec<usize> = (0..3).filter(|&i| !self.intrinsic[i].is_zero()).collect();
+        let denominators = |d: i64| nonzero.iter().all(|&i| *self.intrinsic[i].denom() == d);
+        // Components of the diagonal: two for a plane normal to a basis vector, otherwise three
+        let diagonal = match self.direction {
+            Some(normal) if normal.iter().filter(|v| **v != 0).count() == 1 => 2,
+            _ => 3,
+        };
+        let symbol = match nonzero.as_slice() {
+            [i] if denominators(2) => ['a', 'b', 'c'][*i],
+            _ if nonzero.len() == diagonal && denominators(2) => 'n',
+            _ if nonzero.len() == diagonal && denominators(4) => 'd',
+            _ => 'g',
+        };
+        Some(symbol)
+    }
+}
+
+impl Display for GeometricDescription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let vector = |v: &[Rational; 3]| {
+            let components: Vec<String> = v.iter().map(|c| format!("{c}")).collect();
+            format!("({})", components.join(","))
+        };
+        let sense = self.sense.map(|s| s.to_string()).unwrap_or_default();
+        let location = self.location.as_deref().unwrap_or_default();
+        match self.kind {
+            OperationKind::Identity => write!(f, "1"),
+            OperationKind::Translation => write!(f, "t{}", vector(&self.intrinsic)),
+            OperationKind::Rotation => write!(f, "{}{sense} {location}", self.order),
+            OperationKind::ScrewRotation => write!(
+                f,
+                "{}{sense}{} {location}",
+                self.order,
+                vector(&self.intrinsic)
+            ),
+            OperationKind::Inversion | OperationKind::Rotoinversion => {
+                let centre = self.centre.unwrap_or_default();
+                let centre: Vec<String> = centre.iter().map(|c| format!("{c}")).collect();
+                if self.kind == OperationKind::Inversion {
+                    write!(f, "-1 {}", centre.join(","))
+                } else {
+                    write!(f, "-{}{sense} {location}; {}", self.order, centre.join(","))
+                }
+            }
+            OperationKind::Reflection => write!(f, "m {location}"),
+            OperationKind::GlideReflection => {
+                let symbol = self.glide_symbol().unwrap_or('g');
+                if matches!(symbol, 'a' | 'b' | 'c') {
+                    write!(f, "{symbol} {location}")
+                } else {
+                    write!(f, "{symbol}{} {location}", vector(&self.intrinsic))
+                }
+            }
+        }
+    }
+}
+
+impl SeitzMatrix {
+    /// The intrinsic part `w_g = (W⁰ + W¹ + ... + Wⁿ⁻¹)w / n` of the translation, the screw
+    /// or glide component, with `n` the order of the rotation part
+    pub fn intrinsic_part(&self) -> Result<Vector3<f64>, SeitzMatrixError> {
+        Ok(self.translation_parts()?.0.map(to_f64))
+    }
+
+    /// The location part `w_l = w - w_g` of the translation, which only shifts the axis,
+    /// the plane or the centre off the origin
+    pub fn location_part(&self) -> Result<Vector3<f64>, SeitzMatrixError> {
+        Ok(self.translation_parts()?.1.map(to_f64))
+    }
+
+    /// Whether the operation is a translation `(I, w)` with `w ≠ 0`
+    pub fn is_pure_translation(&self) -> bool {
+        self.rotation_part() == Matrix3::identity() && self.translation_part() != Vector3::zeros()
+    }
+
+    /// The intrinsic and the location parts of the translation as exact fractions
+    pub(crate) fn translation_parts(
+        &self,
+    ) -> Result<(Vector3<Rational>, Vector3<Rational>), SeitzMatrixError> {
+        let rotation = self.rotation_part();
+        let order = self.rotation_order()?;
+        let translation = self
+            .translation_part()
+            .map(|v| Rational::new(v as i64, SEITZ_TRANSLATE_BASE_NUMBER as i64));
+        let mut sum = Matrix3::<i32>::zeros();
+        let mut power = Matrix3::<i32>::identity();
+        for _ in 0..order {
+            sum += power;
+            power *= rotation;
+        }
+        let intrinsic = sum.map(|v| Rational::from_integer(v as i64)) * translation
+            / Rational::from_integer(order as i64);
+        Ok((intrinsic, translation - intrinsic))
+    }
+
+    /// The geometric interpretation of the operation: the type, the sense, the axis or
+    /// the plane with the screw or glide component, and the inversion centre.
+    /// The translation is taken as it is, without the reduction into the unit cell.
+    pub fn geometric_description(&self) -> Result<GeometricDescription, SeitzMatrixError> {
+        let rotation_type = self.rotation_type()?;
+        let rotation = self.rotation_part();
+        let (intrinsic, location_part) = self.translation_parts()?;
+        let intrinsic = [intrinsic[0], intrinsic[1], intrinsic[2]];
+        let location_part = [location_part[0], location_part[1], location_part[2]];
+        let has_intrinsic = intrinsic.iter().any(|v| !v.is_zero());
+
+        let (kind, order) = match rotation_type {
+            RotationType::E if has_intrinsic => (OperationKind::Translation, 1),
+            RotationType::E => (OperationKind::Identity, 1),
+            RotationType::I => (OperationKind::Inversion, 1),
+            RotationType::M if has_intrinsic => (OperationKind::GlideReflection, 2),
+            RotationType::M => (OperationKind::Reflection, 2),
+            RotationType::M3 => (OperationKind::Rotoinversion, 3),
+            RotationType::M4 => (OperationKind::Rotoinversion, 4),
+            RotationType::M6 => (OperationKind::Rotoinversion, 6),
+            _ => {
+                let kind = if has_intrinsic {
+                    OperationKind::ScrewRotation
+                } else {
+                    OperationKind::Rotation
+                };
+                (kind, self.rotation_order()? as u8)
+            }
+        };
+        let direction = self.axis_direction();
+        let sense = direction
+            .filter(|_| order > 2)
+            .map(|u| rotation_sense(&(rotation * self.det()), &u));
+        let fixed = AffineSubspace::fixed_points(&rotation, &location_part);
+        let (location, centre) = match kind {
+            OperationKind::Identity | OperationKind::Translation => (None, None),
+            OperationKind::Rotation | OperationKind::ScrewRotation => (
+                fixed.map(|line| line_expression(&line.point(), &direction.unwrap())),
+                None,
+            ),
+            OperationKind::Inversion => (None, fixed.map(|point| point.point())),
+            OperationKind::Rotoinversion => {
+                let centre = fixed.map(|point| point.point());
+                (
+                    centre.map(|c| line_expression(&c, &direction.unwrap())),
+                    centre,
+                )
+            }
+            OperationKind::Reflection | OperationKind::GlideReflection => {
+                (fixed.map(|plane| parametric_expression(&plane)), None)
+            }
+        };
+        Ok(GeometricDescription {
+            kind,
+            order,
+            sense,
+            direction,
+            intrinsic,
+            location,
+            centre,
+        })
+    }
+}
+
+/// Positive when `det(u, x, Wx) > 0` for a vector `x` off the axis `u` of the proper
+/// rotation `W`
+fn rotation_sense(proper: &Matrix3<i32>, axis: &Vector3<i32>) -> RotationSense {
+    let det = (0..3)
+        .map(|i| {
+            let x = Vector3::ith(i, 1);
+            Matrix3::from_columns(&[*axis, x, proper * x])
+                .map(|v| v as f64)
+                .determinant()
+                .round() as i32
+        })
+        .find(|det| *det != 0)
+        .expect("A rotation axis is not parallel to every basis vector");
+    if det > 0 {
+        RotationSense::Positive
+    } else {
+        RotationSense::Negative
+    }
+}
+
+/// The line `p + tu` written with the parameter of the first non-zero component of `u`,
+/// e.g. `0,y,1/4` or `-x,x,-x`
+fn line_expression(point: &[Rational; 3], direction: &Vector3<i32>) -> String {
+    let i = (0..3)
+        .find(|&i| direction[i] != 0)
+        .expect("The direction is not zero");
+    let t = point[i] / Rational::from_integer(direction[i] as i64);
+    let coordinates: Vec<String> = (0..3)
+        .map(|j| {
+            let mut coefficients = [Rational::zero(); 3];
+            coefficients[i] = Rational::from_integer(direction[j] as i64);
+            let constant = point[j] - t * Rational::from_integer(direction[j] as i64);
+            affine_expression(&coefficients, constant, PARAMETER_NAMES)
+        })
+        .collect();
+    coordinates.join(",")
+}
+
+/// The subspace in the parametric form, e.g. `x,y,1/4` or `x,x,z`
+fn parametric_expression(subspace: &AffineSubspace) -> String {
+    let (constant, coefficients) = subspace.parametric_form();
+    let coordinates: Vec<String> = (0..3)
+        .map(|i| affine_expression(&coefficients[i], constant[i], PARAMETER_NAMES))
+        .collect();
+    coordinates.join(",")
+}
+
+#[cfg(test)]
+mod test {
+    use nalgebra::{Matrix4, Vector3};
+
+    use crate::SeitzMatrix;
+
+    use super::{OperationKind, RotationSense};
+
+    fn symbol(xyz: &str) -> String {
+        SeitzMatrix::try_from_xyz(xyz)
+            .unwrap()
+            .geometric_description()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn geometric_symbols() {
+        assert_eq!("1", symbol("x,y,z"));
+        assert_eq!("t(1/2,1/2,0)", symbol("x+1/2,y+1/2,z"));
+        assert_eq!("2 0,0,z", symbol("-x,-y,z"));
+        assert_eq!("2(0,1/2,0) 0,y,1/4", symbol("-x,y+1/2,-z+1/2"));
+        assert_eq!("-1 0,0,0", symbol("-x,-y,-z"));
+        assert_eq!("c x,1/4,z", symbol("x,-y+1/2,z+1/2"));
+        assert_eq!("m x,y,0", symbol("x,y,-z"));
+        assert_eq!("n(1/2,1/2,0) x,y,1/4", symbol("x+1/2,y+1/2,-z+1/2"));
+        assert_eq!("d(1/4,1/4,0) x,y,1/8", symbol("x+1/4,y+1/4,-z+1/4"));
+        assert_eq!("3+ x,x,x", symbol("z,x,y"));
+        assert_eq!("3- x,x,x", symbol("y,z,x"));
+        assert_eq!("3+ -x,x,-x", symbol("z,-x,-y"));
+        assert_eq!("3+ x,-x,-x", symbol("-z,-x,y"));
+        assert_eq!("4+ 0,0,z", symbol("-y,x,z"));
+        assert_eq!("4-(0,0,3/4) 1/2,0,z", symbol("y+1/2,-x+1/2,z+3/4"));
+        assert_eq!("-4+ 0,0,z; 0,0,0", symbol("y,-x,-z"));
+        assert_eq!("6+ 0,0,z", symbol("x-y,x,z"));
+        assert_eq!("2 x,x,0", symbol("y,x,-z"));
+        assert_eq!("2 x,-x,0", symbol("-y,-x,-z"));
+        assert_eq!("m x,x,z", symbol("y,x,z"));
+        assert_eq!("g(1/4,1/4,1/2) x,x,z", symbol("y+1/4,x+1/4,z+1/2"));
+        assert_eq!("g(1/2,1/2,0) x,x,z", symbol("y+1/2,x+1/2,z"));
+        assert_eq!("n(1/2,1/2,1/2) x,x,z", symbol("y+1/2,x+1/2,z+1/2"));
+        assert_eq!("c x,x,z", symbol("y,x,z+1/2"));
+
+        let screw = SeitzMatrix::try_from_xyz("-y,x,z+1/4")
+            .unwrap()
+            .geometric_description()
+            .unwrap();
+        assert_eq!(OperationKind::ScrewRotation, screw.kind());
+        assert_eq!(4, screw.order());
+        assert_eq!(Some(RotationSense::Positive), screw.sense());
+        assert_eq!(Some(Vector3::new(0, 0, 1)), screw.direction());
+        assert_eq!(Vector3::new(0.0, 0.0, 0.25), screw.intrinsic_translation());
+        assert_eq!(Some("0,0,z"), screw.location());
+        let rotoinversion = SeitzMatrix::try_from_xyz("-z+1/2,-x+1/2,-y+1/2")
+            .unwrap()
+            .geometric_description()
+            .unwrap();
+        assert_eq!(Some(Vector3::new(0.25, 0.25, 0.25)), rotoinversion.centre());
+    }
+
+    #[test]
+    fn translation_parts() {
+        let glide = SeitzMatrix::try_from_xyz("x+1/2,-y+1/2,z+3/4").unwrap();
+        assert_eq!(Ok(Vector3::new(0.5, 0.0, 0.75)), glide.intrinsic_part());
+        assert_eq!(Ok(Vector3::new(0.0, 0.5, 0.0)), glide.location_part());
+        let three = SeitzMatrix::try_from_xyz("z+1/2,x,y").unwrap();
+        assert_eq!(
+            Ok(Vector3::new(1.0, 1.0, 1.0) / 6.0),
+            three.intrinsic_part()
+        );
+        assert_eq!(
+            Ok(Vector3::new(1.0 / 3.0, -1.0 / 6.0, -1.0 / 6.0)),
+            three.location_part()
+        );
+        assert!(SeitzMatrix::try_from_xyz("x+1/2,y+1/2,z")
+            .unwrap()
+            .is_pure_translation());
+        assert!(!SeitzMatrix::identity().is_pure_translation());
+        assert!(!three.is_pure_translation());
+        // A shear has no finite order
+        let shear = SeitzMatrix::new(Matrix4::new(
+            -1, 1, 0, 0, 0, -1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1,
+        ));
+        assert!(shear.intrinsic_part().is_err());
+        assert!(shear.geometric_description().is_err());
+    }
+}
@@ -4,6 +4,14 @@ use nalgebra::Matrix4;
 
 use super::{MatrixSymbol, MatrixSymbolError};
 
+pub use geometric::{GeometricDescription, OperationKind, RotationSense};
+pub(crate) use parser::{coordinate_rows, cut_error, linear_combination, signed_number};
+
+/// Geometric interpretation of `SeitzMatrix`
+mod geometric;
+/// Parser of the Jones-faithful representation for `SeitzMatrix`, shared with the
+/// change-of-basis operators
+mod parser;
 /// Implementation detail for `MatrixSymbol`
 mod rotation_matrices;
 /// Implementation detail for `SeitzMatrix`
@@ -26,7 +34,7 @@ impl Display for SeitzMatrixError {
 }
 
 impl MatrixSymbol {
-    pub fn seitz_matrix(&self) -> Result<SeitzMatrix, MatrixSymbolError> {
+    pub fn seitz_matrix(&self) -> Result<SeitzMatrix, MatrixSymbolError<'_>> {
         let rot_mat = self.get_rotation_matrix()?;
         if self.minus_sign {
             let transformed_mat = self
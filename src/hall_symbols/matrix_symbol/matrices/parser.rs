@@ -0,0 +1,263 @@
+use nalgebra::{Matrix3, Vector3};
+use winnow::{
+    ascii::{digit0, digit1, multispace0, space0},
+    combinator::{alt, delimited, eof, opt, preceded, repeat, separated, terminated},
+    error::{AddContext, ContextError, ErrMode, StrContext, StrContextValue},
+    stream::Stream,
+    token::one_of,
+    ModalResult, Parser,
+};
+
+use crate::{hall_symbols::SEITZ_TRANSLATE_BASE_NUMBER, utils::Rational, wyckoff::affine::to_f64};
+
+use super::SeitzMatrix;
+
+/// Tolerance (in units of `1/SEITZ_TRANSLATE_BASE_NUMBER`) when converting
+/// decimal translations such as `0.3333` into the integer representation.
+const DECIMAL_TOLERANCE: f64 = 1e-2;
+
+/// Coefficients of `x`, `y`, `z` and the constant of a row like `x-y+1/2`
+pub(crate) type AffineRow = ([Rational; 3], Rational);
+
+/// Parse a single operator like `-y,x-y,z+1/3` into a `SeitzMatrix`.
+/// The whole input must be consumed.
+pub fn parse_xyz_operator(input: &mut &str) -> ModalResult<SeitzMatrix> {
+    terminated(xyz_operator, (multispace0, eof)).parse_next(input)
+}
+
+/// Parse a list of operators separated by `;` or line breaks.
+/// Each operator can be optionally quoted by `'` or `"`, as in CIF files.
+pub fn parse_xyz_operator_list(input: &mut &str) -> ModalResult<Vec<SeitzMatrix>> {
+    delimited(
+        multispace0,
+        separated(1.., quoted_xyz_operator, operator_separator),
+        (opt(operator_separator), multispace0, eof),
+    )
+    .parse_next(input)
+}
+
+fn operator_separator<'s>(input: &mut &'s str) -> ModalResult<&'s str> {
+    delimited(space0, alt((";", "\r\n", "\n")), multispace0).parse_next(input)
+}
+
+fn quoted_xyz_operator(input: &mut &str) -> ModalResult<SeitzMatrix> {
+    alt((
+        delimited('\'', terminated(xyz_operator, space0), '\''),
+        delimited('"', terminated(xyz_operator, space0), '"'),
+        xyz_operator,
+    ))
+    .parse_next(input)
+}
+
+fn xyz_operator(input: &mut &str) -> ModalResult<SeitzMatrix> {
+    let rows = coordinate_rows
+        .context(StrContext::Label("Jones-faithful operator"))
+        .parse_next(input)?;
+    if rows.iter().any(|(c, _)| c.iter().any(|v| !v.is_integer())) {
+        return Err(cut_error(input, "Integer coefficients of x, y and z"));
+    }
+    let rotation = Matrix3::from_fn(|i, j| rows[i].0[j].to_integer() as i32);
+    // Decimal translations are rounded to the nearest multiple of 1/12
+    let scaled = rows.map(|(_, t)| to_f64(t) * SEITZ_TRANSLATE_BASE_NUMBER as f64);
+    if scaled
+        .iter()
+        .any(|v| (v - v.round()).abs() >= DECIMAL_TOLERANCE)
+    {
+        return Err(cut_error(
+            input,
+            "Translation must be close to a multiple of 1/12",
+        ));
+    }
+    let translation = Vector3::from_fn(|i, _| scaled[i].round() as i32);
+    let mut mat = rotation.to_homogeneous();
+    mat.set_column(3, &translation.push(1));
+    let seitz_matrix = SeitzMatrix::new(mat);
+    if seitz_matrix.rotation_order().is_err() {
+        return Err(cut_error(
+            input,
+            "The rotation part is not a crystallographic symmetry operation",
+        ));
+    }
+    Ok(seitz_matrix)
+}
+
+/// Three rows like `x-y,x,z+1/4` separated by commas
+pub(crate) fn coordinate_rows(input: &mut &str) -> ModalResult<[AffineRow; 3]> {
+    let rows: Vec<AffineRow> = separated(
+        3,
+        affine_combination(['x', 'y', 'z']),
+        (space0, ',', space0),
+    )
+    .parse_next(input)?;
+    Ok([rows[0], rows[1], rows[2]])
+}
+
+pub(crate) fn cut_error(input: &mut &str, description: &'static str) -> ErrMode<ContextError> {
+    let err_context = ContextError::<StrContext>::new().add_context(
+        input,
+        &input.checkpoint(),
+        StrContext::Expected(StrContextValue::Description(description)),
+    );
+    ErrMode::Cut(err_context)
+}
+
+/// A sum of terms like `a-b`, `1/2a+1/2b` or `x-y`, with the coefficients of the symbols
+pub(crate) fn linear_combination<'s>(
+    symbols: [char; 3],
+) -> impl Parser<&'s str, [Rational; 3], ErrMode<ContextError>> {
+    move |input: &mut &'s str| {
+        let first = preceded(space0, term(symbols, false)).parse_next(input)?;
+        let rest: Vec<(usize, Rational)> =
+            repeat(0.., preceded(space0, term(symbols, true))).parse_next(input)?;
+        let mut coefficients = [Rational::default(); 3];
+        std::iter::once(first)
+            .chain(rest)
+            .for_each(|(i, c)| coefficients[i] += c);
+        Ok(coefficients)
+    }
+}
+
+/// A sum of terms in arbitrary order like `x-y+1/2`, `1/3 + Z` or `-X + 0.25`,
+/// with the coefficients of the symbols and the constant.
+/// The sign of the first term is optional.
+fn affine_combination<'s>(
+    symbols: [char; 3],
+) -> impl Parser<&'s str, AffineRow, ErrMode<ContextError>> {
+    move |input: &mut &'s str| {
+        let term_or_constant = |sign_required: bool| {
+            alt((
+                term(symbols, sign_required).map(|(i, c)| (Some(i), c)),
+                constant(sign_required).map(|c| (None, c)),
+            ))
+        };
+        let first = preceded(space0, term_or_constant(false)).parse_next(input)?;
+        let rest: Vec<(Option<usize>, Rational)> =
+            repeat(0.., preceded(space0, term_or_constant(true))).parse_next(input)?;
+        let mut coefficients = [Rational::default(); 3];
+        let mut constant = Rational::default();
+        std::iter::once(first)
+            .chain(rest)
+            .for_each(|(i, c)| match i {
+                Some(i) => coefficients[i] += c,
+                None => constant += c,
+            });
+        Ok((coefficients, constant))
+    }
+}
+
+/// A constant term like `1/4`, `-1/2`, `+1` or `0.25`
+fn constant<'s>(sign_required: bool) -> impl Parser<&'s str, Rational, ErrMode<ContextError>> {
+    move |input: &mut &'s str| {
+        let sign = if sign_required {
+            Some(one_of(['+', '-']).parse_next(input)?)
+        } else {
+            opt(one_of(['+', '-'])).parse_next(input)?
+        };
+        let value = preceded(space0, unsigned_number).parse_next(input)?;
+        Ok(if matches!(sign, Some('-')) {
+            -value
+        } else {
+            value
+        })
+    }
+}
+
+/// A term with an optional coefficient, e.g. `-b`, `+2/3a` or `X`
+fn term<'s>(
+    symbols: [char; 3],
+    sign_required: bool,
+) -> impl Parser<&'s str, (usize, Rational), ErrMode<ContextError>> {
+    move |input: &mut &'s str| {
+        let sign = if sign_required {
+            Some(one_of(['+', '-']).parse_next(input)?)
+        } else {
+            opt(one_of(['+', '-'])).parse_next(input)?
+        };
+        let coefficient = opt(preceded(space0, unsigned_number))
+            .parse_next(input)?
+            .unwrap_or(Rational::from_integer(1));
+        let symbol = preceded(
+            space0,
+            one_of(|c: char| symbols.contains(&c.to_ascii_lowercase())),
+        )
+        .parse_next(input)?;
+        let index = symbols
+            .iter()
+            .position(|&s| s == symbol.to_ascii_lowercase())
+            .unwrap();
+        let factor = if matches!(sign, Some('-')) { -1 } else { 1 };
+        Ok((index, coefficient * Rational::from_integer(factor)))
+    }
+}
+
+pub(crate) fn signed_number(input: &mut &str) -> ModalResult<Rational> {
+    constant(false).parse_next(input)
+}
+
+/// Fractions like `1/3`, decimals like `0.25`, `.5` or integers
+fn unsigned_number(input: &mut &str) -> ModalResult<Rational> {
+    alt((fraction, decimal)).parse_next(input)
+}
+
+fn fraction(input: &mut &str) -> ModalResult<Rational> {
+    let (numerator, denominator) = (
+        digit1.parse_to::<i64>(),
+        preceded((space0, '/', space0), digit1.parse_to::<i64>()),
+    )
+        .parse_next(input)?;
+    if denominator == 0 {
+        return Err(cut_error(input, "a nonzero denominator"));
+    }
+    Ok(Rational::new(numerator, denominator))
+}
+
+fn decimal(input: &mut &str) -> ModalResult<Rational> {
+    let text =
+        alt(((digit1, opt(('.', digit0))).take(), ('.', digit1).take())).parse_next(input)?;
+    let digits: String = text.chars().filter(|c| *c != '.').collect();
+    let places = text.split_once('.').map_or(0, |(_, f)| f.len() as u32);
+    match (digits.parse::<i64>(), 10_i64.checked_pow(places)) {
+        (Ok(numerator), Some(denominator)) => Ok(Rational::new(numerator, denominator)),
+        _ => Err(cut_error(input, "a number with at most 18 digits")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::hall_symbols::matrix_symbol::SeitzMatrix;
+
+    #[test]
+    fn parse_jones_faithful() {
+        let m = SeitzMatrix::try_from_xyz("-y,x-y,z+1/3").unwrap();
+        assert_eq!("-y,x-y,z+1/3", m.jones_faithful_repr());
+        let m2 = SeitzMatrix::try_from_xyz(" 1/3 + Z , -Y ,X ").unwrap();
+        assert_eq!("z+1/3,-y,x", m2.jones_faithful_repr());
+        let m3 = SeitzMatrix::try_from_xyz("-x+0.5,y+0.3333,-z+.25").unwrap();
+        assert_eq!("-x+1/2,y+1/3,-z+1/4", m3.jones_faithful_repr());
+        assert!(SeitzMatrix::try_from_xyz("x,y").is_err());
+        assert!(SeitzMatrix::try_from_xyz("x,y,z+1/5").is_err());
+        assert!(SeitzMatrix::try_from_xyz("1/2x,y,z").is_err());
+        assert!(SeitzMatrix::try_from_xyz("x,x,z").is_err());
+        assert!(SeitzMatrix::try_from_xyz("x+y,y,z").is_err());
+        assert!(SeitzMatrix::try_from_xyz("x,y+z,z").is_err());
+        assert!(SeitzMatrix::try_from_xyz("x,y,z,").is_err());
+    }
+
+    #[test]
+    fn parse_jones_faithful_round_trip() {
+        let m = SeitzMatrix::try_from_xyz("-x+y,-x,z+2/3").unwrap();
+        let back = SeitzMatrix::try_from_xyz(&m.jones_faithful_repr()).unwrap();
+        assert_eq!(m, back);
+        assert_eq!(m.matrix(), back.matrix());
+    }
+
+    #[test]
+    fn parse_jones_faithful_list() {
+        let list = SeitzMatrix::try_from_xyz_list("'x, y, z'\n'-x, y+1/2, -z+1/2'\n").unwrap();
+        assert_eq!(2, list.len());
+        let list = SeitzMatrix::try_from_xyz_list("x,y,z; -x,-y,z; -x,-y,-z;").unwrap();
+        assert_eq!(3, list.len());
+        assert_eq!(SeitzMatrix::inversion(), list[2]);
+        assert!(SeitzMatrix::try_from_xyz_list("x,y,z; -x,-y").is_err());
+    }
+}
@@ -165,7 +165,7 @@ impl<const A: char> RotationMatrix for Rotation<3, '*', A> {
 }
 
 impl MatrixSymbol {
-    pub(crate) fn get_rotation_matrix(&self) -> Result<Matrix4<i32>, MatrixSymbolError> {
+    pub(crate) fn get_rotation_matrix(&self) -> Result<Matrix4<i32>, MatrixSymbolError<'_>> {
         let fold = self.nfold_body;
         let diag = self.nfold_diag;
         let axis = self.rotation_axis;
@@ -266,7 +266,7 @@ impl MatrixSymbol {
     pub(crate) fn set_translation_from_symbols(
         &self,
         mut rot_mat: Matrix4<i32>,
-    ) -> Result<Matrix4<i32>, MatrixSymbolError> {
+    ) -> Result<Matrix4<i32>, MatrixSymbolError<'_>> {
         match &self.translation_symbols {
             Some(translations) => {
                 let final_translations: Vector3<i32> = translations
@@ -296,7 +296,7 @@ impl MatrixSymbol {
     pub(crate) fn set_transform(
         &self,
         mut rot_mat: Matrix4<i32>,
-    ) -> Result<Matrix4<i32>, MatrixSymbolError> {
+    ) -> Result<Matrix4<i32>, MatrixSymbolError<'_>> {
         match (self.nfold_body, self.nfold_sub) {
             (NFold::Invalid, _) => Err(MatrixSymbolError::Invalid(self)),
             (NFold::N1, _) => Ok(rot_mat),
@@ -1,5 +1,6 @@
-use fraction::{GenericFraction, Zero};
+use fraction::{GenericFraction, Integer, Zero};
 use nalgebra::{Matrix3, Matrix4, Vector3};
+use winnow::ModalResult;
 
 use crate::hall_symbols::{
     matrix_symbol::RotationType, SymmetryElement, SEITZ_TRANSLATE_BASE_NUMBER,
@@ -11,7 +12,10 @@ use std::{
     ops::{Add, Mul, Sub},
 };
 
-use super::{SeitzMatrix, SeitzMatrixError};
+use super::{
+    parser::{parse_xyz_operator, parse_xyz_operator_list},
+    SeitzMatrix, SeitzMatrixError,
+};
 
 impl Hash for SeitzMatrix {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
@@ -61,6 +65,19 @@ impl SeitzMatrix {
     pub fn new(v: Matrix4<i32>) -> Self {
         Self(v)
     }
+    /// Parse the Jones-faithful representation, e.g. `-y,x-y,z+1/3`.
+    /// Translations can be written as fractions or decimals, terms in arbitrary order,
+    /// with optional whitespace and uppercase letters.
+    pub fn try_from_xyz(input: &str) -> ModalResult<Self> {
+        let mut input = input;
+        parse_xyz_operator(&mut input)
+    }
+    /// Parse a list of Jones-faithful representations separated by `;` or line breaks,
+    /// e.g. the operators from `_space_group_symop_operation_xyz` in CIF files.
+    pub fn try_from_xyz_list(input: &str) -> ModalResult<Vec<Self>> {
+        let mut input = input;
+        parse_xyz_operator_list(&mut input)
+    }
     pub fn is_unique_rotation(&self, reference: &Self) -> bool {
         self.rotation_part() != reference.rotation_part()
             && self.rotation_part().map(|v| -v) != reference.rotation_part()
@@ -124,6 +141,47 @@ impl SeitzMatrix {
             _ => Err(SeitzMatrixError::NotRotationMatrix(self.matrix())),
         }
     }
+    /// The smallest `n` such that the n-th power of the rotation part is the identity.
+    /// Matrices that only match the determinant and trace of a rotation, such as shears,
+    /// have no finite order and are rejected.
+    pub fn rotation_order(&self) -> Result<usize, SeitzMatrixError> {
+        let order = match self.rotation_type()? {
+            RotationType::E => 1,
+            RotationType::N2 | RotationType::I | RotationType::M => 2,
+            RotationType::N3 => 3,
+            RotationType::N4 | RotationType::M4 => 4,
+            RotationType::N6 | RotationType::M3 | RotationType::M6 => 6,
+        };
+        let rotation = self.rotation_part();
+        let power = (1..order).fold(rotation, |acc, _| acc * rotation);
+        if power == Matrix3::identity() {
+            Ok(order)
+        } else {
+            Err(SeitzMatrixError::NotRotationMatrix(self.matrix()))
+        }
+    }
+
+    /// Direction of the rotation or rotoinversion axis, or the normal of the mirror plane,
+    /// as the primitive integer vector spanning the kernel of `det(W) W - I`.
+    /// The sign follows the International Tables: the body diagonals `[1,1,1]`,
+    /// `[1,-1,-1]`, `[-1,1,-1]` and `[-1,-1,1]`, and otherwise the first non-zero
+    /// component positive, e.g. `[1,-1,0]`.
+    /// `None` for the identity and the inversion.
+    pub fn axis_direction(&self) -> Option<Vector3<i32>> {
+        let a = self.proper_rotation()? - Matrix3::identity();
+        let rows: Vec<Vector3<i32>> = a.row_iter().map(|row| row.transpose()).collect();
+        let axis = [(0, 1), (0, 2), (1, 2)]
+            .iter()
+            .map(|&(i, j)| rows[i].cross(&rows[j]))
+            .find(|v| *v != Vector3::zeros())?;
+        let axis = axis / axis.iter().fold(0, |gcd, v| gcd.gcd(v));
+        let flip = if axis.iter().all(|v| v.abs() == 1) {
+            axis.iter().product::<i32>() < 0
+        } else {
+            axis.iter().find(|v| **v != 0).is_some_and(|v| *v < 0)
+        };
+        Some(if flip { -axis } else { axis })
+    }
 
     pub(crate) fn proper_rotation(&self) -> Option<Matrix3<i32>> {
         if self
@@ -138,7 +196,7 @@ impl SeitzMatrix {
         }
     }
 
-    fn det(&self) -> i32 {
+    pub(super) fn det(&self) -> i32 {
         self.to_f64_mat().fixed_resize::<3, 3>(1.0).determinant() as i32
     }
 
@@ -202,6 +260,14 @@ impl SeitzMatrix {
         mat_frac
     }
 
+    /// Apply the operation to a point in fractional coordinates
+    pub fn transform_point(&self, point: &Vector3<f64>) -> Vector3<f64> {
+        self.rotation_part().map(|v| v as f64) * point
+            + self
+                .translation_part()
+                .map(|v| v as f64 / SEITZ_TRANSLATE_BASE_NUMBER as f64)
+    }
+
     pub fn try_inverse(&self) -> Option<Self> {
         let mut inv = self.to_f64_mat().try_inverse()?;
         inv.column_mut(3).iter_mut().enumerate().for_each(|(i, v)| {
@@ -10,7 +10,8 @@ mod notations;
 mod parser;
 
 pub use builder::MatrixSymbolBuilder;
-pub use matrices::SeitzMatrix;
+pub(crate) use matrices::{coordinate_rows, cut_error, linear_combination, signed_number};
+pub use matrices::{GeometricDescription, OperationKind, RotationSense, SeitzMatrix};
 pub use notations::*;
 use winnow::ModalResult;
 
@@ -152,4 +153,20 @@ mod test {
         println!("{}", m3);
         println!("{}", m3.seitz_matrix().unwrap());
     }
+
+    #[test]
+    fn display_rotation_axis() {
+        // The z axis is the default of a matrix symbol and is not printed
+        assert_eq!("", format!("{}", RotationAxis::Z));
+        assert_eq!("", format!("{}", RotationAxis::Omitted));
+        let m2z = MatrixSymbol::new_builder()
+            .set_nfold_body(NFold::N2)
+            .set_rotation_axis(RotationAxis::Z)
+            .build()
+            .unwrap();
+        assert_eq!("2", format!("{m2z}"));
+        let parsed = MatrixSymbol::try_from_str(&mut "2z").unwrap();
+        assert_eq!(RotationAxis::Z, parsed.rotation_axis());
+        assert_eq!("2", format!("{parsed}"));
+    }
 }
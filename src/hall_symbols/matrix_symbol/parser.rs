@@ -46,11 +46,13 @@ pub fn parse_hall_matrix_symbol(input: &mut &str) -> ModalResult<MatrixSymbol> {
                     .unwrap())
             }
         }
+        // The axis is omitted, and will be restored by the rules of the default axis directions.
         Err(_) => {
             if let Ok((sub, translations)) = parse_translations(input) {
                 Ok(MatrixSymbol::new_builder()
                     .set_minus_sign(sign)
                     .set_nfold_body(nfold)
+                    .set_rotation_axis(RotationAxis::Omitted)
                     .set_nfold_sub(sub)
                     .set_translation_symbols(translations)
                     .build()
@@ -59,6 +61,7 @@ pub fn parse_hall_matrix_symbol(input: &mut &str) -> ModalResult<MatrixSymbol> {
                 Ok(MatrixSymbol::new_builder()
                     .set_minus_sign(sign)
                     .set_nfold_body(nfold)
+                    .set_rotation_axis(RotationAxis::Omitted)
                     .build()
                     .unwrap())
             }
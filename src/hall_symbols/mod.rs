@@ -4,7 +4,6 @@ use std::{
 };
 
 use nalgebra::{Matrix3, Vector3};
-use winnow::ModalResult;
 
 use crate::{
     database::{SpaceGroupHallSymbol, ORDER_12, ORDER_24, ORDER_48},
@@ -13,11 +12,14 @@ use crate::{
 
 use self::{
     lattice_symbol::LatticeSymbol,
-    matrix_symbol::{MatrixSymbol, NFold, NFoldDiag},
+    matrix_symbol::{MatrixSymbol, NFold, NFoldDiag, RotationAxis},
     origin_shift::OriginShift,
-    parser::parse_hall_symbol,
+    parser::{implied_axis, parse_hall_symbol},
 };
 
+pub(crate) mod change_of_basis;
+mod derivation;
+mod error;
 mod general_positions;
 mod lattice_symbol;
 mod matrix_symbol;
@@ -25,10 +27,16 @@ mod origin_shift;
 mod parser;
 mod translation_symbol;
 
-pub use general_positions::GeneralPositions;
-pub use matrix_symbol::SeitzMatrix;
+pub use change_of_basis::{ChangeOfBasis, ChangeOfBasisSymbol};
+pub use error::{GroupGenerationError, HallSymbolError};
+pub use general_positions::{GeneralPositions, Orbit};
+pub(crate) use matrix_symbol::{coordinate_rows, RotationType};
+pub use matrix_symbol::{GeometricDescription, OperationKind, RotationSense, SeitzMatrix};
+pub use origin_shift::CHANGE_OF_BASIS_BASE_NUMBER;
 
 pub(crate) const SEITZ_TRANSLATE_BASE_NUMBER: i32 = 12;
+/// Order of the point group `m-3m`, the largest crystallographic point group
+const MAX_POINT_GROUP_ORDER: usize = 48;
 
 pub trait SymmetryElement {
     fn equiv_num(&self) -> usize;
@@ -39,6 +47,7 @@ pub struct HallSymbolNotation {
     lattice_symbol: LatticeSymbol,
     matrix_symbols: Vec<MatrixSymbol>,
     origin_shift: OriginShift,
+    change_of_basis: Option<ChangeOfBasisSymbol>,
 }
 
 impl HallSymbolNotation {
@@ -51,51 +60,92 @@ impl HallSymbolNotation {
             lattice_symbol,
             matrix_symbols,
             origin_shift,
+            change_of_basis: None,
         }
     }
-    pub fn try_from_str(input: &str) -> ModalResult<Self> {
-        let mut input = input;
-        parse_hall_symbol(&mut input)
+    /// Replace the origin shift by a general change-of-basis operator, e.g. `(-a+b,a,c)`
+    pub fn with_change_of_basis(mut self, change_of_basis: ChangeOfBasisSymbol) -> Self {
+        self.origin_shift = OriginShift::default();
+        self.change_of_basis = Some(change_of_basis);
+        self
+    }
+    pub fn change_of_basis(&self) -> Option<&ChangeOfBasisSymbol> {
+        self.change_of_basis.as_ref()
+    }
+    /// Parse the Hall symbol at the start of the input, ignoring what follows it.
+    /// A lattice symbol alone like `P` is accepted for the group `P 1`.
+    pub fn try_from_str(input: &str) -> Result<Self, HallSymbolError> {
+        let mut remaining = input;
+        parse_hall_symbol(&mut remaining)
+            .map_err(|e| HallSymbolError::from_parser(input, remaining, e))
+    }
+    /// Parse the whole input as a Hall symbol with at least one matrix symbol,
+    /// rejecting any trailing characters except whitespace
+    pub fn try_from_str_strict(input: &str) -> Result<Self, HallSymbolError> {
+        let mut remaining = input;
+        let symbol = parse_hall_symbol(&mut remaining)
+            .map_err(|e| HallSymbolError::from_parser(input, remaining, e))?;
+        let offset = input.len() - remaining.len();
+        if symbol.matrix_symbols.is_empty() {
+            Err(HallSymbolError::missing_matrix_symbol(input, offset))
+        } else if !remaining.trim().is_empty() {
+            let offset = offset + (remaining.len() - remaining.trim_start().len());
+            Err(HallSymbolError::trailing(input, offset))
+        } else {
+            Ok(symbol)
+        }
     }
 
     fn num_generators(&self) -> usize {
         self.lattice_symbol.equiv_num() + self.matrix_symbols.len()
     }
 
-    fn max_equiv_pos(&self) -> usize {
+    fn max_equiv_pos(&self) -> Result<usize, GroupGenerationError> {
         self.matrix_symbols
             .iter()
-            .map(|m| m.seitz_matrix().expect("Invalid Seitz Matrix").equiv_num())
-            .fold(self.lattice_symbol.equiv_num(), |acc, x| acc * x)
+            .try_fold(self.lattice_symbol.equiv_num(), |acc, m| {
+                Ok(acc * self.matrix_symbol_seitz(m)?.equiv_num())
+            })
     }
 
-    fn get_matrice_order(&self) -> Vec<&str> {
-        let first_m = self.matrix_symbols.first().unwrap();
-        match first_m.nfold_body() {
+    fn matrix_symbol_seitz(&self, m: &MatrixSymbol) -> Result<SeitzMatrix, GroupGenerationError> {
+        m.seitz_matrix()
+            .map_err(|_| GroupGenerationError::InvalidMatrixSymbol(format!("{m}")))
+    }
+
+    fn get_matrice_order(&self) -> Result<Vec<&str>, GroupGenerationError> {
+        let first_m = self
+            .matrix_symbols
+            .first()
+            .ok_or(GroupGenerationError::NoMatrixSymbol)?;
+        Ok(match first_m.nfold_body() {
             NFold::N6 => ORDER_24.to_vec(),
             NFold::N3 => match first_m.nfold_diag() {
                 NFoldDiag::Asterisk => ORDER_12.to_vec(),
                 _ => ORDER_24.to_vec(),
             },
             _ => ORDER_48.to_vec(),
-        }
+        })
     }
 
-    fn sort_general_positions(&self, positions: &[SeitzMatrix]) -> Vec<SeitzMatrix> {
-        let mut ret_position: Vec<SeitzMatrix> = positions.to_vec();
-        let order_to_use = self.get_matrice_order();
-        ret_position.sort_by(|a, b| {
-            let a_id = order_to_use
-                .iter()
-                .position(|&s| s == a.jones_faithful_repr_rot())
-                .unwrap_or_else(|| panic!("{} fails to match", a.jones_faithful_repr_rot()));
-            let b_id = order_to_use
-                .iter()
-                .position(|&s| s == b.jones_faithful_repr_rot())
-                .unwrap_or_else(|| panic!("{} fails to match", b.jones_faithful_repr_rot()));
-            a_id.cmp(&b_id)
-        });
-        ret_position.to_vec()
+    fn sort_general_positions(
+        &self,
+        positions: &[SeitzMatrix],
+    ) -> Result<Vec<SeitzMatrix>, GroupGenerationError> {
+        let order_to_use = self.get_matrice_order()?;
+        let mut keyed = positions
+            .iter()
+            .map(|m| {
+                let rotation = m.jones_faithful_repr_rot();
+                order_to_use
+                    .iter()
+                    .position(|&s| s == rotation)
+                    .map(|id| (id, *m))
+                    .ok_or(GroupGenerationError::UnknownRotation(rotation))
+            })
+            .collect::<Result<Vec<(usize, SeitzMatrix)>, GroupGenerationError>>()?;
+        keyed.sort_by_key(|(id, _)| *id);
+        Ok(keyed.into_iter().map(|(_, m)| m).collect())
     }
 
     /// Find the minimal positive x,y,z for the translation vector,
@@ -165,21 +215,26 @@ impl HallSymbolNotation {
         }
     }
 
-    fn generate_positions(&self) -> Vec<SeitzMatrix> {
+    fn generate_positions(&self) -> Result<Vec<SeitzMatrix>, GroupGenerationError> {
         // let num_generators = self.num_generators();
-        let mut list: Vec<SeitzMatrix> = Vec::with_capacity(self.max_equiv_pos());
+        let mut list: Vec<SeitzMatrix> = Vec::with_capacity(self.max_equiv_pos()?);
         let mut matrice_map: HashMap<Matrix3<i32>, HashSet<Vector3<i32>>> = HashMap::new();
+        // The inversion of a centrosymmetric lattice symbol moves with the origin as well
         self.lattice_symbol.seitz_matrices().iter().for_each(|&m| {
-            self.add_to_list(&mut list, &mut matrice_map, m);
-        });
-        self.matrix_symbols.iter().for_each(|ms| {
-            let seitz_mx = ms
-                .seitz_matrix()
-                .unwrap_or_else(|_| panic!("SeitzMatrix generation failed for {}", ms));
-            let shifted = self.origin_shift.shifted_matrix(seitz_mx);
+            let shifted = self.origin_shift.shifted_matrix(m);
             self.add_to_list(&mut list, &mut matrice_map, shifted);
         });
+        for ms in self.matrix_symbols.iter() {
+            let shifted = self
+                .origin_shift
+                .shifted_matrix(self.matrix_symbol_seitz(ms)?);
+            self.add_to_list(&mut list, &mut matrice_map, shifted);
+        }
         loop {
+            // The generators of an infinite group keep producing new rotations
+            if matrice_map.len() > MAX_POINT_GROUP_ORDER {
+                return Err(GroupGenerationError::InfiniteGroup);
+            }
             let mut list_cloned = list.clone();
             for i in list.iter().skip(1) {
                 for j in list.iter().skip(1) {
@@ -197,30 +252,75 @@ impl HallSymbolNotation {
         }
         self.sort_general_positions(&list)
     }
+    /// The general positions generated from the symbol.
+    /// # Panics
+    /// Panics when the symbol does not generate a crystallographic space group,
+    /// see `try_general_positions` for the symbols supplied by users.
     pub fn general_positions(&self) -> GeneralPositions {
-        GeneralPositions::new(
+        self.try_general_positions()
+            .unwrap_or_else(|e| panic!("Generation failed for {}: {}", self, e))
+    }
+    /// The general positions generated from the symbol, or the reason why the symbol
+    /// does not generate a crystallographic space group
+    pub fn try_general_positions(&self) -> Result<GeneralPositions, GroupGenerationError> {
+        let general_positions = GeneralPositions::new(
             self.lattice_symbol.get_translations(),
-            self.generate_positions(),
-        )
+            self.generate_positions()?,
+        );
+        // The change of basis may alter the lattice, so it is applied to the whole group
+        match self.change_of_basis {
+            Some(symbol) => symbol
+                .change_of_basis()
+                .transform_general_positions(&general_positions)
+                .ok_or(GroupGenerationError::InvalidChangeOfBasis(format!(
+                    "{symbol}"
+                ))),
+            None => Ok(general_positions),
+        }
     }
 }
 
 impl From<SpaceGroupHallSymbol> for HallSymbolNotation {
     fn from(value: SpaceGroupHallSymbol) -> Self {
-        Self::try_from_str(&value.get_hall_symbol()).unwrap()
+        Self::try_from_str(&value.get_hall_symbol()).expect("Tabulated Hall symbols are valid")
     }
 }
 
 impl Display for HallSymbolNotation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let lattice_symbol = format!("{}", self.lattice_symbol);
+        // Omit the axis and diagonal symbols which are implied by the default rules.
         let matrice = self
             .matrix_symbols
             .iter()
-            .map(|m| format!("{m}"))
+            .enumerate()
+            .map(|(i, m)| {
+                let preceding_fold = i
+                    .checked_sub(1)
+                    .map(|j| self.matrix_symbols[j].nfold_body());
+                let (axis, diag) = implied_axis(i, m.nfold_body(), preceding_fold);
+                let mut shown = m.clone();
+                if matches!(m.nfold_body(), NFold::N1) || m.rotation_axis() == axis {
+                    shown.set_rotation_axis(RotationAxis::Omitted);
+                    if m.nfold_diag() == diag {
+                        shown.set_nfold_diag(NFoldDiag::None);
+                    }
+                    format!("{shown}")
+                } else if m.rotation_axis() == RotationAxis::Z {
+                    // The z axis prints nothing in a matrix symbol, so write it after the n-fold
+                    let mut shown = format!("{shown}");
+                    let after_fold = shown.find(|c: char| c.is_ascii_digit()).unwrap_or(0) + 1;
+                    shown.insert(after_fold, 'z');
+                    shown
+                } else {
+                    format!("{shown}")
+                }
+            })
             .collect::<Vec<String>>()
             .join(" ");
-        let origin_shift = if self.origin_shift != OriginShift::default() {
+        let origin_shift = if let Some(change_of_basis) = self.change_of_basis {
+            format!(" {}", change_of_basis)
+        } else if self.origin_shift != OriginShift::default() {
             format!(" {}", self.origin_shift)
         } else {
             String::new()
@@ -235,13 +335,14 @@ mod test {
     use std::{collections::HashSet, fs::read_to_string, path::Path};
 
     use indicatif::ProgressIterator;
+    use nalgebra::{Matrix3, Vector3};
 
-    use crate::database::DEFAULT_SPACE_GROUP_SYMBOLS;
+    use crate::database::{SpaceGroupHallSymbol, DEFAULT_SPACE_GROUP_SYMBOLS};
 
     use super::{
         matrix_symbol::{MatrixSymbol, NFold, NFoldSub},
         translation_symbol::TranslationSymbol,
-        HallSymbolNotation,
+        GeneralPositions, HallSymbolNotation, SeitzMatrix,
     };
 
     #[test]
@@ -314,6 +415,112 @@ mod test {
         test("-I 41b")
     }
 
+    #[test]
+    fn test_display_implied_axes() {
+        // Explicit axes equal to the implied ones are omitted
+        let g = HallSymbolNotation::try_from_str("P 4z 2x 3*").unwrap();
+        assert_eq!("P 4 2 3", format!("{g}"));
+        let g = HallSymbolNotation::try_from_str("P 6z 2'").unwrap();
+        assert_eq!("P 6 2", format!("{g}"));
+        let g = HallSymbolNotation::try_from_str("P 6z 2\"").unwrap();
+        assert_eq!("P 6 2\"", format!("{g}"));
+        // Other axes are kept
+        let g = HallSymbolNotation::try_from_str("P 2x 2z").unwrap();
+        assert_eq!("P 2x 2z", format!("{g}"));
+        // The tabulated symbols are written without any implied axis
+        SpaceGroupHallSymbol::all().for_each(|symbol| {
+            let symbol = symbol.get_hall_symbol();
+            let g = HallSymbolNotation::try_from_str(&symbol).unwrap();
+            assert_eq!(symbol, format!("{g}"));
+        });
+    }
+
+    #[test]
+    fn test_origin_shift_lattice_inversion() {
+        // The inversion of the lattice symbol is moved with the origin as the matrix symbols
+        let operators = SeitzMatrix::try_from_xyz_list("x,y,z; -x,-y,-z+1/2").unwrap();
+        let expected = GeneralPositions::from_operators(&operators);
+        for symbol in ["-P 1 (0 0 3)", "P -1 (0 0 3)"] {
+            let g = HallSymbolNotation::try_from_str(symbol).unwrap();
+            assert!(g.general_positions().is_same_group(&expected), "{symbol}");
+        }
+        // and the shift is applied to the centred inversions as well
+        let shifted = HallSymbolNotation::try_from_str("-C 2 (1 2 3)")
+            .unwrap()
+            .general_positions();
+        let inversions: Vec<SeitzMatrix> = shifted
+            .derive_full_sets()
+            .concat()
+            .into_iter()
+            .filter(|m| m.rotation_part() == -Matrix3::identity())
+            .collect();
+        assert_eq!(2, inversions.len());
+        let operators = SeitzMatrix::try_from_xyz_list(
+            "x,y,z; -x+1/6,-y+1/3,z; -x+1/6,-y+1/3,-z+1/2; x,y,-z+1/2;
+             x+1/2,y+1/2,z; -x+2/3,-y+5/6,z; -x+2/3,-y+5/6,-z+1/2; x+1/2,y+1/2,-z+1/2",
+        )
+        .unwrap();
+        assert!(shifted.is_same_group(&GeneralPositions::from_operators(&operators)));
+    }
+
+    #[test]
+    fn test_change_of_basis_symbol() {
+        let g = HallSymbolNotation::try_from_str("P 2 2 (-a+b,a,c)").unwrap();
+        assert_eq!("P 2 2 (-a+b,a,c)", format!("{g}"));
+        let expected = g
+            .change_of_basis()
+            .unwrap()
+            .change_of_basis()
+            .transform_general_positions(
+                &HallSymbolNotation::try_from_str("P 2 2")
+                    .unwrap()
+                    .general_positions(),
+            )
+            .unwrap();
+        assert!(g.general_positions().is_same_group(&expected));
+        let g = HallSymbolNotation::try_from_str("P 3 (x-y,x,z)").unwrap();
+        assert_eq!("P 3 (x-y,x,z)", format!("{g}"));
+        // The coordinate form of an origin shift is the same as the vector form
+        let shifted = HallSymbolNotation::try_from_str("P 4 2 (x,y,z+1/12)").unwrap();
+        assert_eq!("P 4 2 (x,y,z+1/12)", format!("{shifted}"));
+        assert!(shifted.general_positions().is_same_group(
+            &HallSymbolNotation::try_from_str("P 4 2 (0 0 1)")
+                .unwrap()
+                .general_positions()
+        ));
+        // A doubled cell is centred
+        let doubled = HallSymbolNotation::try_from_str("-P 1 (2a,b,c)")
+            .unwrap()
+            .general_positions();
+        assert_eq!(2, doubled.lattice_translations().len());
+        assert_eq!(2, doubled.num_of_general_pos());
+        assert!(HallSymbolNotation::try_from_str("P 2 2 (a,a,c)").is_err());
+    }
+
+    #[test]
+    fn test_orbit() {
+        let positions = HallSymbolNotation::try_from_str("-F 4vw 2vw 3")
+            .unwrap()
+            .general_positions();
+        // 8c of F d -3 m (origin choice 2)
+        let orbit = positions.orbit(Vector3::new(0.0, 0.0, 0.5), 1e-4);
+        assert_eq!(16, orbit.multiplicity());
+        assert_eq!(192 / 16, orbit.stabilizer().len());
+        orbit.positions().iter().for_each(|p| {
+            assert!(p.iter().all(|&v| (0.0..1.0).contains(&v)));
+        });
+        orbit.stabilizer().iter().for_each(|m| {
+            let image = m.transform_point(&Vector3::new(0.0, 0.0, 0.5));
+            assert!((image - Vector3::new(0.0, 0.0, 0.5)).norm() < 1e-9);
+        });
+        // Slightly off the position, merged within the tolerance
+        let orbit = positions.orbit(Vector3::new(0.0, 1e-6, 0.5 - 1e-6), 1e-4);
+        assert_eq!(16, orbit.multiplicity());
+        let orbit = positions.orbit(Vector3::new(0.1, 0.2, 0.3), 1e-4);
+        assert_eq!(192, orbit.multiplicity());
+        assert_eq!(1, orbit.stabilizer().len());
+    }
+
     #[test]
     fn test_all() {
         let default_list = DEFAULT_SPACE_GROUP_SYMBOLS.get(2).unwrap();
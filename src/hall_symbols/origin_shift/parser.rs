@@ -1,38 +1,36 @@
 use winnow::{
     ascii::{dec_int, space0, space1},
-    combinator::{delimited, preceded, separated},
-    error::{AddContext, ContextError, StrContext, StrContextValue},
-    stream::Stream,
+    combinator::{cut_err, delimited, preceded, separated},
+    error::{ContextError, StrContext, StrContextValue},
     ModalResult, Parser,
 };
 
 use super::OriginShift;
 
+/// Parse the origin shift vector like ` (0 0 -1)`. The shift is zero when the symbol
+/// has no parentheses, but an opened parenthesis must contain three integers.
 pub fn parse_origin_shift(input: &mut &str) -> ModalResult<OriginShift> {
-    let parse_shift: ModalResult<Vec<i32>> = preceded(
+    if preceded(space0::<_, ContextError>, '(')
+        .parse_peek(*input)
+        .is_err()
+    {
+        return Ok(OriginShift::default());
+    }
+    let numbers: Vec<i32> = preceded(
         space0,
-        delimited("(", separated(1..=3, dec_int::<_, i32, _>, space1), ")"),
+        delimited(
+            '(',
+            cut_err(separated(3, preceded(space0, dec_int::<_, i32, _>), space1)),
+            cut_err((space0, ')')),
+        ),
     )
     .context(StrContext::Label("Origin shift vector"))
-    .parse_next(input);
-    match parse_shift {
-        Ok(numbers) => {
-            if numbers.len() != 3 {
-                let err_context = ContextError::<StrContext>::new().add_context(
-                    input,
-                    &input.checkpoint(),
-                    StrContext::Expected(StrContextValue::Description(
-                        "Incorrect input: not `i32`",
-                    )),
-                );
-                Err(winnow::error::ErrMode::Backtrack(err_context))
-            } else {
-                let [va, vb, vc] = numbers.try_into().unwrap();
-                Ok(OriginShift::new(va, vb, vc))
-            }
-        }
-        Err(_e) => Ok(OriginShift::default()),
-    }
+    .context(StrContext::Expected(StrContextValue::Description(
+        "three integers like `(0 0 -1)` or a change-of-basis operator like `(-a+b,a,c)`",
+    )))
+    .parse_next(input)?;
+    let [va, vb, vc] = numbers.try_into().unwrap();
+    Ok(OriginShift::new(va, vb, vc))
 }
 
 #[cfg(test)]
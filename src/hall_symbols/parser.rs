@@ -1,6 +1,7 @@
-use winnow::ModalResult;
+use winnow::{combinator::opt, ModalResult, Parser};
 
 use crate::hall_symbols::{
+    change_of_basis::ChangeOfBasisSymbol,
     matrix_symbol::{MatrixSymbol, NFold, NFoldDiag},
     origin_shift::OriginShift,
 };
@@ -13,13 +14,17 @@ pub fn parse_hall_symbol(input: &mut &str) -> ModalResult<HallSymbolNotation> {
     while let Ok(symbol) = MatrixSymbol::try_from_str(input) {
         matrix_symbols.push(symbol);
     }
-    let origin_shift = OriginShift::try_from_str(input)?;
+    let change_of_basis = opt(ChangeOfBasisSymbol::try_from_str).parse_next(input)?;
+    let origin_shift = match change_of_basis {
+        Some(_) => OriginShift::default(),
+        None => OriginShift::try_from_str(input)?,
+    };
     restore_information_in_matrix_symbols(&mut matrix_symbols);
-    Ok(HallSymbolNotation::new(
-        lattice_symbol,
-        matrix_symbols,
-        origin_shift,
-    ))
+    let notation = HallSymbolNotation::new(lattice_symbol, matrix_symbols, origin_shift);
+    Ok(match change_of_basis {
+        Some(change_of_basis) => notation.with_change_of_basis(change_of_basis),
+        None => notation,
+    })
 }
 
 fn restore_information_in_matrix_symbols(symbols_vec: &mut [MatrixSymbol]) {
@@ -35,19 +40,12 @@ fn restore_information_in_matrix_symbols(symbols_vec: &mut [MatrixSymbol]) {
         .map(|symbol| symbol.nfold_body())
         .collect();
     symbols_vec.iter_mut().enumerate().for_each(|(i, symbol)| {
-        if i == 0 && matches!(symbol.rotation_axis(), RotationAxis::Omitted) {
-            symbol.set_rotation_axis(RotationAxis::Z)
-        }
-        if i == 1 && matches!(symbol.nfold_body(), NFold::N2) {
-            match rotation_folds[i - 1] {
-                NFold::N2 | NFold::N4 => symbol.set_rotation_axis(RotationAxis::X),
-                NFold::N3 | NFold::N6 => {
-                    if matches!(symbol.nfold_diag(), NFoldDiag::None) {
-                        symbol.set_nfold_diag(NFoldDiag::SingleQuote)
-                    }
-                    symbol.set_rotation_axis(RotationAxis::Z);
-                }
-                _ => (),
+        if matches!(symbol.rotation_axis(), RotationAxis::Omitted) {
+            let preceding_fold = i.checked_sub(1).map(|j| rotation_folds[j]);
+            let (axis, diag) = implied_axis(i, symbol.nfold_body(), preceding_fold);
+            symbol.set_rotation_axis(axis);
+            if matches!(symbol.nfold_diag(), NFoldDiag::None) {
+                symbol.set_nfold_diag(diag);
             }
         }
         if i == 2 && matches!(symbol.nfold_body(), NFold::N3) {
@@ -55,3 +53,18 @@ fn restore_information_in_matrix_symbols(symbols_vec: &mut [MatrixSymbol]) {
         }
     });
 }
+
+/// The implied rotation axis and diagonal symbol of the `i`-th matrix symbol
+/// when the axis symbol is omitted.
+pub(crate) fn implied_axis(
+    i: usize,
+    nfold: NFold,
+    preceding_fold: Option<NFold>,
+) -> (RotationAxis, NFoldDiag) {
+    match (i, nfold, preceding_fold) {
+        (1, NFold::N2, Some(NFold::N2 | NFold::N4)) => (RotationAxis::X, NFoldDiag::None),
+        (1, NFold::N2, Some(NFold::N3 | NFold::N6)) => (RotationAxis::Z, NFoldDiag::SingleQuote),
+        (2, NFold::N3, _) => (RotationAxis::Z, NFoldDiag::Asterisk),
+        _ => (RotationAxis::Z, NFoldDiag::None),
+    }
+}
@@ -1,6 +1,12 @@
 #![allow(dead_code)]
 pub mod database;
+pub mod group;
 pub mod hall_symbols;
+pub mod reciprocal;
 pub mod utils;
+pub mod wyckoff;
 
-pub use hall_symbols::{GeneralPositions, HallSymbolNotation, SeitzMatrix};
+pub use hall_symbols::{
+    ChangeOfBasis, ChangeOfBasisSymbol, GeneralPositions, GroupGenerationError, HallSymbolError,
+    HallSymbolNotation, SeitzMatrix,
+};
@@ -0,0 +1,291 @@
+use nalgebra::{Matrix3, Vector3};
+
+use crate::{
+    database::{PointGroup, SpaceGroupHallSymbol},
+    HallSymbolNotation, SeitzMatrix,
+};
+
+/// The reference asymmetric units of the reciprocal space of cctbx, in the default
+/// settings of the Laue classes with the hexagonal axes for the trigonal ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReferenceAsu {
+    /// -1
+    Triclinic,
+    /// 2/m with the unique axis b
+    Monoclinic,
+    /// mmm
+    Orthorhombic,
+    /// 4/m
+    TetragonalLow,
+    /// 4/mmm
+    TetragonalHigh,
+    /// -3
+    TrigonalLow,
+    /// -3m1
+    Trigonal3m1,
+    /// -31m
+    Trigonal31m,
+    /// 6/m
+    HexagonalLow,
+    /// 6/mmm
+    HexagonalHigh,
+    /// m-3
+    CubicLow,
+    /// m-3m
+    CubicHigh,
+}
+
+impl ReferenceAsu {
+    fn contains(&self, [h, k, l]: [i32; 3]) -> bool {
+        match self {
+            ReferenceAsu::Triclinic => l > 0 || (l == 0 && (h > 0 || (h == 0 && k >= 0))),
+            ReferenceAsu::Monoclinic => k >= 0 && (l > 0 || (l == 0 && h >= 0)),
+            ReferenceAsu::Orthorhombic => h >= 0 && k >= 0 && l >= 0,
+            ReferenceAsu::TetragonalLow | ReferenceAsu::HexagonalLow => {
+                l >= 0 && ((h >= 0 && k > 0) || (h == 0 && k == 0))
+            }
+            ReferenceAsu::TetragonalHigh | ReferenceAsu::HexagonalHigh => {
+                h >= k && k >= 0 && l >= 0
+            }
+            ReferenceAsu::TrigonalLow => (h >= 0 && k > 0) || (h == 0 && k == 0 && l >= 0),
+            ReferenceAsu::Trigonal3m1 => h >= k && k >= 0 && (h > k || l >= 0),
+            ReferenceAsu::Trigonal31m => h >= k && k >= 0 && (k > 0 || l >= 0),
+            ReferenceAsu::CubicLow => h >= 0 && ((l >= h && k > h) || (l == h && k == h)),
+            ReferenceAsu::CubicHigh => k >= l && l >= h && h >= 0,
+        }
+    }
+
+    fn condition(&self) -> &'static str {
+        match self {
+            ReferenceAsu::Triclinic => "l>0 or (l=0 and (h>0 or (h=0 and k>=0)))",
+            ReferenceAsu::Monoclinic => "k>=0 and (l>0 or (l=0 and h>=0))",
+            ReferenceAsu::Orthorhombic => "h>=0 and k>=0 and l>=0",
+            ReferenceAsu::TetragonalLow | ReferenceAsu::HexagonalLow => {
+                "l>=0 and ((h>=0 and k>0) or (h=0 and k=0))"
+            }
+            ReferenceAsu::TetragonalHigh | ReferenceAsu::HexagonalHigh => "h>=k and k>=0 and l>=0",
+            ReferenceAsu::TrigonalLow => "(h>=0 and k>0) or (h=0 and k=0 and l>=0)",
+            ReferenceAsu::Trigonal3m1 => "h>=k and k>=0 and (h>k or l>=0)",
+            ReferenceAsu::Trigonal31m => "h>=k and k>=0 and (k>0 or l>=0)",
+            ReferenceAsu::CubicLow => "h>=0 and ((l>=h and k>h) or (l=h and k=h))",
+            ReferenceAsu::CubicHigh => "k>=l and l>=h and h>=0",
+        }
+    }
+}
+
+/// The asymmetric unit of the reciprocal space of a space group setting, the one of its
+/// Laue class in the default setting as used by cctbx and CCP4. It contains one
+/// reflection of each set of reflections equivalent under the Laue class, i.e. with the
+/// Friedel mates merged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReciprocalAsu {
+    laue_class: PointGroup,
+    reference: ReferenceAsu,
+    /// `P` of the change of basis into the default setting, the indices transforming as
+    /// `h' = hP`
+    to_reference: Matrix3<i32>,
+    operations: Vec<SeitzMatrix>,
+}
+
+/// Reflection of the asymmetric unit equivalent to a given one `h`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AsuReflection {
+    hkl: Vector3<i32>,
+    operation: SeitzMatrix,
+    friedel_mate: bool,
+}
+
+impl AsuReflection {
+    /// `hW`, or `-hW` for a Friedel mate
+    pub fn hkl(&self) -> Vector3<i32> {
+        self.hkl
+    }
+
+    /// The operation `(W, w)` of the space group mapping the reflection, with the
+    /// structure factor `F(hW) = exp(-2πi h·w) F(h)`
+    pub fn operation(&self) -> SeitzMatrix {
+        self.operation
+    }
+
+    /// Whether the reflection is the Friedel mate `-hW`, whose anomalous signal is the
+    /// opposite one
+    pub fn is_friedel_mate(&self) -> bool {
+        self.friedel_mate
+    }
+}
+
+impl ReciprocalAsu {
+    pub fn laue_class(&self) -> PointGroup {
+        self.laue_class
+    }
+
+    /// The condition like `h>=k and k>=0 and l>=0`, on the indices in the default setting
+    pub fn condition(&self) -> &'static str {
+        self.reference.condition()
+    }
+
+    pub fn in_reciprocal_asu(&self, hkl: &Vector3<i32>) -> bool {
+        let reference = self.to_reference.transpose() * hkl;
+        self.reference.contains(reference.into())
+    }
+
+    /// The reflection of the asymmetric unit equivalent to `hkl` under the Laue class,
+    /// with the operation used
+    pub fn map_to_asu(&self, hkl: &Vector3<i32>) -> AsuReflection {
+        for friedel_mate in [false, true] {
+            for m in self.operations.iter() {
+                let mut equivalent = m.rotation_part().transpose() * hkl;
+                if friedel_mate {
+                    equivalent = -equivalent;
+                }
+                if self.in_reciprocal_asu(&equivalent) {
+                    return AsuReflection {
+                        hkl: equivalent,
+                        operation: *m,
+                        friedel_mate,
+                    };
+                }
+            }
+        }
+        unreachable!("The asymmetric unit contains a reflection of each orbit")
+    }
+}
+
+impl SpaceGroupHallSymbol {
+    /// The asymmetric unit of the reciprocal space in the setting, from the one of the
+    /// default setting
+    pub fn reciprocal_asu(&self) -> ReciprocalAsu {
+        let default = self.entry().default_setting();
+        let to_reference = self
+            .basis_change_to(&default)
+            .expect("The settings of a space group are related")
+            .matrix_rational()
+            .map(|v| {
+                assert!(
+                    v.is_integer(),
+                    "The default settings have primitive or larger cells"
+                );
+                v.to_integer() as i32
+            });
+        let laue_class = self.laue_class();
+        ReciprocalAsu {
+            laue_class,
+            reference: reference_asu(laue_class, &default),
+            to_reference,
+            operations: HallSymbolNotation::from(*self)
+                .general_positions()
+                .core_position_set()
+                .to_vec(),
+        }
+    }
+}
+
+fn reference_asu(laue_class: PointGroup, default: &SpaceGroupHallSymbol) -> ReferenceAsu {
+    match laue_class {
+        PointGroup::Ci => ReferenceAsu::Triclinic,
+        PointGroup::C2h => ReferenceAsu::Monoclinic,
+        PointGroup::D2h => ReferenceAsu::Orthorhombic,
+        PointGroup::C4h => ReferenceAsu::TetragonalLow,
+        PointGroup::D4h => ReferenceAsu::TetragonalHigh,
+        PointGroup::C3i => ReferenceAsu::TrigonalLow,
+        PointGroup::D3d => {
+            // -3m1 has the 2-fold axis along a, x-y,-y,-z, or the mirror normal to it
+            let two_fold = Matrix3::new(1, -1, 0, 0, -1, 0, 0, 0, -1);
+            if HallSymbolNotation::from(*default)
+                .general_positions()
+                .core_position_set()
+                .iter()
+                .any(|m| m.rotation_part() == two_fold || m.rotation_part() == -two_fold)
+            {
+                ReferenceAsu::Trigonal3m1
+            } else {
+                ReferenceAsu::Trigonal31m
+            }
+        }
+        PointGroup::C6h => ReferenceAsu::HexagonalLow,
+        PointGroup::D6h => ReferenceAsu::HexagonalHigh,
+        PointGroup::Th => ReferenceAsu::CubicLow,
+        PointGroup::Oh => ReferenceAsu::CubicHigh,
+        _ => unreachable!("Laue classes are centrosymmetric"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use nalgebra::{Matrix3, Vector3};
+
+    use crate::database::{OrthorhombicAxes, Setting, SpaceGroupEntry, UniqueAxis};
+
+    use super::ReciprocalAsu;
+
+    fn reciprocal_asu(hall_symbol: &str) -> ReciprocalAsu {
+        SpaceGroupEntry::all()
+            .find(|entry| entry.hall_symbol() == hall_symbol)
+            .unwrap()
+            .symbol()
+            .reciprocal_asu()
+    }
+
+    #[test]
+    fn map_to_asu() {
+        let asu = reciprocal_asu("-P 4 2 3");
+        assert_eq!("k>=l and l>=h and h>=0", asu.condition());
+        let mapped = asu.map_to_asu(&Vector3::new(-3, 1, 2));
+        assert_eq!(Vector3::new(1, 3, 2), mapped.hkl());
+        assert!(!mapped.is_friedel_mate());
+        // P2₁: the Friedel mate of -h,-k,-l through -x,y+1/2,-z
+        let asu = reciprocal_asu("P 2yb");
+        let mapped = asu.map_to_asu(&Vector3::new(1, -2, 3));
+        assert_eq!(Vector3::new(1, 2, 3), mapped.hkl());
+        assert!(mapped.is_friedel_mate());
+        assert_eq!(
+            Matrix3::new(-1, 0, 0, 0, 1, 0, 0, 0, -1),
+            mapped.operation().rotation_part()
+        );
+        assert!(asu.in_reciprocal_asu(&Vector3::new(1, 2, 0)));
+        assert!(!asu.in_reciprocal_asu(&Vector3::new(-1, 2, 0)));
+    }
+
+    #[test]
+    fn fundamental_domains() {
+        // One reflection of each orbit of the Laue class is in the asymmetric unit, in the
+        // default settings and in the ones with other axes: c unique, cab, rhombohedral
+        let range = -2..=2;
+        let entries = SpaceGroupEntry::all().filter(|entry| {
+            entry.is_default_setting()
+                || matches!(
+                    entry.setting(),
+                    Setting::Monoclinic(UniqueAxis::C)
+                        | Setting::Orthorhombic(OrthorhombicAxes::Cab)
+                        | Setting::RhombohedralAxes
+                )
+        });
+        for entry in entries {
+            let asu = entry.symbol().reciprocal_asu();
+            for h in range.clone() {
+                for k in range.clone() {
+                    for l in range.clone() {
+                        let hkl = Vector3::new(h, k, l);
+                        let mapped = asu.map_to_asu(&hkl);
+                        assert!(asu.in_reciprocal_asu(&mapped.hkl()));
+                        let orbit: Vec<_> = asu
+                            .operations
+                            .iter()
+                            .flat_map(|m| {
+                                let e = m.rotation_part().transpose() * hkl;
+                                [e, -e]
+                            })
+                            .collect();
+                        let inside = orbit
+                            .iter()
+                            .enumerate()
+                            .filter(|(i, e)| !orbit[..*i].contains(e))
+                            .filter(|(_, e)| asu.in_reciprocal_asu(e))
+                            .count();
+                        assert_eq!(1, inside, "{} {hkl:?}", entry.hall_symbol());
+                    }
+                }
+            }
+        }
+    }
+}
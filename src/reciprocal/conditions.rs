@@ -0,0 +1,270 @@
+use std::fmt::Display;
+
+use fraction::{Integer, Zero};
+use nalgebra::{Matrix3, Vector3};
+
+use crate::{
+    database::SpaceGroupHallSymbol,
+    hall_symbols::{
+        change_of_basis::{affine_expression, linear_expression},
+        SEITZ_TRANSLATE_BASE_NUMBER,
+    },
+    utils::Rational,
+    wyckoff::affine::AffineSubspace,
+    GeneralPositions, GroupGenerationError, HallSymbolNotation, SeitzMatrix,
+};
+
+/// Names of the Miller indices
+const INDEX_NAMES: [char; 3] = ['h', 'k', 'l'];
+
+/// A general reflection condition, e.g. `h0l: l=2n`, imposed by one operation on the
+/// reflections it keeps: the integral conditions on `hkl` come from the centring
+/// translations, the zonal ones from the glide planes and the serial ones from the
+/// screw axes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReflectionCondition {
+    /// The reflections `h` with `hW = h`, e.g. `h0l`
+    zone: String,
+    /// e.g. `h+l=2n`
+    condition: String,
+    rotation: Matrix3<i32>,
+    /// Translation in the unit of `1/SEITZ_TRANSLATE_BASE_NUMBER`
+    translation: Vector3<i32>,
+}
+
+impl ReflectionCondition {
+    /// The reflections the condition applies to, like `hkl`, `h0l`, `hhl` or `00l`
+    pub fn zone(&self) -> &str {
+        &self.zone
+    }
+
+    /// The condition for the reflections to be present, like `l=2n` or `-h+k+l=3n`
+    pub fn condition(&self) -> &str {
+        &self.condition
+    }
+
+    /// Whether the reflection is outside the zone or fulfils the condition
+    pub fn allows(&self, hkl: &Vector3<i32>) -> bool {
+        hkl.transpose() * self.rotation != hkl.transpose()
+            || (hkl.dot(&self.translation)) % SEITZ_TRANSLATE_BASE_NUMBER == 0
+    }
+}
+
+impl Display for ReflectionCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.zone, self.condition)
+    }
+}
+
+impl GeneralPositions {
+    /// The general reflection conditions of all the operations, the integral conditions
+    /// first, then the zonal and the serial ones. The conditions implied by the others,
+    /// e.g. `0k0: k=2n` by `hkl: h+k=2n`, are listed as well when an operation imposes
+    /// them.
+    pub fn reflection_conditions(&self) -> Vec<ReflectionCondition> {
+        let mut conditions: Vec<(usize, ReflectionCondition)> = Vec::new();
+        for m in self.derive_full_sets().concat() {
+            let Some((dim, condition)) = reflection_condition(&m) else {
+                continue;
+            };
+            if !conditions
+                .iter()
+                .any(|(_, c)| c.zone == condition.zone && c.condition == condition.condition)
+            {
+                conditions.push((dim, condition));
+            }
+        }
+        // Keep the strongest condition on each zone, e.g. `00l: l=4n` over `00l: l=2n`
+        let implied: Vec<bool> = conditions
+            .iter()
+            .enumerate()
+            .map(|(i, (_, c))| {
+                conditions.iter().enumerate().any(|(j, (_, d))| {
+                    i != j && c.zone == d.zone && implies(d, c) && !(j > i && implies(c, d))
+                })
+            })
+            .collect();
+        let mut conditions: Vec<_> = conditions
+            .into_iter()
+            .zip(implied)
+            .filter_map(|(c, implied)| (!implied).then_some(c))
+            .collect();
+        conditions.sort_by(|(d1, c1), (d2, c2)| {
+            d2.cmp(d1)
+                .then(c1.zone.cmp(&c2.zone))
+                .then(c1.condition.cmp(&c2.condition))
+        });
+        conditions.into_iter().map(|(_, c)| c).collect()
+    }
+
+    /// Whether an operation `(W, w)` keeps the reflection, `hW = h`, with the phase
+    /// `h·w` not an integer
+    pub fn is_systematically_absent(&self, hkl: &Vector3<i32>) -> bool {
+        self.core_position_set()
+            .iter()
+            .filter(|m| hkl.transpose() * m.rotation_part() == hkl.transpose())
+            .any(|m| {
+                self.lattice_translations().iter().any(|t| {
+                    hkl.dot(&(m.translation_part() + t)) % SEITZ_TRANSLATE_BASE_NUMBER != 0
+                })
+            })
+    }
+}
+
+impl HallSymbolNotation {
+    /// The general reflection conditions of the group, see
+    /// `GeneralPositions::reflection_conditions`. Fails when the symbol does not generate
+    /// a group.
+    pub fn reflection_conditions(&self) -> Result<Vec<ReflectionCondition>, GroupGenerationError> {
+        Ok(self.try_general_positions()?.reflection_conditions())
+    }
+
+    /// See `GeneralPositions::is_systematically_absent`
+    pub fn is_systematically_absent(
+        &self,
+        hkl: &Vector3<i32>,
+    ) -> Result<bool, GroupGenerationError> {
+        Ok(self.try_general_positions()?.is_systematically_absent(hkl))
+    }
+}
+
+impl SpaceGroupHallSymbol {
+    /// The general reflection conditions of the setting, see
+    /// `GeneralPositions::reflection_conditions`
+    pub fn reflection_conditions(&self) -> Vec<ReflectionCondition> {
+        HallSymbolNotation::from(*self)
+            .general_positions()
+            .reflection_conditions()
+    }
+}
+
+/// Whether the reflections of the zone allowed by `d` are allowed by `c`, checked over a
+/// period of the conditions
+fn implies(d: &ReflectionCondition, c: &ReflectionCondition) -> bool {
+    let range = -SEITZ_TRANSLATE_BASE_NUMBER..=SEITZ_TRANSLATE_BASE_NUMBER;
+    range.clone().all(|h| {
+        range.clone().all(|k| {
+            range.clone().all(|l| {
+                let hkl = Vector3::new(h, k, l);
+                !d.allows(&hkl) || c.allows(&hkl)
+            })
+        })
+    })
+}
+
+/// The condition `h·w ∈ Z` on the zone `hW = h` with its dimension, or `None` when the
+/// zone has only the origin or every reflection of it fulfils the condition
+fn reflection_condition(seitz_matrix: &SeitzMatrix) -> Option<(usize, ReflectionCondition)> {
+    let rotation = seitz_matrix.rotation_part();
+    let zone = AffineSubspace::fixed_points(&rotation.transpose(), &[Rational::zero(); 3])?;
+    if zone.dim() == 0 {
+        return None;
+    }
+    let (_, coefficients) = zone.parametric_form();
+    let w = seitz_matrix
+        .translation_part()
+        .map(|v| Rational::new(v as i64, SEITZ_TRANSLATE_BASE_NUMBER as i64));
+    // h·w with h = Σ_j f_j c_j over the free indices f_j
+    let phase: [Rational; 3] = std::array::from_fn(|j| {
+        (0..3).fold(Rational::zero(), |acc, i| acc + coefficients[i][j] * w[i])
+    });
+    let n = phase.iter().fold(1, |lcm, v| lcm.lcm(v.denom()));
+    if n == 1 {
+        return None;
+    }
+    // The coefficients modulo n within (-n/2, n/2], multiplied by the unit modulo n
+    // giving the fewest negative ones, e.g. -h+k+l=3n rather than h-k-l=3n or 2h+k+l=3n
+    let integers = phase.map(|v| (v * Rational::from_integer(n)).to_integer());
+    let reduced = (1..n)
+        .filter(|u| u.gcd(&n) == 1)
+        .map(|u| {
+            integers.map(|v| {
+                let c = (u * v).rem_euclid(n);
+                if 2 * c > n {
+                    c - n
+                } else {
+                    c
+                }
+            })
+        })
+        .min_by_key(|a| {
+            (
+                a.iter().map(|v| v.abs()).sum::<i64>(),
+                a.iter().filter(|v| **v < 0).count(),
+            )
+        })?
+        .map(Rational::from_integer);
+    let zone_symbol: String = coefficients
+        .iter()
+        .map(|row| affine_expression(row, Rational::zero(), INDEX_NAMES))
+        .collect();
+    Some((
+        zone.dim(),
+        ReflectionCondition {
+            zone: zone_symbol,
+            condition: format!("{}={n}n", linear_expression(&reduced, INDEX_NAMES)),
+            rotation,
+            translation: seitz_matrix.translation_part(),
+        },
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use nalgebra::Vector3;
+
+    use crate::HallSymbolNotation;
+
+    fn conditions(symbol: &str) -> Vec<String> {
+        HallSymbolNotation::try_from_str(symbol)
+            .unwrap()
+            .general_positions()
+            .reflection_conditions()
+            .iter()
+            .map(|c| c.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn reflection_conditions() {
+        assert_eq!(vec!["h0l: l=2n", "0k0: k=2n"], conditions("-P 2ybc"));
+        assert_eq!(
+            vec!["00l: l=2n", "0k0: k=2n", "h00: h=2n"],
+            conditions("P 2ac 2ab")
+        );
+        assert_eq!(vec!["hkl: h+k+l=2n", "00l: l=2n"], conditions("I 4"));
+        assert_eq!(vec!["hkl: -h+k+l=3n", "00l: l=3n"], conditions("R 3"));
+        assert_eq!(vec!["00l: l=4n"], conditions("P 4w"));
+        assert!(conditions("-P 4 2 3").is_empty());
+        assert!(conditions("C 2y").contains(&"hkl: h+k=2n".to_string()));
+    }
+
+    #[test]
+    fn systematic_absences() {
+        // Fd-3m
+        let positions = HallSymbolNotation::try_from_str("-F 4vw 2vw 3")
+            .unwrap()
+            .general_positions();
+        let absent = |h, k, l| positions.is_systematically_absent(&Vector3::new(h, k, l));
+        assert!(absent(1, 0, 0));
+        assert!(absent(2, 0, 0));
+        assert!(!absent(4, 0, 0));
+        assert!(!absent(2, 2, 0));
+        assert!(!absent(1, 1, 1));
+        assert!(!absent(0, 0, 0));
+        // The absences are those violating a condition
+        let conditions = positions.reflection_conditions();
+        for h in -4..=4 {
+            for k in -4..=4 {
+                for l in -4..=4 {
+                    let hkl = Vector3::new(h, k, l);
+                    assert_eq!(
+                        absent(h, k, l),
+                        conditions.iter().any(|c| !c.allows(&hkl)),
+                        "{hkl:?}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,13 @@
+//! Reciprocal space: the reflection conditions, the equivalent reflections and the
+//! asymmetric units of the space groups.
+//!
+//! A reflection `h` is systematically absent when an operation `(W, w)` of the group
+//! keeps it, `hW = h`, while the phase `h·w` is not an integer.
+
+mod asu;
+mod conditions;
+mod reflections;
+
+pub use asu::{AsuReflection, ReciprocalAsu};
+pub use conditions::ReflectionCondition;
+pub use reflections::{EquivalentReflection, EquivalentReflections};
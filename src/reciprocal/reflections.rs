@@ -0,0 +1,194 @@
+use std::f64::consts::PI;
+
+use nalgebra::{Complex, Vector3};
+
+use crate::{
+    database::SpaceGroupHallSymbol, hall_symbols::SEITZ_TRANSLATE_BASE_NUMBER, GeneralPositions,
+    GroupGenerationError, HallSymbolNotation,
+};
+
+/// A reflection `hW` equivalent to `h` through the operation `(W, w)`, with the structure
+/// factor `F(hW) = exp(-2πi h·w) F(h)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EquivalentReflection {
+    hkl: Vector3<i32>,
+    /// `h·w` modulo 1 in the unit of `1/SEITZ_TRANSLATE_BASE_NUMBER`
+    phase_shift: i32,
+}
+
+impl EquivalentReflection {
+    pub fn hkl(&self) -> Vector3<i32> {
+        self.hkl
+    }
+
+    /// `2π h·w` in radians within `[0, 2π)`
+    pub fn phase_shift(&self) -> f64 {
+        2.0 * PI * self.phase_shift as f64 / SEITZ_TRANSLATE_BASE_NUMBER as f64
+    }
+
+    /// `exp(2πi h·w)`
+    pub fn phase_factor(&self) -> Complex<f64> {
+        Complex::from_polar(1.0, self.phase_shift())
+    }
+}
+
+/// The reflections equivalent to `h` under the rotation parts of a space group, whose
+/// intensities are equal and phases related by the translation parts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EquivalentReflections {
+    equivalents: Vec<EquivalentReflection>,
+    /// `h·w` in the unit of `1/SEITZ_TRANSLATE_BASE_NUMBER` of an operation with `hW = -h`
+    centric_shift: Option<i32>,
+}
+
+impl EquivalentReflections {
+    /// The distinct reflections `hW`, starting with `h` itself. The phase shifts are only
+    /// meaningful when the reflection is not systematically absent.
+    pub fn equivalents(&self) -> &[EquivalentReflection] {
+        &self.equivalents
+    }
+
+    /// The number of reflections equivalent under the Laue class, i.e. with the Friedel
+    /// mates `-hW` counted as well
+    pub fn multiplicity(&self) -> usize {
+        if self.centric_shift.is_some() {
+            self.equivalents.len()
+        } else {
+            2 * self.equivalents.len()
+        }
+    }
+
+    /// Whether `-h` is equivalent to `h`, so that the phase is restricted
+    pub fn is_centric(&self) -> bool {
+        self.centric_shift.is_some()
+    }
+
+    /// The two phases in radians within `[0, 2π)` allowed to a centric reflection:
+    /// `F(-h) = exp(-2πi h·w) F(h)` and Friedel's law give `φ = π h·w` modulo `π`
+    pub fn restricted_phases(&self) -> Option<[f64; 2]> {
+        self.centric_shift.map(|shift| {
+            let phase = PI * shift as f64 / SEITZ_TRANSLATE_BASE_NUMBER as f64;
+            [phase, phase + PI]
+        })
+    }
+}
+
+impl GeneralPositions {
+    /// The reflections equivalent to `hkl`, through the transposed rotation parts of the
+    /// coset representatives
+    pub fn equivalent_reflections(&self, hkl: &Vector3<i32>) -> EquivalentReflections {
+        let mut equivalents: Vec<EquivalentReflection> = Vec::new();
+        let mut centric_shift = None;
+        for m in self.core_position_set() {
+            let equivalent = m.rotation_part().transpose() * hkl;
+            let phase_shift = hkl
+                .dot(&m.translation_part())
+                .rem_euclid(SEITZ_TRANSLATE_BASE_NUMBER);
+            if centric_shift.is_none() && equivalent == -hkl {
+                centric_shift = Some(phase_shift);
+            }
+            if !equivalents.iter().any(|e| e.hkl == equivalent) {
+                equivalents.push(EquivalentReflection {
+                    hkl: equivalent,
+                    phase_shift,
+                });
+            }
+        }
+        EquivalentReflections {
+            equivalents,
+            centric_shift,
+        }
+    }
+}
+
+impl HallSymbolNotation {
+    /// The reflections equivalent to `hkl` in the group, see
+    /// `GeneralPositions::equivalent_reflections`. Fails when the symbol does not generate
+    /// a group.
+    pub fn equivalent_reflections(
+        &self,
+        hkl: &Vector3<i32>,
+    ) -> Result<EquivalentReflections, GroupGenerationError> {
+        Ok(self.try_general_positions()?.equivalent_reflections(hkl))
+    }
+}
+
+impl SpaceGroupHallSymbol {
+    /// The reflections equivalent to `hkl` in the setting, see
+    /// `GeneralPositions::equivalent_reflections`
+    pub fn equivalent_reflections(&self, hkl: &Vector3<i32>) -> EquivalentReflections {
+        HallSymbolNotation::from(*self)
+            .general_positions()
+            .equivalent_reflections(hkl)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64::consts::PI;
+
+    use nalgebra::Vector3;
+
+    use crate::HallSymbolNotation;
+
+    #[test]
+    fn equivalent_reflections() {
+        // P2₁/c
+        let positions = HallSymbolNotation::try_from_str("-P 2ybc")
+            .unwrap()
+            .general_positions();
+        let reflections = positions.equivalent_reflections(&Vector3::new(1, 2, 3));
+        let hkl: Vec<_> = reflections.equivalents().iter().map(|e| e.hkl()).collect();
+        assert_eq!(
+            vec![
+                Vector3::new(1, 2, 3),
+                Vector3::new(-1, 2, -3),
+                Vector3::new(1, -2, 3),
+                Vector3::new(-1, -2, -3),
+            ],
+            hkl
+        );
+        assert_eq!(4, reflections.multiplicity());
+        assert!(reflections.is_centric());
+        assert_eq!(Some([0.0, PI]), reflections.restricted_phases());
+        // -x,y+1/2,-z+1/2: h·w = (2+3)/2
+        let shift = reflections.equivalents()[1];
+        assert!((shift.phase_shift() - PI).abs() < 1e-12);
+        assert!((shift.phase_factor().re + 1.0).abs() < 1e-12);
+
+        let reflections = positions.equivalent_reflections(&Vector3::new(1, 0, 1));
+        assert_eq!(2, reflections.equivalents().len());
+        assert_eq!(2, reflections.multiplicity());
+
+        // P2₁2₁2₁: only the zones are centric, hk0 with φ = π k/2 mod π
+        let positions = HallSymbolNotation::try_from_str("P 2ac 2ab")
+            .unwrap()
+            .general_positions();
+        let reflections = positions.equivalent_reflections(&Vector3::new(1, 2, 3));
+        assert_eq!(4, reflections.equivalents().len());
+        assert_eq!(8, reflections.multiplicity());
+        assert!(!reflections.is_centric());
+        assert_eq!(None, reflections.restricted_phases());
+        let reflections = positions.equivalent_reflections(&Vector3::new(1, 1, 0));
+        assert!(reflections.is_centric());
+        assert_eq!(4, reflections.multiplicity());
+        let phases = reflections.restricted_phases().unwrap();
+        assert!((phases[0] - PI / 2.0).abs() < 1e-12);
+
+        // Pm-3m
+        let positions = HallSymbolNotation::try_from_str("-P 4 2 3")
+            .unwrap()
+            .general_positions();
+        for (hkl, multiplicity) in [
+            ([1, 0, 0], 6),
+            ([1, 1, 0], 12),
+            ([1, 1, 1], 8),
+            ([1, 2, 0], 24),
+            ([1, 1, 2], 24),
+            ([1, 2, 3], 48),
+        ] {
+            let reflections = positions.equivalent_reflections(&Vector3::from(hkl));
+            assert_eq!(multiplicity, reflections.multiplicity(), "{hkl:?}");
+        }
+    }
+}
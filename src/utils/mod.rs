@@ -1,5 +1,10 @@
+use fraction::Ratio;
+
 use crate::hall_symbols::SEITZ_TRANSLATE_BASE_NUMBER;
 
+/// Exact rational number used for the coordinates of the special positions
+pub(crate) type Rational = Ratio<i64>;
+
 /// Get the positive mod against `SEITZ_TRANSLATE_BASE_NUMBER` (12)
 /// for i32
 pub(crate) fn positive_mod_stbn_i32(val: i32) -> i32 {
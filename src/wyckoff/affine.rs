@@ -0,0 +1,500 @@
+use std::fmt::Display;
+
+use fraction::{Integer, Signed, Zero};
+use nalgebra::{Matrix3, Vector3};
+
+use crate::{
+    hall_symbols::{change_of_basis::inverse, SEITZ_TRANSLATE_BASE_NUMBER},
+    utils::Rational,
+    ChangeOfBasis, SeitzMatrix,
+};
+
+/// Names of the free parameters, in the order of the coordinates they run along
+pub(crate) const PARAMETER_NAMES: [char; 3] = ['x', 'y', 'z'];
+
+fn rational(numer: i64, denom: i64) -> Rational {
+    Rational::new(numer, denom)
+}
+
+pub(crate) fn integer(v: i64) -> Rational {
+    Rational::from_integer(v)
+}
+
+/// Fractional part in `[0, 1)`
+pub(crate) fn reduce_into_cell(v: Rational) -> Rational {
+    v - v.floor()
+}
+
+/// Translation part of a `SeitzMatrix` as exact fractions
+fn translation_of(seitz_matrix: &SeitzMatrix) -> [Rational; 3] {
+    let t = seitz_matrix.translation_part();
+    [0, 1, 2].map(|i| rational(t[i] as i64, SEITZ_TRANSLATE_BASE_NUMBER as i64))
+}
+
+/// Affine subspace `{x | A x = b}` of the fractional coordinates.
+/// The rows `(A | b)` are kept in the reduced row echelon form, so that
+/// two descriptions of the same subspace compare equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct AffineSubspace {
+    rows: Vec<[Rational; 4]>,
+}
+
+impl AffineSubspace {
+    /// The whole space, i.e. the general position
+    pub(crate) fn whole() -> Self {
+        Self { rows: Vec::new() }
+    }
+
+    /// Build from the equations; `None` if they are inconsistent.
+    fn from_equations(rows: Vec<[Rational; 4]>) -> Option<Self> {
+        reduce_rows(rows, [0, 1, 2]).map(|(rows, _)| Self { rows })
+    }
+
+    /// Points fixed by the operation `seitz_matrix` followed by the lattice translation `t`:
+    /// `(I - W)x = w + t`
+    pub(crate) fn fixed_by(seitz_matrix: &SeitzMatrix, t: &Vector3<i32>) -> Option<Self> {
+        let w = translation_of(seitz_matrix);
+        let translation = [0, 1, 2].map(|i| w[i] + integer(t[i] as i64));
+        Self::fixed_points(&seitz_matrix.rotation_part(), &translation)
+    }
+
+    /// Points fixed by the operation with the exact translation: `(I - W)x = w`
+    pub(crate) fn fixed_points(
+        rotation: &Matrix3<i32>,
+        translation: &[Rational; 3],
+    ) -> Option<Self> {
+        let rows = (0..3)
+            .map(|i| {
+                let mut row = [Rational::zero(); 4];
+                (0..3).for_each(|j| {
+                    let delta = if i == j { 1 } else { 0 };
+                    row[j] = integer((delta - rotation[(i, j)]) as i64);
+                });
+                row[3] = translation[i];
+                row
+            })
+            .collect();
+        Self::from_equations(rows)
+    }
+
+    /// The points of the coordinate triplet for all the values of its parameters
+    pub(crate) fn spanned_by(triplet: &CoordinateTriplet) -> Self {
+        let directions: Vec<[Rational; 4]> = (0..3)
+            .map(|j| {
+                let mut row = [Rational::zero(); 4];
+                (0..3).for_each(|i| row[i] = triplet.coefficients[i][j]);
+                row
+            })
+            .collect();
+        let (directions, pivots) =
+            reduce_rows(directions, [0, 1, 2]).expect("Homogeneous equations are consistent");
+        // Normal vectors of the directions, one for each non-pivot column
+        let rows = (0..3)
+            .filter(|c| !pivots.contains(c))
+            .map(|f| {
+                let mut row = [Rational::zero(); 4];
+                row[f] = integer(1);
+                pivots
+                    .iter()
+                    .zip(directions.iter())
+                    .for_each(|(&p, direction)| row[p] = -direction[f]);
+                row[3] = (0..3).fold(Rational::zero(), |acc, i| {
+                    acc + row[i] * triplet.constant[i]
+                });
+                row
+            })
+            .collect();
+        Self::from_equations(rows).expect("Equations through a point are consistent")
+    }
+
+    pub(crate) fn dim(&self) -> usize {
+        3 - self.rows.len()
+    }
+
+    pub(crate) fn intersect(&self, other: &Self) -> Option<Self> {
+        Self::from_equations(self.rows.iter().chain(other.rows.iter()).cloned().collect())
+    }
+
+    /// Image of the subspace under the operation: `A g^-1(x') = b`
+    pub(crate) fn transform(&self, seitz_matrix: &SeitzMatrix) -> Self {
+        let inv_rotation = inverse(&seitz_matrix.rotation_part().map(|v| integer(v as i64)));
+        let w = translation_of(seitz_matrix);
+        // g^-1 = (W^-1, -W^-1 w)
+        let inv_translation: [Rational; 3] = [0, 1, 2].map(|i| {
+            -(0..3)
+                .map(|j| inv_rotation[(i, j)] * w[j])
+                .fold(Rational::zero(), |acc, v| acc + v)
+        });
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                let mut new_row = [Rational::zero(); 4];
+                (0..3).for_each(|j| {
+                    new_row[j] = (0..3)
+                        .map(|k| row[k] * inv_rotation[(k, j)])
+                        .fold(Rational::zero(), |acc, v| acc + v);
+                });
+                new_row[3] = row[3]
+                    - (0..3)
+                        .map(|k| row[k] * inv_translation[k])
+                        .fold(Rational::zero(), |acc, v| acc + v);
+                new_row
+            })
+            .collect();
+        Self::from_equations(rows).expect("The image of a subspace is not empty")
+    }
+
+    /// The representative of the subspace modulo the lattice translations,
+    /// with the smallest right-hand sides in `[0, 1)`.
+    pub(crate) fn modulo_lattice(&self) -> Self {
+        let pivots = pivot_columns(&self.rows);
+        let free: Vec<usize> = (0..3).filter(|c| !pivots.contains(c)).collect();
+        // Shifts along the free coordinates repeat after the lcm of the denominators
+        let period = self
+            .rows
+            .iter()
+            .flat_map(|row| free.iter().map(|&f| *row[f].denom()))
+            .fold(1_i64, |acc, d| acc.lcm(&d));
+        let shifts: Vec<Vec<i64>> = free.iter().fold(vec![Vec::new()], |acc, _| {
+            acc.iter()
+                .flat_map(|prefix| {
+                    (0..period).map(move |s| {
+                        let mut shift = prefix.clone();
+                        shift.push(s);
+                        shift
+                    })
+                })
+                .collect()
+        });
+        shifts
+            .iter()
+            .map(|shift| {
+                let rows: Vec<[Rational; 4]> = self
+                    .rows
+                    .iter()
+                    .map(|row| {
+                        let mut row = *row;
+                        let b = free
+                            .iter()
+                            .zip(shift.iter())
+                            .fold(row[3], |acc, (&f, &s)| acc + row[f] * integer(s));
+                        row[3] = reduce_into_cell(b);
+                        row
+                    })
+                    .collect();
+                rows
+            })
+            .min_by(|a, b| {
+                let rhs = |rows: &Vec<[Rational; 4]>| rows.iter().map(|r| r[3]).collect::<Vec<_>>();
+                rhs(a).cmp(&rhs(b))
+            })
+            .map(|rows| Self { rows })
+            .unwrap_or_else(Self::whole)
+    }
+
+    /// A point on the subspace, with zero free coordinates
+    pub(crate) fn point(&self) -> [Rational; 3] {
+        let mut point = [Rational::zero(); 3];
+        pivot_columns(&self.rows)
+            .iter()
+            .zip(self.rows.iter())
+            .for_each(|(&p, row)| point[p] = row[3]);
+        point
+    }
+
+    /// Basis of the directions of the subspace
+    pub(crate) fn directions(&self) -> Vec<[Rational; 3]> {
+        let pivots = pivot_columns(&self.rows);
+        (0..3)
+            .filter(|c| !pivots.contains(c))
+            .map(|f| {
+                let mut direction = [Rational::zero(); 3];
+                direction[f] = integer(1);
+                pivots
+                    .iter()
+                    .zip(self.rows.iter())
+                    .for_each(|(&p, row)| direction[p] = -row[f]);
+                direction
+            })
+            .collect()
+    }
+
+    /// Whether the subspace passes through the closed unit cell `[0, 1]^3`
+    pub(crate) fn intersects_unit_cell(&self) -> bool {
+        let point = self.point();
+        let directions = self.directions();
+        let unit = |v: &Rational| *v >= Rational::zero() && *v <= integer(1);
+        match self.dim() {
+            0 => point.iter().all(unit),
+            1 => {
+                let d = directions[0];
+                let mut lower: Option<Rational> = None;
+                let mut upper: Option<Rational> = None;
+                for i in 0..3 {
+                    if d[i].is_zero() {
+                        if !unit(&point[i]) {
+                            return false;
+                        }
+                    } else {
+                        let (s0, s1) = (-point[i] / d[i], (integer(1) - point[i]) / d[i]);
+                        let (lo, hi) = if s0 < s1 { (s0, s1) } else { (s1, s0) };
+                        lower = Some(lower.map_or(lo, |l| l.max(lo)));
+                        upper = Some(upper.map_or(hi, |u| u.min(hi)));
+                    }
+                }
+                lower.zip(upper).is_none_or(|(l, u)| l <= u)
+            }
+            2 => {
+                let row = self.rows[0];
+                let (min, max) =
+                    (0..3).fold((Rational::zero(), Rational::zero()), |(min, max), i| {
+                        if row[i].is_negative() {
+                            (min + row[i], max)
+                        } else {
+                            (min, max + row[i])
+                        }
+                    });
+                min <= row[3] && row[3] <= max
+            }
+            _ => true,
+        }
+    }
+
+    /// Whether every point of the subspace is mapped onto itself by the operation,
+    /// modulo the lattice translations. Returns the required lattice translation.
+    pub(crate) fn fixing_translation(&self, seitz_matrix: &SeitzMatrix) -> Option<Vector3<i32>> {
+        let rotation = seitz_matrix.rotation_part();
+        let apply = |v: &[Rational; 3]| -> [Rational; 3] {
+            [0, 1, 2].map(|i| {
+                (0..3)
+                    .map(|j| integer(rotation[(i, j)] as i64) * v[j])
+                    .fold(Rational::zero(), |acc, x| acc + x)
+            })
+        };
+        if self.directions().iter().any(|d| apply(d) != *d) {
+            return None;
+        }
+        let point = self.point();
+        let image = apply(&point);
+        let w = translation_of(seitz_matrix);
+        let t: [Rational; 3] = [0, 1, 2].map(|i| point[i] - image[i] - w[i]);
+        t.iter()
+            .all(|v| v.is_integer())
+            .then(|| Vector3::new(t[0], t[1], t[2]).map(|v| v.to_integer() as i32))
+    }
+
+    /// Parametric form with integer coefficients whenever possible,
+    /// e.g. `x,2x,z` instead of `1/2y,y,z`.
+    pub(crate) fn coordinate_triplet(&self) -> CoordinateTriplet {
+        let (constant, coefficients) = self.parametric_form();
+        CoordinateTriplet::new(constant, coefficients)
+    }
+
+    /// The constant part and the coefficients of the free parameters of the parametric
+    /// form, with the constant part not reduced into the unit cell
+    pub(crate) fn parametric_form(&self) -> ([Rational; 3], [[Rational; 3]; 3]) {
+        const ORDERS: [[usize; 3]; 6] = [
+            [2, 1, 0],
+            [2, 0, 1],
+            [1, 2, 0],
+            [1, 0, 2],
+            [0, 2, 1],
+            [0, 1, 2],
+        ];
+        let (rows, pivots) = ORDERS
+            .iter()
+            .filter_map(|&order| reduce_rows(self.rows.clone(), order))
+            .find(|(rows, _)| rows.iter().all(|r| r[..3].iter().all(|v| v.is_integer())))
+            .unwrap_or_else(|| {
+                let pivots = pivot_columns(&self.rows);
+                (self.rows.clone(), pivots)
+            });
+        let mut constant = [Rational::zero(); 3];
+        let mut coefficients = [[Rational::zero(); 3]; 3];
+        (0..3).filter(|c| !pivots.contains(c)).for_each(|f| {
+            coefficients[f][f] = integer(1);
+            pivots
+                .iter()
+                .zip(rows.iter())
+                .for_each(|(&p, row)| coefficients[p][f] = -row[f]);
+        });
+        pivots
+            .iter()
+            .zip(rows.iter())
+            .for_each(|(&p, row)| constant[p] = row[3]);
+        (constant, coefficients)
+    }
+}
+
+/// Pivot column of each row in the reduced row echelon form
+fn pivot_columns(rows: &[[Rational; 4]]) -> Vec<usize> {
+    rows.iter()
+        .map(|row| (0..3).find(|&c| !row[c].is_zero()).unwrap_or(3))
+        .collect()
+}
+
+/// Gauss-Jordan elimination taking the columns in the given order.
+/// Returns the non-zero rows sorted by their pivot order with the pivot columns,
+/// or `None` if the system is inconsistent.
+fn reduce_rows(
+    mut rows: Vec<[Rational; 4]>,
+    order: [usize; 3],
+) -> Option<(Vec<[Rational; 4]>, Vec<usize>)> {
+    let mut pivots: Vec<usize> = Vec::new();
+    let mut rank = 0;
+    for &col in order.iter() {
+        let Some(found) = (rank..rows.len()).find(|&r| !rows[r][col].is_zero()) else {
+            continue;
+        };
+        rows.swap(rank, found);
+        let pivot = rows[rank][col];
+        rows[rank].iter_mut().for_each(|v| *v /= pivot);
+        let pivot_row = rows[rank];
+        rows.iter_mut().enumerate().for_each(|(r, row)| {
+            if r != rank && !row[col].is_zero() {
+                let factor = row[col];
+                row.iter_mut()
+                    .zip(pivot_row.iter())
+                    .for_each(|(v, p)| *v -= factor * p);
+            }
+        });
+        pivots.push(col);
+        rank += 1;
+    }
+    if rows[rank..].iter().any(|row| !row[3].is_zero()) {
+        return None;
+    }
+    rows.truncate(rank);
+    Some((rows, pivots))
+}
+
+/// Coordinates of a Wyckoff position as an affine function of the free parameters,
+/// e.g. `x,-x+1/2,1/4`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CoordinateTriplet {
+    /// Constant part, reduced into `[0, 1)`
+    constant: [Rational; 3],
+    /// `coefficients[i][j]`: coefficient of the parameter `j` (`x`, `y`, `z`) in the coordinate `i`
+    coefficients: [[Rational; 3]; 3],
+}
+
+impl CoordinateTriplet {
+    pub(crate) fn new(constant: [Rational; 3], coefficients: [[Rational; 3]; 3]) -> Self {
+        Self {
+            constant: constant.map(reduce_into_cell),
+            coefficients,
+        }
+    }
+
+    /// Apply a symmetry operation to the triplet
+    pub(crate) fn transform(&self, seitz_matrix: &SeitzMatrix) -> Self {
+        let rotation = seitz_matrix.rotation_part().map(|v| integer(v as i64));
+        let w = translation_of(seitz_matrix);
+        let constant = [0, 1, 2]
+            .map(|i| (0..3).fold(w[i], |acc, k| acc + rotation[(i, k)] * self.constant[k]));
+        let coefficients = [0, 1, 2].map(|i| {
+            [0, 1, 2].map(|j| {
+                (0..3).fold(Rational::zero(), |acc, k| {
+                    acc + rotation[(i, k)] * self.coefficients[k][j]
+                })
+            })
+        });
+        Self::new(constant, coefficients)
+    }
+
+    /// The same points in the coordinates of the new basis: `x' = P⁻¹(x - p)`
+    pub(crate) fn change_basis(&self, change_of_basis: &ChangeOfBasis) -> Self {
+        let p_inv = inverse(&change_of_basis.matrix_rational());
+        let p = change_of_basis.vector_rational();
+        let constant = [0, 1, 2].map(|i| {
+            (0..3).fold(Rational::zero(), |acc, k| {
+                acc + p_inv[(i, k)] * (self.constant[k] - p[k])
+            })
+        });
+        let coefficients = [0, 1, 2].map(|i| {
+            [0, 1, 2].map(|j| {
+                (0..3).fold(Rational::zero(), |acc, k| {
+                    acc + p_inv[(i, k)] * self.coefficients[k][j]
+                })
+            })
+        });
+        Self::new(constant, coefficients)
+    }
+
+    /// Indices of the free parameters (0 for `x`, 1 for `y`, 2 for `z`)
+    pub fn free_parameters(&self) -> Vec<usize> {
+        (0..3)
+            .filter(|&j| self.coefficients.iter().any(|row| !row[j].is_zero()))
+            .collect()
+    }
+
+    /// Number of the free parameters
+    pub fn degrees_of_freedom(&self) -> usize {
+        self.free_parameters().len()
+    }
+
+    /// Evaluate the coordinates with the values of the parameters `x`, `y`, `z`.
+    /// Values of the parameters which do not appear in the triplet are ignored.
+    pub fn evaluate(&self, parameters: &Vector3<f64>) -> Vector3<f64> {
+        Vector3::from_fn(|i, _| {
+            (0..3).fold(to_f64(self.constant[i]), |acc, j| {
+                acc + to_f64(self.coefficients[i][j]) * parameters[j]
+            })
+        })
+    }
+
+    /// Constant part of the coordinates
+    pub fn constant_f64(&self) -> Vector3<f64> {
+        Vector3::from_fn(|i, _| to_f64(self.constant[i]))
+    }
+
+    pub(crate) fn constant(&self) -> &[Rational; 3] {
+        &self.constant
+    }
+
+    pub(crate) fn coefficients(&self) -> &[[Rational; 3]; 3] {
+        &self.coefficients
+    }
+}
+
+pub(crate) fn to_f64(v: Rational) -> f64 {
+    *v.numer() as f64 / *v.denom() as f64
+}
+
+impl Display for CoordinateTriplet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let coordinates: Vec<String> = (0..3)
+            .map(|i| {
+                let mut expr = String::new();
+                (0..3).for_each(|j| {
+                    let c = self.coefficients[i][j];
+                    if c.is_zero() {
+                        return;
+                    }
+                    let sign = if c.is_negative() {
+                        "-"
+                    } else if expr.is_empty() {
+                        ""
+                    } else {
+                        "+"
+                    };
+                    let abs = c.abs();
+                    let factor = if abs == integer(1) {
+                        String::new()
+                    } else {
+                        format!("{abs}")
+                    };
+                    expr.push_str(&format!("{sign}{factor}{}", PARAMETER_NAMES[j]));
+                });
+                let constant = self.constant[i];
+                match (expr.is_empty(), constant.is_zero()) {
+                    (true, _) => format!("{constant}"),
+                    (false, true) => expr,
+                    (false, false) => format!("{expr}+{constant}"),
+                }
+            })
+            .collect();
+        write!(f, "{}", coordinates.join(","))
+    }
+}
@@ -0,0 +1,221 @@
+use nalgebra::{Matrix3, Vector3};
+
+use crate::{
+    database::SpaceGroupHallSymbol,
+    hall_symbols::{change_of_basis::inverse, SEITZ_TRANSLATE_BASE_NUMBER},
+    GeneralPositions, GroupGenerationError, HallSymbolNotation, SeitzMatrix,
+};
+
+use super::{
+    affine::{integer, to_f64},
+    full_operators, WyckoffPosition,
+};
+
+/// Tolerance to decide whether an operation fixes an already symmetrised point
+const SYMMETRISED_TOLERANCE: f64 = 1e-8;
+/// Threshold of the singular values ignored by the least-squares projection
+const PSEUDO_INVERSE_EPSILON: f64 = 1e-12;
+
+/// A point located on a Wyckoff position
+#[derive(Debug, Clone, PartialEq)]
+pub struct WyckoffSite {
+    position: WyckoffPosition,
+    /// Values of the free parameters of the representative, in the order of `x`, `y`, `z`
+    parameters: Vec<f64>,
+    /// The point moved exactly onto the Wyckoff position
+    symmetrised: Vector3<f64>,
+    /// Operations of the site-symmetry group of the symmetrised point
+    site_symmetry_operations: Vec<SeitzMatrix>,
+}
+
+impl WyckoffSite {
+    pub fn position(&self) -> &WyckoffPosition {
+        &self.position
+    }
+
+    /// Values of the free parameters of the representative triplet of the position,
+    /// e.g. the values of `x` and `z` for `x,1/4,z`. The values are in `[0, 1)`.
+    pub fn parameters(&self) -> &[f64] {
+        &self.parameters
+    }
+
+    /// The point moved exactly onto the Wyckoff position, close to the given point
+    pub fn symmetrised(&self) -> Vector3<f64> {
+        self.symmetrised
+    }
+
+    /// Operations of the site-symmetry group of the symmetrised point, with the lattice
+    /// translations included so that they fix the point.
+    pub fn site_symmetry_operations(&self) -> &[SeitzMatrix] {
+        &self.site_symmetry_operations
+    }
+
+    /// Oriented site-symmetry symbol of the position
+    pub fn site_symmetry(&self) -> &str {
+        self.position.site_symmetry()
+    }
+}
+
+impl HallSymbolNotation {
+    /// Locate the point in fractional coordinates on the Wyckoff positions of the group.
+    /// See `assign_wyckoff_position`. Fails when the symbol does not generate a group.
+    pub fn assign_wyckoff_position(
+        &self,
+        point: &Vector3<f64>,
+        tolerance: f64,
+    ) -> Result<Option<WyckoffSite>, GroupGenerationError> {
+        let general_positions = self.try_general_positions()?;
+        Ok(assign_wyckoff_position(
+            &general_positions,
+            &super::wyckoff_positions(&general_positions),
+            point,
+            tolerance,
+        ))
+    }
+}
+
+impl SpaceGroupHallSymbol {
+    /// Locate the point in fractional coordinates on the Wyckoff positions of the setting.
+    /// See `assign_wyckoff_position`.
+    pub fn assign_wyckoff_position(
+        &self,
+        point: &Vector3<f64>,
+        tolerance: f64,
+    ) -> Option<WyckoffSite> {
+        assign_wyckoff_position(
+            &HallSymbolNotation::from(*self).general_positions(),
+            self.wyckoff_positions(),
+            point,
+            tolerance,
+        )
+    }
+}
+
+/// Locate the point on the most special Wyckoff position lying within `tolerance`,
+/// measured as the distance in fractional coordinates.
+/// The positions are tried from the letter `a`, so the general position is found
+/// when no special position is close enough. Returns `None` only for a negative
+/// tolerance.
+pub fn assign_wyckoff_position(
+    general_positions: &GeneralPositions,
+    wyckoff_positions: &[WyckoffPosition],
+    point: &Vector3<f64>,
+    tolerance: f64,
+) -> Option<WyckoffSite> {
+    let operators = full_operators(general_positions);
+    wyckoff_positions.iter().find_map(|position| {
+        operators.iter().find_map(|g| {
+            let (parameters, symmetrised) = fit_representative(position, g, point, tolerance)?;
+            Some(WyckoffSite {
+                position: position.clone(),
+                parameters,
+                site_symmetry_operations: general_positions
+                    .orbit(symmetrised, SYMMETRISED_TOLERANCE)
+                    .stabilizer()
+                    .to_vec(),
+                symmetrised,
+            })
+        })
+    })
+}
+
+/// Fit the image of the point under `g` to the representative triplet of the position.
+/// Returns the values of the free parameters and the symmetrised point.
+fn fit_representative(
+    position: &WyckoffPosition,
+    g: &SeitzMatrix,
+    point: &Vector3<f64>,
+    tolerance: f64,
+) -> Option<(Vec<f64>, Vector3<f64>)> {
+    let representative = position.representative();
+    let free = representative.free_parameters();
+    let image = g.transform_point(point);
+    // The representative takes the free parameters as the coordinates themselves,
+    // e.g. `x,2x,z`, so reading them from the image gives a first guess to fix the
+    // lattice translation.
+    let mut guess = Vector3::zeros();
+    free.iter().for_each(|&j| guess[j] = image[j]);
+    let lattice_shift = (image - representative.evaluate(&guess)).map(|v| v.round());
+    // Least-squares projection onto the affine subspace, in the same norm as the tolerance
+    let coefficients = Matrix3::from_fn(|i, j| to_f64(representative.coefficients()[i][j]));
+    let values = coefficients.pseudo_inverse(PSEUDO_INVERSE_EPSILON).ok()?
+        * (image - lattice_shift - representative.constant_f64());
+    let fitted = representative.evaluate(&values) + lattice_shift;
+    if (image - fitted).norm() > tolerance {
+        return None;
+    }
+    // Move the fitted point back with the inverse of `g`
+    let inverse_rotation = inverse(&g.rotation_part().map(|v| integer(v as i64))).map(to_f64);
+    let translation = g
+        .translation_part()
+        .map(|v| v as f64 / SEITZ_TRANSLATE_BASE_NUMBER as f64);
+    let symmetrised = inverse_rotation * (fitted - translation);
+    Some((
+        free.iter().map(|&j| values[j].rem_euclid(1.0)).collect(),
+        symmetrised,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use nalgebra::Vector3;
+
+    use crate::{database::SpaceGroupHallSymbol, HallSymbolNotation};
+
+    #[test]
+    fn assign_pnma() {
+        let group = HallSymbolNotation::try_from_str("-P 2ac 2n").unwrap();
+        // Close to the mirror at y = 3/4, an image of 4c `x,1/4,z`
+        let site = group
+            .assign_wyckoff_position(&Vector3::new(0.3, 0.751, 0.6), 0.01)
+            .unwrap()
+            .unwrap();
+        assert_eq!("4c", site.position().label());
+        assert_eq!(".m.", site.site_symmetry());
+        assert_eq!(2, site.site_symmetry_operations().len());
+        assert_eq!(2, site.parameters().len());
+        assert!((site.symmetrised() - Vector3::new(0.3, 0.75, 0.6)).norm() < 1e-9);
+        // Too far from the mirror with a tight tolerance
+        let site = group
+            .assign_wyckoff_position(&Vector3::new(0.3, 0.751, 0.6), 1e-4)
+            .unwrap()
+            .unwrap();
+        assert_eq!("8d", site.position().label());
+        assert_eq!(1, site.site_symmetry_operations().len());
+    }
+
+    #[test]
+    fn assign_fm3m() {
+        let group = SpaceGroupHallSymbol::try_from_hm("F m -3 m").unwrap();
+        let site = group
+            .assign_wyckoff_position(&Vector3::new(0.749, 0.25, 1.251), 0.01)
+            .unwrap();
+        assert_eq!("8c", site.position().label());
+        assert_eq!(24, site.site_symmetry_operations().len());
+        assert!(site.parameters().is_empty());
+        let site = group
+            .assign_wyckoff_position(&Vector3::new(0.1, 0.1, 0.102), 0.01)
+            .unwrap();
+        assert_eq!('f', site.position().letter());
+        assert!((site.parameters()[0] - 0.1).abs() < 0.01);
+        site.site_symmetry_operations().iter().for_each(|m| {
+            let image = m.transform_point(&site.symmetrised());
+            assert!((image - site.symmetrised()).norm() < 1e-9);
+        });
+    }
+
+    #[test]
+    fn assign_perturbed_hexagonal() {
+        let group = SpaceGroupHallSymbol::try_from_hm("P 6/m m m").unwrap();
+        // Off the mirror of `x,2x,z` by (0.004, -0.002, 0): the direct readout of
+        // `x` would leave a residual of 0.01 on `y`
+        let site = group
+            .assign_wyckoff_position(&Vector3::new(0.204, 0.398, 0.3), 0.05)
+            .unwrap();
+        assert_eq!("x,2x,z", site.position().representative().to_string());
+        assert_eq!(2, site.parameters().len());
+        assert!((site.parameters()[0] - 0.2).abs() < 1e-9);
+        assert!((site.parameters()[1] - 0.3).abs() < 1e-9);
+        assert!((site.symmetrised() - Vector3::new(0.2, 0.4, 0.3)).norm() < 1e-9);
+    }
+}
@@ -0,0 +1,605 @@
+//! Wyckoff positions derived from the symmetry operations.
+//!
+//! The special positions are found as the affine subspaces fixed by the operations
+//! (combined with the lattice translations) and their intersections. Subspaces related
+//! by the operations of the space group form one Wyckoff position, whose multiplicity
+//! is the order of the group divided by the order of the site-symmetry group.
+
+use std::{collections::HashSet, sync::OnceLock};
+
+use fraction::Signed;
+use nalgebra::{Matrix3, Vector3};
+
+use crate::{
+    database::{identify_space_group, SpaceGroupHallSymbol},
+    hall_symbols::SEITZ_TRANSLATE_BASE_NUMBER,
+    utils::{positive_mod_stbn_i32, Rational},
+    ChangeOfBasis, GeneralPositions, GroupGenerationError, HallSymbolNotation, SeitzMatrix,
+};
+
+use self::affine::AffineSubspace;
+
+pub(crate) mod affine;
+mod assignment;
+mod site_symmetry;
+mod table;
+
+pub use affine::CoordinateTriplet;
+pub use assignment::{assign_wyckoff_position, WyckoffSite};
+pub use site_symmetry::SiteSymmetry;
+
+/// Letters of the Wyckoff positions. The general position of `P m m m` is the 27th.
+const WYCKOFF_LETTERS: [char; 27] = [
+    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's',
+    't', 'u', 'v', 'w', 'x', 'y', 'z', 'α',
+];
+
+/// A Wyckoff position of a space group setting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WyckoffPosition {
+    letter: char,
+    multiplicity: usize,
+    site_symmetry: String,
+    /// Coordinate triplets of all the equivalent positions in the conventional cell,
+    /// the first one is the representative.
+    coordinates: Vec<CoordinateTriplet>,
+    /// Operations of the site-symmetry group of the representative, with the
+    /// lattice translations included so that they fix the representative.
+    site_symmetry_operations: Vec<SeitzMatrix>,
+}
+
+impl WyckoffPosition {
+    pub fn letter(&self) -> char {
+        self.letter
+    }
+
+    /// Number of the equivalent positions in the conventional cell
+    pub fn multiplicity(&self) -> usize {
+        self.multiplicity
+    }
+
+    /// Oriented site-symmetry symbol, e.g. `..2`, `.2/m.`, `4/mmm`
+    pub fn site_symmetry(&self) -> &str {
+        &self.site_symmetry
+    }
+
+    /// Coordinate triplets of the equivalent positions in the conventional cell,
+    /// including the centring translations. The first one is the representative.
+    pub fn coordinates(&self) -> &[CoordinateTriplet] {
+        &self.coordinates
+    }
+
+    /// The representative coordinate triplet, e.g. `x,1/4,z`
+    pub fn representative(&self) -> &CoordinateTriplet {
+        &self.coordinates[0]
+    }
+
+    /// Operations of the site-symmetry group of the representative
+    pub fn site_symmetry_operations(&self) -> &[SeitzMatrix] {
+        &self.site_symmetry_operations
+    }
+
+    /// Number of the free parameters
+    pub fn degrees_of_freedom(&self) -> usize {
+        self.representative().degrees_of_freedom()
+    }
+
+    /// Label like `4e`
+    pub fn label(&self) -> String {
+        format!("{}{}", self.multiplicity, self.letter)
+    }
+}
+
+impl HallSymbolNotation {
+    /// Wyckoff positions of the group, from the most special position `a`
+    /// to the general position, or the reason why the symbol does not generate a group
+    pub fn wyckoff_positions(&self) -> Result<Vec<WyckoffPosition>, GroupGenerationError> {
+        Ok(wyckoff_positions(&self.try_general_positions()?))
+    }
+}
+
+impl SpaceGroupHallSymbol {
+    /// Wyckoff positions of the setting, derived once and kept for later calls.
+    /// See `wyckoff_positions` for the order of the letters.
+    pub fn wyckoff_positions(&self) -> &'static [WyckoffPosition] {
+        static TABLE: [OnceLock<Vec<WyckoffPosition>>; 530] = [const { OnceLock::new() }; 530];
+        TABLE[*self as usize].get_or_init(|| {
+            let general_positions = HallSymbolNotation::from(*self).general_positions();
+            let operators = full_operators(&general_positions);
+            let tabulated = tabulated_representatives_in(*self, &ChangeOfBasis::identity());
+            derive_wyckoff_positions(&general_positions, &operators, tabulated)
+        })
+    }
+
+    /// Find the Wyckoff position by its letter
+    pub fn wyckoff_position(&self, letter: char) -> Option<&'static WyckoffPosition> {
+        self.wyckoff_positions().iter().find(|w| w.letter == letter)
+    }
+}
+
+/// Derive the Wyckoff positions from the general positions.
+///
+/// The letters and the representatives follow the International Tables: the space group
+/// setting is identified, and the tabulated representatives are transformed from the
+/// default setting into the coordinates of the input. If this fails, the letters are
+/// assigned in the ascending order of the multiplicities, then of the number of free
+/// parameters and of the coordinates, which may differ from the International Tables.
+pub fn wyckoff_positions(general_positions: &GeneralPositions) -> Vec<WyckoffPosition> {
+    let operators = full_operators(general_positions);
+    let tabulated = identify_space_group(&operators).and_then(|found| {
+        tabulated_representatives_in(found.symbol(), &found.change_of_basis().inverse()?)
+    });
+    derive_wyckoff_positions(general_positions, &operators, tabulated)
+}
+
+/// The tabulated representatives of the special positions of the setting `symbol`,
+/// in the coordinates given by `to_input` from the ones of the setting
+fn tabulated_representatives_in(
+    symbol: SpaceGroupHallSymbol,
+    to_input: &ChangeOfBasis,
+) -> Option<Vec<CoordinateTriplet>> {
+    let entry = symbol.entry();
+    let change_of_basis = entry
+        .default_setting()
+        .change_of_basis_to(&symbol)?
+        .then(to_input)?;
+    Some(
+        table::tabulated_representatives(entry.number())
+            .iter()
+            .map(|triplet| triplet.change_basis(&change_of_basis))
+            .collect(),
+    )
+}
+
+fn derive_wyckoff_positions(
+    general_positions: &GeneralPositions,
+    operators: &[SeitzMatrix],
+    tabulated: Option<Vec<CoordinateTriplet>>,
+) -> Vec<WyckoffPosition> {
+    let group_rotations: Vec<Matrix3<i32>> = general_positions
+        .core_position_set()
+        .iter()
+        .map(|m| m.rotation_part())
+        .collect();
+    let subspaces = special_subspaces(operators);
+    // Group the subspaces (modulo lattice translations) into orbits
+    let mut classified: HashSet<AffineSubspace> = HashSet::new();
+    let mut orbits: Vec<Vec<AffineSubspace>> = Vec::new();
+    subspaces.iter().for_each(|s| {
+        let key = s.modulo_lattice();
+        if classified.contains(&key) {
+            return;
+        }
+        let mut orbit: Vec<AffineSubspace> = Vec::new();
+        operators.iter().for_each(|g| {
+            let image = key.transform(g).modulo_lattice();
+            if classified.insert(image.clone()) {
+                orbit.push(image);
+            }
+        });
+        orbits.push(orbit);
+    });
+    let position_of = |subspace: &AffineSubspace, representative: CoordinateTriplet| {
+        let site_symmetry_operations: Vec<SeitzMatrix> = operators
+            .iter()
+            .filter_map(|g| {
+                subspace.fixing_translation(g).map(|t| {
+                    let mut m = *g;
+                    m.set_translation_part(g.translation_part() + t * SEITZ_TRANSLATE_BASE_NUMBER);
+                    m
+                })
+            })
+            .collect();
+        let multiplicity = operators.len() / site_symmetry_operations.len();
+        let mut coordinates: Vec<CoordinateTriplet> = Vec::with_capacity(multiplicity);
+        operators.iter().for_each(|g| {
+            let image = representative.transform(g);
+            if !coordinates.contains(&image) {
+                coordinates.push(image);
+            }
+        });
+        let site_rotations: Vec<Matrix3<i32>> = site_symmetry_operations
+            .iter()
+            .map(|m| m.rotation_part())
+            .collect();
+        WyckoffPosition {
+            letter: ' ',
+            multiplicity,
+            site_symmetry: site_symmetry::oriented_symbol(&group_rotations, &site_rotations),
+            coordinates,
+            site_symmetry_operations,
+        }
+    };
+    let positions = tabulated
+        .and_then(|tabulated| {
+            tabulated_positions(&orbits, &tabulated).map(|matched| {
+                matched
+                    .iter()
+                    .map(|(subspace, representative)| position_of(subspace, *representative))
+                    .collect::<Vec<WyckoffPosition>>()
+            })
+        })
+        .unwrap_or_else(|| {
+            let mut positions: Vec<(WyckoffPosition, usize)> = orbits
+                .iter()
+                .map(|orbit| {
+                    let (subspace, representative) = orbit
+                        .iter()
+                        .map(|s| (s, s.coordinate_triplet()))
+                        .min_by_key(|(_, triplet)| representative_cost(triplet))
+                        .expect("Orbit is not empty");
+                    (position_of(subspace, representative), subspace.dim())
+                })
+                .collect();
+            positions.sort_by_cached_key(|(position, dim)| {
+                let representative = position.representative();
+                (
+                    position.multiplicity,
+                    *dim,
+                    orientation_order(representative),
+                    representative_cost(representative),
+                )
+            });
+            positions
+                .into_iter()
+                .map(|(position, _)| position)
+                .collect()
+        });
+    positions
+        .into_iter()
+        .zip(WYCKOFF_LETTERS.iter())
+        .map(|(mut position, &letter)| {
+            position.letter = letter;
+            position
+        })
+        .collect()
+}
+
+/// Match the tabulated representatives with the orbits of the special subspaces, in the
+/// order of the table followed by the general position. The tabulated parameters are
+/// kept unless the change of basis mixes them, e.g. from the hexagonal axes to the
+/// rhombohedral ones. `None` if the table does not cover every orbit exactly once.
+fn tabulated_positions(
+    orbits: &[Vec<AffineSubspace>],
+    tabulated: &[CoordinateTriplet],
+) -> Option<Vec<(AffineSubspace, CoordinateTriplet)>> {
+    if tabulated.len() + 1 != orbits.len() {
+        return None;
+    }
+    let mut matched: Vec<usize> = Vec::with_capacity(orbits.len());
+    let mut positions: Vec<(AffineSubspace, CoordinateTriplet)> = tabulated
+        .iter()
+        .map(|triplet| {
+            let subspace = AffineSubspace::spanned_by(triplet);
+            let key = subspace.modulo_lattice();
+            let index = orbits.iter().position(|orbit| orbit.contains(&key))?;
+            if matched.contains(&index) || subspace.dim() == 3 {
+                return None;
+            }
+            matched.push(index);
+            let integral = triplet
+                .coefficients()
+                .iter()
+                .flatten()
+                .all(|v| v.is_integer());
+            let representative = if integral {
+                *triplet
+            } else {
+                subspace.coordinate_triplet()
+            };
+            Some((subspace, representative))
+        })
+        .collect::<Option<_>>()?;
+    positions.push((
+        AffineSubspace::whole(),
+        AffineSubspace::whole().coordinate_triplet(),
+    ));
+    Some(positions)
+}
+
+/// All the operations in the conventional cell, with the translations reduced into `[0, 1)`
+fn full_operators(general_positions: &GeneralPositions) -> Vec<SeitzMatrix> {
+    general_positions
+        .derive_full_sets()
+        .concat()
+        .into_iter()
+        .map(|m| {
+            let mut m = m;
+            m.set_translation_part(m.translation_part().map(positive_mod_stbn_i32));
+            m
+        })
+        .collect()
+}
+
+/// Preference of the representative triplet: positive coefficients, parameters along
+/// the earlier axes, fewer and smaller non-zero constants.
+fn representative_cost(
+    triplet: &CoordinateTriplet,
+) -> (usize, Vec<usize>, usize, Rational, [Rational; 3]) {
+    let negatives = triplet
+        .coefficients()
+        .iter()
+        .flatten()
+        .filter(|v| v.is_negative())
+        .count();
+    let constant = triplet.constant();
+    let non_zeros = constant
+        .iter()
+        .filter(|v| **v != Rational::default())
+        .count();
+    let sum = constant.iter().fold(Rational::default(), |acc, v| acc + v);
+    (
+        negatives,
+        triplet.free_parameters(),
+        non_zeros,
+        sum,
+        *constant,
+    )
+}
+
+/// Lines are ordered by the axis they run along, planes by the axis they are
+/// fixed on, e.g. `x,0,0` before `0,y,0`, and `0,y,z` before `x,0,z`.
+fn orientation_order(triplet: &CoordinateTriplet) -> Vec<usize> {
+    let free = triplet.free_parameters();
+    if free.len() == 2 {
+        (0..3).filter(|i| !free.contains(i)).collect()
+    } else {
+        free
+    }
+}
+
+/// All the subspaces passing through the unit cell which are fixed pointwise
+/// by some operations, together with the whole space for the general position.
+fn special_subspaces(operators: &[SeitzMatrix]) -> Vec<AffineSubspace> {
+    let mut fixed: Vec<AffineSubspace> = Vec::new();
+    let mut known: HashSet<AffineSubspace> = HashSet::new();
+    operators
+        .iter()
+        .filter(|g| g.rotation_part() != Matrix3::identity())
+        .for_each(|g| {
+            lattice_shifts_through_cell(g).iter().for_each(|t| {
+                if let Some(s) = AffineSubspace::fixed_by(g, t) {
+                    if s.intersects_unit_cell() && known.insert(s.clone()) {
+                        fixed.push(s);
+                    }
+                }
+            })
+        });
+    // Close the set under intersection
+    let mut subspaces: Vec<AffineSubspace> = vec![AffineSubspace::whole()];
+    subspaces.extend(fixed.iter().cloned());
+    let mut i = 1;
+    while i < subspaces.len() {
+        if subspaces[i].dim() > 0 {
+            let current = subspaces[i].clone();
+            fixed.iter().for_each(|f| {
+                if let Some(s) = current.intersect(f) {
+                    if s.dim() < current.dim()
+                        && s.intersects_unit_cell()
+                        && known.insert(s.clone())
+                    {
+                        subspaces.push(s);
+                    }
+                }
+            });
+        }
+        i += 1;
+    }
+    subspaces
+}
+
+/// Lattice translations `t` for which `(I - W)x = w + t` can have a solution in the unit cell
+fn lattice_shifts_through_cell(seitz_matrix: &SeitzMatrix) -> Vec<Vector3<i32>> {
+    let rotation = seitz_matrix.rotation_part();
+    let w = seitz_matrix.translation_part();
+    let ranges: Vec<Vec<i32>> = (0..3)
+        .map(|i| {
+            let row: Vec<i32> = (0..3)
+                .map(|j| if i == j { 1 } else { 0 } - rotation[(i, j)])
+                .collect();
+            let lower: i32 = row.iter().filter(|v| **v < 0).sum();
+            let upper: i32 = row.iter().filter(|v| **v > 0).sum();
+            // lower - w/12 <= t <= upper - w/12
+            (lower - 1..=upper)
+                .filter(|t| {
+                    let scaled = t * SEITZ_TRANSLATE_BASE_NUMBER + w[i];
+                    scaled >= lower * SEITZ_TRANSLATE_BASE_NUMBER
+                        && scaled <= upper * SEITZ_TRANSLATE_BASE_NUMBER
+                })
+                .collect()
+        })
+        .collect();
+    let mut shifts = Vec::new();
+    for &x in ranges[0].iter() {
+        for &y in ranges[1].iter() {
+            for &z in ranges[2].iter() {
+                shifts.push(Vector3::new(x, y, z));
+            }
+        }
+    }
+    shifts
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{database::SpaceGroupHallSymbol, HallSymbolNotation};
+
+    fn summary(symbol: &str) -> Vec<String> {
+        HallSymbolNotation::try_from_str(symbol)
+            .unwrap()
+            .wyckoff_positions()
+            .unwrap()
+            .iter()
+            .map(|w| format!("{} {} {}", w.label(), w.site_symmetry(), w.representative()))
+            .collect()
+    }
+
+    #[test]
+    fn wyckoff_pnma() {
+        assert_eq!(
+            vec![
+                "4a -1 0,0,0",
+                "4b -1 0,0,1/2",
+                "4c .m. x,1/4,z",
+                "8d 1 x,y,z"
+            ],
+            summary("-P 2ac 2n")
+        );
+        let position = HallSymbolNotation::try_from_str("-P 2ac 2n")
+            .unwrap()
+            .wyckoff_positions()
+            .unwrap()[2]
+            .clone();
+        let coordinates: Vec<String> = position
+            .coordinates()
+            .iter()
+            .map(|c| format!("{c}"))
+            .collect();
+        assert_eq!(4, coordinates.len());
+        for expected in [
+            "x,1/4,z",
+            "-x+1/2,3/4,z+1/2",
+            "-x,3/4,-z",
+            "x+1/2,1/4,-z+1/2",
+        ] {
+            assert!(coordinates.contains(&expected.to_string()), "{expected}");
+        }
+        assert_eq!(2, position.site_symmetry_operations().len());
+    }
+
+    #[test]
+    fn wyckoff_hexagonal() {
+        let positions = summary("-P 6c 2c");
+        assert_eq!(12, positions.len());
+        assert_eq!("2b -6m2 0,0,1/4", positions[1]);
+        assert_eq!("6h mm2 x,2x,1/4", positions[7]);
+        assert_eq!("24l 1 x,y,z", positions[11]);
+    }
+
+    /// Labels and representatives as listed in the International Tables
+    fn labels(symbol: &str) -> Vec<String> {
+        HallSymbolNotation::try_from_str(symbol)
+            .unwrap()
+            .wyckoff_positions()
+            .unwrap()
+            .iter()
+            .map(|w| format!("{} {}", w.label(), w.representative()))
+            .collect()
+    }
+
+    #[test]
+    fn wyckoff_international_tables() {
+        // P 1 21/c 1
+        assert_eq!(
+            vec![
+                "2a 0,0,0",
+                "2b 1/2,0,0",
+                "2c 0,0,1/2",
+                "2d 1/2,0,1/2",
+                "4e x,y,z"
+            ],
+            labels("-P 2ybc")
+        );
+        // P m m m
+        assert_eq!(
+            vec![
+                "1a 0,0,0",
+                "1b 1/2,0,0",
+                "1c 0,0,1/2",
+                "1d 1/2,0,1/2",
+                "1e 0,1/2,0",
+                "1f 1/2,1/2,0",
+                "1g 0,1/2,1/2",
+                "1h 1/2,1/2,1/2",
+                "2i x,0,0",
+                "2j x,0,1/2",
+                "2k x,1/2,0",
+                "2l x,1/2,1/2",
+                "2m 0,y,0",
+                "2n 0,y,1/2",
+                "2o 1/2,y,0",
+                "2p 1/2,y,1/2",
+                "2q 0,0,z",
+                "2r 0,1/2,z",
+                "2s 1/2,0,z",
+                "2t 1/2,1/2,z",
+                "4u 0,y,z",
+                "4v 1/2,y,z",
+                "4w x,0,z",
+                "4x x,1/2,z",
+                "4y x,y,0",
+                "4z x,y,1/2",
+                "8α x,y,z"
+            ],
+            labels("-P 2 2")
+        );
+        // P 4/m m m
+        assert_eq!(
+            vec![
+                "1a 0,0,0",
+                "1b 0,0,1/2",
+                "1c 1/2,1/2,0",
+                "1d 1/2,1/2,1/2",
+                "2e 0,1/2,1/2",
+                "2f 0,1/2,0",
+                "2g 0,0,z",
+                "2h 1/2,1/2,z",
+                "4i 0,1/2,z",
+                "4j x,x,0",
+                "4k x,x,1/2",
+                "4l x,0,0",
+                "4m x,0,1/2",
+                "4n x,1/2,0",
+                "4o x,1/2,1/2",
+                "8p x,y,0",
+                "8q x,y,1/2",
+                "8r x,x,z",
+                "8s x,0,z",
+                "8t x,1/2,z",
+                "16u x,y,z"
+            ],
+            labels("-P 4 2")
+        );
+        // I a -3 d
+        assert_eq!(
+            vec![
+                "16a 0,0,0",
+                "16b 1/8,1/8,1/8",
+                "24c 1/8,0,1/4",
+                "24d 3/8,0,1/4",
+                "32e x,x,x",
+                "48f x,0,1/4",
+                "48g 1/8,y,-y+1/4",
+                "96h x,y,z"
+            ],
+            labels("-I 4bd 2c 3")
+        );
+    }
+
+    #[test]
+    fn wyckoff_tabulated() {
+        let fm3m = SpaceGroupHallSymbol::MF_4_2_3.wyckoff_positions();
+        let multiplicities: Vec<usize> = fm3m.iter().map(|w| w.multiplicity()).collect();
+        assert_eq!(
+            vec![4, 4, 8, 24, 24, 32, 48, 48, 48, 96, 96, 192],
+            multiplicities
+        );
+        assert_eq!("m-3m", fm3m[0].site_symmetry());
+        assert_eq!("-43m", fm3m[2].site_symmetry());
+        let f = SpaceGroupHallSymbol::MF_4_2_3
+            .wyckoff_position('f')
+            .unwrap();
+        assert_eq!(".3m", f.site_symmetry());
+        assert_eq!("x,x,x", format!("{}", f.representative()));
+        assert_eq!(1, f.degrees_of_freedom());
+        // Multiplicities in the rhombohedral axes are one third of those in the hexagonal axes
+        let hexagonal = SpaceGroupHallSymbol::MR_3_2D.wyckoff_positions();
+        let rhombohedral = SpaceGroupHallSymbol::MP_3A_2.wyckoff_positions();
+        assert_eq!(hexagonal.len(), rhombohedral.len());
+        assert!(hexagonal.iter().zip(rhombohedral.iter()).all(|(h, r)| {
+            h.multiplicity() == 3 * r.multiplicity()
+                && h.site_symmetry().trim_end_matches('.')
+                    == r.site_symmetry().trim_end_matches('.')
+        }));
+    }
+}
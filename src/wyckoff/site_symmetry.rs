@@ -0,0 +1,253 @@
+use nalgebra::{Matrix3, Vector3};
+
+use crate::{
+    database::{proper_rotation_order, CrystalSystem, PointGroup},
+    GeneralPositions, SeitzMatrix,
+};
+
+/// Site-symmetry group of a point
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiteSymmetry {
+    operations: Vec<SeitzMatrix>,
+    point_group: Option<PointGroup>,
+    oriented_symbol: String,
+}
+
+impl SiteSymmetry {
+    /// Operations fixing the point, with the lattice translations needed to fix it
+    pub fn operations(&self) -> &[SeitzMatrix] {
+        &self.operations
+    }
+
+    /// Point group of the site.
+    /// `None` when the operations found within the tolerance do not form a point group.
+    pub fn point_group(&self) -> Option<PointGroup> {
+        self.point_group
+    }
+
+    /// Oriented site-symmetry symbol, e.g. `.2/m.`
+    pub fn oriented_symbol(&self) -> &str {
+        &self.oriented_symbol
+    }
+
+    /// Order of the site-symmetry group
+    pub fn order(&self) -> usize {
+        self.operations.len()
+    }
+}
+
+impl GeneralPositions {
+    /// Site-symmetry group of a point in fractional coordinates, i.e. the operations
+    /// mapping the point onto itself within `tolerance`, modulo the lattice translations.
+    pub fn site_symmetry(&self, point: Vector3<f64>, tolerance: f64) -> SiteSymmetry {
+        let operations = self.orbit(point, tolerance).stabilizer().to_vec();
+        let group_rotations: Vec<Matrix3<i32>> = self
+            .core_position_set()
+            .iter()
+            .map(|m| m.rotation_part())
+            .collect();
+        let site_rotations: Vec<Matrix3<i32>> =
+            operations.iter().map(|m| m.rotation_part()).collect();
+        SiteSymmetry {
+            point_group: PointGroup::from_operations(&operations),
+            oriented_symbol: oriented_symbol(&group_rotations, &site_rotations),
+            operations,
+        }
+    }
+}
+
+/// Direction of the rotation axis, or the normal of the mirror plane.
+/// `None` for the identity and the inversion.
+fn rotation_axis(rotation: &Matrix3<i32>) -> Option<Vector3<i32>> {
+    SeitzMatrix::new(rotation.to_homogeneous()).axis_direction()
+}
+
+fn is_parallel(u: &Vector3<i32>, v: &Vector3<i32>) -> bool {
+    u.cross(v) == Vector3::zeros()
+}
+
+/// Sets of the equivalent symmetry directions in the order of the positions
+/// in the Hermann-Mauguin symbol of the lattice.
+pub(crate) fn symmetry_directions(rotations: &[Matrix3<i32>]) -> Vec<Vec<Vector3<i32>>> {
+    let v = Vector3::new;
+    match CrystalSystem::from_rotations(rotations) {
+        CrystalSystem::Triclinic => Vec::new(),
+        CrystalSystem::Monoclinic => rotations
+            .iter()
+            .find_map(rotation_axis)
+            .map(|axis| vec![vec![axis]])
+            .unwrap_or_default(),
+        CrystalSystem::Orthorhombic => vec![vec![v(1, 0, 0)], vec![v(0, 1, 0)], vec![v(0, 0, 1)]],
+        CrystalSystem::Tetragonal => vec![
+            vec![v(0, 0, 1)],
+            vec![v(1, 0, 0), v(0, 1, 0)],
+            vec![v(1, -1, 0), v(1, 1, 0)],
+        ],
+        CrystalSystem::Trigonal | CrystalSystem::Hexagonal => {
+            let rhombohedral_axes = rotations.iter().any(|r| {
+                proper_rotation_order(r) == 3
+                    && rotation_axis(r).is_some_and(|axis| axis == v(1, 1, 1))
+            });
+            if rhombohedral_axes {
+                vec![
+                    vec![v(1, 1, 1)],
+                    vec![v(1, -1, 0), v(0, 1, -1), v(-1, 0, 1)],
+                ]
+            } else {
+                vec![
+                    vec![v(0, 0, 1)],
+                    vec![v(1, 0, 0), v(0, 1, 0), v(-1, -1, 0)],
+                    vec![v(1, -1, 0), v(1, 2, 0), v(-2, -1, 0)],
+                ]
+            }
+        }
+        CrystalSystem::Cubic => vec![
+            vec![v(1, 0, 0), v(0, 1, 0), v(0, 0, 1)],
+            vec![v(1, 1, 1), v(1, -1, -1), v(-1, 1, -1), v(-1, -1, 1)],
+            vec![
+                v(1, -1, 0),
+                v(1, 1, 0),
+                v(0, 1, -1),
+                v(0, 1, 1),
+                v(-1, 0, 1),
+                v(1, 0, 1),
+            ],
+        ],
+    }
+}
+
+/// Symbol of the symmetry elements of the site along one direction
+fn element_along(
+    site_rotations: &[Matrix3<i32>],
+    direction: &Vector3<i32>,
+) -> Option<&'static str> {
+    let along: Vec<(i32, i32)> = site_rotations
+        .iter()
+        .filter(|r| rotation_axis(r).is_some_and(|axis| is_parallel(&axis, direction)))
+        .map(|r| {
+            let det = r.map(|v| v as f64).determinant().round() as i32;
+            (det, proper_rotation_order(r))
+        })
+        .collect();
+    let has = |det: i32, order: i32| along.contains(&(det, order));
+    // A mirror is the rotoinversion -2
+    let mirror = has(-1, 2);
+    if has(1, 6) {
+        Some(if mirror { "6/m" } else { "6" })
+    } else if has(-1, 6) {
+        // -6 = 3/m
+        Some("-6")
+    } else if has(1, 4) {
+        Some(if mirror { "4/m" } else { "4" })
+    } else if has(-1, 4) {
+        Some("-4")
+    } else if has(-1, 3) {
+        Some("-3")
+    } else if has(1, 3) {
+        Some("3")
+    } else if has(1, 2) {
+        Some(if mirror { "2/m" } else { "2" })
+    } else if mirror {
+        Some("m")
+    } else {
+        None
+    }
+}
+
+/// Oriented site-symmetry symbol, e.g. `.2/m.`, `4m.m` or `-3m`.
+/// Each position of the symbol refers to a set of symmetry directions of the lattice
+/// of the space group; an element is given for every direction of the set which is not
+/// equivalent to a previous one under the site-symmetry group, and `.` if none.
+pub(crate) fn oriented_symbol(
+    group_rotations: &[Matrix3<i32>],
+    site_rotations: &[Matrix3<i32>],
+) -> String {
+    let positions: Vec<Vec<&'static str>> = symmetry_directions(group_rotations)
+        .iter()
+        .map(|directions| {
+            let mut covered: Vec<Vector3<i32>> = Vec::new();
+            directions
+                .iter()
+                .filter_map(|d| {
+                    if covered.iter().any(|c| is_parallel(c, d)) {
+                        return None;
+                    }
+                    let symbol = element_along(site_rotations, d)?;
+                    site_rotations.iter().for_each(|r| covered.push(r * d));
+                    Some(symbol)
+                })
+                .collect()
+        })
+        .collect();
+    if positions.iter().all(|p| p.is_empty()) {
+        let inversion = site_rotations
+            .iter()
+            .any(|r| *r == -Matrix3::<i32>::identity());
+        return if inversion { "-1" } else { "1" }.to_string();
+    }
+    // Short symbols of the centrosymmetric groups with more than one `n/m`:
+    // `mmm`, `4/mmm`, `6/mmm`, `-3m`, `m-3`, `m-3m`
+    let tokens: Vec<&str> = positions.iter().flatten().copied().collect();
+    let with_minus_three = tokens.contains(&"-3");
+    let multiple_planes = tokens.iter().filter(|t| t.contains('/')).count() >= 2;
+    positions
+        .iter()
+        .map(|position| {
+            if position.is_empty() {
+                return ".".to_string();
+            }
+            position
+                .iter()
+                .map(|&token| match token {
+                    "2/m" if with_minus_three || multiple_planes => "m",
+                    "4/m" | "6/m" if with_minus_three => "m",
+                    _ => token,
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .concat()
+}
+
+#[cfg(test)]
+mod test {
+    use nalgebra::Vector3;
+
+    use crate::{database::PointGroup, HallSymbolNotation};
+
+    #[test]
+    fn site_symmetry_of_points() {
+        // P n m a: 4c `x,1/4,z` and the inversion centre 4b
+        let positions = HallSymbolNotation::try_from_str("-P 2ac 2n")
+            .unwrap()
+            .general_positions();
+        let site = positions.site_symmetry(Vector3::new(0.1, 0.25, 0.7), 1e-6);
+        assert_eq!(Some(PointGroup::Cs), site.point_group());
+        assert_eq!(".m.", site.oriented_symbol());
+        assert_eq!(2, site.order());
+        let site = positions.site_symmetry(Vector3::new(0.0, 0.0, 0.5), 1e-6);
+        assert_eq!(Some(PointGroup::Ci), site.point_group());
+        // F m -3 m
+        let positions = HallSymbolNotation::try_from_str("-F 4 2 3")
+            .unwrap()
+            .general_positions();
+        let site = positions.site_symmetry(Vector3::new(0.5, 0.5, 0.5), 1e-6);
+        assert_eq!(Some(PointGroup::Oh), site.point_group());
+        assert_eq!(48, site.order());
+        let site = positions.site_symmetry(Vector3::new(0.25, 0.25, 0.25), 1e-6);
+        assert_eq!(Some(PointGroup::Td), site.point_group());
+        let site = positions.site_symmetry(Vector3::new(0.0, 0.25, 0.25), 1e-6);
+        assert_eq!(Some(PointGroup::D2h), site.point_group());
+        assert_eq!("m.mm", site.oriented_symbol());
+        // P 6/m 2/m 2/m
+        let positions = HallSymbolNotation::try_from_str("-P 6 2")
+            .unwrap()
+            .general_positions();
+        let site = positions.site_symmetry(Vector3::new(0.0, 0.5, 0.0), 1e-6);
+        assert_eq!(Some(PointGroup::D2h), site.point_group());
+        assert_eq!("mmm", site.oriented_symbol());
+        let site = positions.site_symmetry(Vector3::new(1.0 / 3.0, 2.0 / 3.0, 0.5), 1e-6);
+        assert_eq!(Some(PointGroup::D3h), site.point_group());
+        assert_eq!("-6m2", site.oriented_symbol());
+    }
+}
@@ -0,0 +1,488 @@
+//! Representatives of the special Wyckoff positions of the default settings, as listed
+//! in the International Tables for Crystallography Vol. A: origin choice 1, hexagonal
+//! axes for the rhombohedral groups and the unique axis b for the monoclinic groups.
+
+use winnow::Parser;
+
+use crate::hall_symbols::coordinate_rows;
+
+use super::CoordinateTriplet;
+
+/// Representatives of the Wyckoff positions in the order of the letters, without the
+/// general position. The entry of the space group number `n` is at the index `n - 1`.
+const WYCKOFF_REPRESENTATIVES: [&str; 230] = [
+    // 1
+    "",
+    // 2
+    "0,0,0 0,0,1/2 0,1/2,0 1/2,0,0 1/2,1/2,0 1/2,0,1/2 0,1/2,1/2 1/2,1/2,1/2",
+    // 3
+    "0,y,0 0,y,1/2 1/2,y,0 1/2,y,1/2",
+    // 4
+    "",
+    // 5
+    "0,y,0 0,y,1/2",
+    // 6
+    "x,0,z x,1/2,z",
+    // 7
+    "",
+    // 8
+    "x,0,z",
+    // 9
+    "",
+    // 10
+    "0,0,0 0,1/2,0 0,0,1/2 1/2,0,0 1/2,1/2,0 0,1/2,1/2 1/2,0,1/2 1/2,1/2,1/2 0,y,0 1/2,y,0 0,y,1/2 1/2,y,1/2 x,0,z x,1/2,z",
+    // 11
+    "0,0,0 1/2,0,0 0,0,1/2 1/2,0,1/2 x,1/4,z",
+    // 12
+    "0,0,0 0,1/2,0 0,0,1/2 0,1/2,1/2 1/4,1/4,0 1/4,1/4,1/2 0,y,0 0,y,1/2 x,0,z",
+    // 13
+    "0,0,0 1/2,1/2,0 0,1/2,0 1/2,0,0 0,y,1/4 1/2,y,1/4",
+    // 14
+    "0,0,0 1/2,0,0 0,0,1/2 1/2,0,1/2",
+    // 15
+    "0,0,0 0,1/2,0 1/4,1/4,0 1/4,1/4,1/2 0,y,1/4",
+    // 16
+    "0,0,0 1/2,0,0 0,1/2,0 0,0,1/2 1/2,1/2,0 1/2,0,1/2 0,1/2,1/2 1/2,1/2,1/2 x,0,0 x,0,1/2 x,1/2,0 x,1/2,1/2 0,y,0 0,y,1/2 1/2,y,0 1/2,y,1/2 0,0,z 1/2,0,z 0,1/2,z 1/2,1/2,z",
+    // 17
+    "x,0,0 x,1/2,0 0,y,1/4 1/2,y,1/4",
+    // 18
+    "0,0,z 0,1/2,z",
+    // 19
+    "",
+    // 20
+    "x,0,0 0,y,1/4",
+    // 21
+    "0,0,0 0,1/2,0 1/2,0,1/2 0,0,1/2 x,0,0 x,0,1/2 0,y,0 0,y,1/2 0,0,z 0,1/2,z 1/4,1/4,z",
+    // 22
+    "0,0,0 0,0,1/2 1/4,1/4,1/4 1/4,1/4,3/4 x,0,0 0,y,0 0,0,z 1/4,1/4,z 1/4,y,1/4 x,1/4,1/4",
+    // 23
+    "0,0,0 1/2,0,0 0,0,1/2 0,1/2,0 x,0,0 x,0,1/2 0,y,0 1/2,y,0 0,0,z 1/2,0,z",
+    // 24
+    "x,0,1/4 1/4,y,0 0,1/4,z",
+    // 25
+    "0,0,z 0,1/2,z 1/2,0,z 1/2,1/2,z x,0,z x,1/2,z 0,y,z 1/2,y,z",
+    // 26
+    "0,y,z 1/2,y,z",
+    // 27
+    "0,0,z 0,1/2,z 1/2,0,z 1/2,1/2,z",
+    // 28
+    "0,0,z 0,1/2,z 1/4,y,z",
+    // 29
+    "",
+    // 30
+    "0,0,z 1/2,0,z",
+    // 31
+    "0,y,z",
+    // 32
+    "0,0,z 0,1/2,z",
+    // 33
+    "",
+    // 34
+    "0,0,z 0,1/2,z",
+    // 35
+    "0,0,z 0,1/2,z 1/4,1/4,z x,0,z 0,y,z",
+    // 36
+    "0,y,z",
+    // 37
+    "0,0,z 0,1/2,z 1/4,1/4,z",
+    // 38
+    "0,0,z 1/2,0,z x,0,z 0,y,z 1/2,y,z",
+    // 39
+    "0,0,z 1/2,0,z x,1/4,z",
+    // 40
+    "0,0,z 1/4,y,z",
+    // 41
+    "0,0,z",
+    // 42
+    "0,0,z 1/4,1/4,z 0,y,z x,0,z",
+    // 43
+    "0,0,z",
+    // 44
+    "0,0,z 0,1/2,z x,0,z 0,y,z",
+    // 45
+    "0,0,z 1/2,0,z",
+    // 46
+    "0,0,z 1/4,y,z",
+    // 47
+    "0,0,0 1/2,0,0 0,0,1/2 1/2,0,1/2 0,1/2,0 1/2,1/2,0 0,1/2,1/2 1/2,1/2,1/2 x,0,0 x,0,1/2 x,1/2,0 x,1/2,1/2 0,y,0 0,y,1/2 1/2,y,0 1/2,y,1/2 0,0,z 0,1/2,z 1/2,0,z 1/2,1/2,z 0,y,z 1/2,y,z x,0,z x,1/2,z x,y,0 x,y,1/2",
+    // 48
+    "0,0,0 1/2,0,0 0,0,1/2 0,1/2,0 1/4,1/4,1/4 3/4,3/4,3/4 x,0,0 x,0,1/2 0,y,0 1/2,y,0 0,0,z 1/2,0,z",
+    // 49
+    "0,0,0 1/2,1/2,0 0,1/2,0 1/2,0,0 0,0,1/4 1/2,0,1/4 0,1/2,1/4 1/2,1/2,1/4 x,0,1/4 x,1/2,1/4 0,y,1/4 1/2,y,1/4 0,0,z 1/2,1/2,z 0,1/2,z 1/2,0,z x,y,0",
+    // 50
+    "0,0,0 1/2,0,0 1/2,0,1/2 0,0,1/2 1/4,1/4,0 1/4,1/4,1/2 x,0,0 x,0,1/2 0,y,0 0,y,1/2 0,0,z 0,1/2,z",
+    // 51
+    "0,0,0 0,1/2,0 0,0,1/2 0,1/2,1/2 1/4,0,z 1/4,1/2,z 0,y,0 0,y,1/2 x,0,z x,1/2,z 1/4,y,z",
+    // 52
+    "0,0,0 0,0,1/2 1/4,0,z x,1/4,1/4",
+    // 53
+    "0,0,0 1/2,0,0 1/2,1/2,0 0,1/2,0 x,0,0 x,1/2,0 1/4,y,1/4 0,y,z",
+    // 54
+    "0,0,0 0,1/2,0 0,y,1/4 1/4,0,z 1/4,1/2,z",
+    // 55
+    "0,0,0 0,0,1/2 0,1/2,0 0,1/2,1/2 0,0,z 0,1/2,z x,y,0 x,y,1/2",
+    // 56
+    "0,0,0 0,0,1/2 1/4,1/4,z 1/4,3/4,z",
+    // 57
+    "0,0,0 1/2,0,0 x,1/4,0 x,y,1/4",
+    // 58
+    "0,0,0 0,0,1/2 0,1/2,0 0,1/2,1/2 0,0,z 0,1/2,z x,y,0",
+    // 59
+    "0,0,z 0,1/2,z 1/4,1/4,0 1/4,1/4,1/2 0,y,z x,0,z",
+    // 60
+    "0,0,0 0,1/2,0 0,y,1/4",
+    // 61
+    "0,0,0 0,0,1/2",
+    // 62
+    "0,0,0 0,0,1/2 x,1/4,z",
+    // 63
+    "0,0,0 0,1/2,0 0,y,1/4 1/4,1/4,0 x,0,0 0,y,z x,y,1/4",
+    // 64
+    "0,0,0 1/2,0,0 1/4,1/4,0 x,0,0 1/4,y,1/4 0,y,z",
+    // 65
+    "0,0,0 1/2,0,0 1/2,0,1/2 0,0,1/2 1/4,1/4,0 1/4,1/4,1/2 x,0,0 x,0,1/2 0,y,0 0,y,1/2 0,0,z 0,1/2,z 1/4,1/4,z 0,y,z x,0,z x,y,0 x,y,1/2",
+    // 66
+    "0,0,1/4 0,1/2,1/4 0,0,0 0,1/2,0 1/4,1/4,0 1/4,3/4,0 x,0,1/4 0,y,1/4 0,0,z 0,1/2,z 1/4,1/4,z x,y,0",
+    // 67
+    "1/4,0,0 1/4,0,1/2 0,0,0 0,0,1/2 1/4,1/4,0 1/4,1/4,1/2 0,1/4,z x,0,0 x,0,1/2 1/4,y,0 1/4,y,1/2 1/4,0,z 0,y,z x,1/4,z",
+    // 68
+    "0,0,0 0,0,1/2 1/4,0,1/4 0,1/4,1/4 x,0,0 0,y,0 0,0,z 1/4,1/4,z",
+    // 69
+    "0,0,0 0,0,1/2 0,1/4,1/4 1/4,0,1/4 1/4,1/4,0 1/4,1/4,1/4 x,0,0 0,y,0 0,0,z 1/4,1/4,z 1/4,y,1/4 x,1/4,1/4 0,y,z x,0,z x,y,0",
+    // 70
+    "0,0,0 0,0,1/2 1/8,1/8,1/8 5/8,5/8,5/8 x,0,0 0,y,0 0,0,z",
+    // 71
+    "0,0,0 0,1/2,1/2 1/2,1/2,0 1/2,0,1/2 x,0,0 x,1/2,0 0,y,0 0,y,1/2 0,0,z 1/2,0,z 1/4,1/4,1/4 0,y,z x,0,z x,y,0",
+    // 72
+    "0,0,1/4 1/2,0,1/4 0,0,0 1/2,0,0 1/4,1/4,1/4 x,0,1/4 0,y,1/4 0,0,z 0,1/2,z x,y,0",
+    // 73
+    "0,0,0 1/4,1/4,1/4 x,0,1/4 1/4,y,0 0,1/4,z",
+    // 74
+    "0,0,0 0,0,1/2 1/4,1/4,1/4 1/4,1/4,3/4 0,1/4,z x,0,0 1/4,y,1/4 0,y,z x,1/4,z",
+    // 75
+    "0,0,z 1/2,1/2,z 0,1/2,z",
+    // 76
+    "",
+    // 77
+    "0,0,z 1/2,1/2,z 0,1/2,z",
+    // 78
+    "",
+    // 79
+    "0,0,z 0,1/2,z",
+    // 80
+    "0,0,z",
+    // 81
+    "0,0,0 0,0,1/2 1/2,1/2,0 1/2,1/2,1/2 0,0,z 1/2,1/2,z 0,1/2,z",
+    // 82
+    "0,0,0 0,0,1/2 0,1/2,1/4 0,1/2,3/4 0,0,z 0,1/2,z",
+    // 83
+    "0,0,0 0,0,1/2 1/2,1/2,0 1/2,1/2,1/2 0,1/2,1/2 0,1/2,0 0,0,z 1/2,1/2,z 0,1/2,z x,y,0 x,y,1/2",
+    // 84
+    "0,0,0 1/2,1/2,0 0,1/2,0 0,1/2,1/2 0,0,1/4 1/2,1/2,1/4 0,0,z 1/2,1/2,z 0,1/2,z x,y,0",
+    // 85
+    "0,0,0 0,0,1/2 0,1/2,z 1/4,1/4,0 1/4,1/4,1/2 0,0,z",
+    // 86
+    "0,0,0 0,0,1/2 1/4,1/4,1/4 1/4,1/4,3/4 0,0,z 0,1/2,z",
+    // 87
+    "0,0,0 0,0,1/2 0,1/2,0 0,1/2,1/4 0,0,z 1/4,1/4,1/4 0,1/2,z x,y,0",
+    // 88
+    "0,0,0 0,0,1/2 0,1/4,1/8 0,1/4,5/8 0,0,z",
+    // 89
+    "0,0,0 0,0,1/2 1/2,1/2,0 1/2,1/2,1/2 0,1/2,1/2 0,1/2,0 0,0,z 1/2,1/2,z 0,1/2,z x,x,0 x,x,1/2 x,0,0 x,0,1/2 x,1/2,0 x,1/2,1/2",
+    // 90
+    "0,0,0 0,0,1/2 0,1/2,z 0,0,z x,x,0 x,x,1/2",
+    // 91
+    "0,y,0 1/2,y,0 x,x,3/8",
+    // 92
+    "x,x,0",
+    // 93
+    "0,0,0 1/2,1/2,0 0,1/2,0 0,1/2,1/2 0,0,1/4 1/2,1/2,1/4 0,0,z 1/2,1/2,z 0,1/2,z x,0,0 x,1/2,1/2 x,0,1/2 x,1/2,0 x,x,1/4 x,x,3/4",
+    // 94
+    "0,0,0 0,0,1/2 0,0,z 0,1/2,z x,x,0 x,x,1/2",
+    // 95
+    "0,y,0 1/2,y,0 x,x,5/8",
+    // 96
+    "x,x,0",
+    // 97
+    "0,0,0 0,0,1/2 0,1/2,0 0,1/2,1/4 0,0,z 0,1/2,z x,0,0 x,x,0 x,0,1/2 x,x+1/2,1/4",
+    // 98
+    "0,0,0 0,0,1/2 0,0,z x,x,0 -x,x,0 x,1/4,1/8",
+    // 99
+    "0,0,z 1/2,1/2,z 1/2,0,z x,x,z x,0,z x,1/2,z",
+    // 100
+    "0,0,z 1/2,0,z x,x+1/2,z",
+    // 101
+    "0,0,z 1/2,1/2,z 0,1/2,z x,x,z",
+    // 102
+    "0,0,z 0,1/2,z x,x,z",
+    // 103
+    "0,0,z 1/2,1/2,z 0,1/2,z",
+    // 104
+    "0,0,z 0,1/2,z",
+    // 105
+    "0,0,z 1/2,1/2,z 0,1/2,z x,0,z x,1/2,z",
+    // 106
+    "0,0,z 0,1/2,z",
+    // 107
+    "0,0,z 0,1/2,z x,x,z x,0,z",
+    // 108
+    "0,0,z 1/2,0,z x,x+1/2,z",
+    // 109
+    "0,0,z 0,y,z",
+    // 110
+    "0,0,z",
+    // 111
+    "0,0,0 1/2,1/2,1/2 0,0,1/2 1/2,1/2,0 1/2,0,0 1/2,0,1/2 0,0,z 1/2,1/2,z x,0,0 x,1/2,1/2 x,0,1/2 x,1/2,0 0,1/2,z x,x,z",
+    // 112
+    "0,0,1/4 1/2,0,1/4 1/2,1/2,1/4 0,1/2,1/4 0,0,0 1/2,1/2,0 x,0,1/4 1/2,y,1/4 x,1/2,1/4 0,y,1/4 0,0,z 1/2,1/2,z 0,1/2,z",
+    // 113
+    "0,0,0 0,0,1/2 0,1/2,z 0,0,z x,x+1/2,z",
+    // 114
+    "0,0,0 0,0,1/2 0,0,z 0,1/2,z",
+    // 115
+    "0,0,0 1/2,1/2,0 1/2,1/2,1/2 0,0,1/2 0,0,z 1/2,1/2,z 0,1/2,z x,x,0 x,x,1/2 x,0,z x,1/2,z",
+    // 116
+    "0,0,1/4 1/2,1/2,1/4 0,0,0 1/2,1/2,0 x,x,1/4 x,x,3/4 0,0,z 1/2,1/2,z 0,1/2,z",
+    // 117
+    "0,0,0 0,0,1/2 0,1/2,0 0,1/2,1/2 0,0,z 0,1/2,z x,x+1/2,0 x,x+1/2,1/2",
+    // 118
+    "0,0,0 0,0,1/2 0,1/2,1/4 0,1/2,3/4 0,0,z 0,1/2,z x,-x+1/2,1/4 x,x+1/2,1/4",
+    // 119
+    "0,0,0 0,0,1/2 0,1/2,1/4 0,1/2,3/4 0,0,z 0,1/2,z x,x,0 x,x+1/2,1/4 x,0,z",
+    // 120
+    "0,0,1/4 0,0,0 0,1/2,1/4 0,1/2,0 x,x,1/4 x,x+1/2,0 0,0,z 0,1/2,z",
+    // 121
+    "0,0,0 0,0,1/2 0,1/2,0 0,1/2,1/4 0,0,z x,0,0 x,0,1/2 0,1/2,z x,x,z",
+    // 122
+    "0,0,0 0,0,1/2 0,0,z x,1/4,1/8",
+    // 123
+    "0,0,0 0,0,1/2 1/2,1/2,0 1/2,1/2,1/2 0,1/2,1/2 0,1/2,0 0,0,z 1/2,1/2,z 0,1/2,z x,x,0 x,x,1/2 x,0,0 x,0,1/2 x,1/2,0 x,1/2,1/2 x,y,0 x,y,1/2 x,x,z x,0,z x,1/2,z",
+    // 124
+    "0,0,1/4 0,0,0 1/2,1/2,1/4 1/2,1/2,0 0,1/2,0 0,1/2,1/4 0,0,z 1/2,1/2,z 0,1/2,z x,0,1/4 x,1/2,1/4 x,x,1/4 x,y,0",
+    // 125
+    "0,0,0 0,0,1/2 0,1/2,1/2 0,1/2,0 1/4,1/4,0 1/4,1/4,1/2 0,0,z 0,1/2,z x,0,0 x,0,1/2 x,x,0 x,x,1/2 x,-x+1/2,z",
+    // 126
+    "0,0,0 0,0,1/2 0,1/2,0 0,1/2,1/4 0,0,z 1/4,1/4,1/4 0,1/2,z x,0,0 x,0,1/2 x,x,0",
+    // 127
+    "0,0,0 0,0,1/2 0,1/2,1/2 0,1/2,0 0,0,z 0,1/2,z x,x+1/2,0 x,x+1/2,1/2 x,y,0 x,y,1/2 x,x+1/2,z",
+    // 128
+    "0,0,0 0,0,1/2 0,1/2,0 0,1/2,1/4 0,0,z 0,1/2,z x,x+1/2,1/4 x,y,0",
+    // 129
+    "0,0,0 0,0,1/2 0,1/2,z 1/4,1/4,0 1/4,1/4,1/2 0,0,z x,x,0 x,x,1/2 x,0,z x,x+1/2,z",
+    // 130
+    "0,0,0 0,0,1/4 0,1/2,z 1/4,1/4,0 0,0,z x,x,1/4",
+    // 131
+    "0,0,0 1/2,1/2,0 0,1/2,0 0,1/2,1/2 0,0,1/4 1/2,1/2,1/4 0,0,z 1/2,1/2,z 0,1/2,z x,0,0 x,1/2,1/2 x,0,1/2 x,1/2,0 x,x,1/4 0,y,z 1/2,y,z x,y,0",
+    // 132
+    "0,0,0 0,0,1/4 1/2,1/2,0 1/2,1/2,1/4 0,1/2,0 0,1/2,1/4 0,0,z 1/2,1/2,z x,x,0 x,x,1/2 x,0,1/4 x,1/2,1/4 0,1/2,z x,x,z x,y,0",
+    // 133
+    "0,0,0 0,0,1/4 0,1/2,0 0,1/2,1/4 1/4,1/4,1/4 x,0,1/4 x,0,3/4 x,x+1/2,0 0,0,z 0,1/2,z",
+    // 134
+    "0,0,0 0,0,1/2 0,1/2,0 0,1/2,1/4 1/4,1/4,1/4 1/4,1/4,3/4 0,0,z x,0,0 x,0,1/2 x,x+1/2,1/4 x,x+1/2,3/4 0,1/2,z x,x,z",
+    // 135
+    "0,0,0 0,0,1/4 0,1/2,0 0,1/2,1/4 0,0,z 0,1/2,z x,x+1/2,1/4 x,y,0",
+    // 136
+    "0,0,0 0,0,1/2 0,1/2,0 0,1/2,1/4 0,0,z x,x,0 x,-x,0 0,1/2,z x,y,0 x,x,z",
+    // 137
+    "0,0,0 0,0,1/2 0,0,z 0,1/2,z 1/4,1/4,1/4 x,x,0 x,0,z",
+    // 138
+    "0,0,0 0,0,1/4 1/4,1/4,1/4 1/4,1/4,3/4 0,1/2,z x,x,1/4 x,x,3/4 0,0,z x,x+1/2,z",
+    // 139
+    "0,0,0 0,0,1/2 0,1/2,0 0,1/2,1/4 0,0,z 1/4,1/4,1/4 0,1/2,z x,x,0 x,0,0 x,1/2,0 x,x+1/2,1/4 x,y,0 x,x,z 0,y,z",
+    // 140
+    "0,0,1/4 0,1/2,1/4 0,0,0 0,1/2,0 1/4,1/4,1/4 0,0,z 0,1/2,z x,x+1/2,0 x,0,1/4 x,x,1/4 x,y,0 x,x+1/2,z",
+    // 141
+    "0,0,0 0,0,1/2 0,1/4,1/8 1/4,0,3/8 0,0,z x,x,0 x,1/4,1/8 x,0,z",
+    // 142
+    "0,0,0 0,0,1/4 0,1/4,1/8 x,x,1/4 x,1/4,3/8 0,0,z",
+    // 143
+    "0,0,z 1/3,2/3,z 2/3,1/3,z",
+    // 144
+    "",
+    // 145
+    "",
+    // 146
+    "0,0,z",
+    // 147
+    "0,0,0 0,0,1/2 0,0,z 1/3,2/3,z 1/2,0,0 1/2,0,1/2",
+    // 148
+    "0,0,0 0,0,1/2 0,0,z 1/2,0,1/2 1/2,0,0",
+    // 149
+    "0,0,0 0,0,1/2 1/3,2/3,0 1/3,2/3,1/2 2/3,1/3,0 2/3,1/3,1/2 0,0,z 1/3,2/3,z 2/3,1/3,z x,-x,0 x,-x,1/2",
+    // 150
+    "0,0,0 0,0,1/2 0,0,z 1/3,2/3,z x,0,0 x,0,1/2",
+    // 151
+    "x,-x,1/3 x,-x,5/6",
+    // 152
+    "x,0,1/3 x,0,5/6",
+    // 153
+    "x,-x,2/3 x,-x,1/6",
+    // 154
+    "x,0,2/3 x,0,1/6",
+    // 155
+    "0,0,0 0,0,1/2 0,0,z x,0,0 x,0,1/2",
+    // 156
+    "0,0,z 1/3,2/3,z 2/3,1/3,z x,-x,z",
+    // 157
+    "0,0,z 1/3,2/3,z x,0,z",
+    // 158
+    "0,0,z 1/3,2/3,z 2/3,1/3,z",
+    // 159
+    "0,0,z 1/3,2/3,z",
+    // 160
+    "0,0,z x,-x,z",
+    // 161
+    "0,0,z",
+    // 162
+    "0,0,0 0,0,1/2 1/3,2/3,0 1/3,2/3,1/2 0,0,z 1/2,0,0 1/2,0,1/2 1/3,2/3,z x,-x,0 x,-x,1/2 x,0,z",
+    // 163
+    "0,0,1/4 0,0,0 1/3,2/3,1/4 2/3,1/3,1/4 0,0,z 1/3,2/3,z 1/2,0,0 x,-x,1/4",
+    // 164
+    "0,0,0 0,0,1/2 0,0,z 1/3,2/3,z 1/2,0,0 1/2,0,1/2 x,0,0 x,0,1/2 x,-x,z",
+    // 165
+    "0,0,1/4 0,0,0 0,0,z 1/3,2/3,z 1/2,0,0 x,0,1/4",
+    // 166
+    "0,0,0 0,0,1/2 0,0,z 1/2,0,1/2 1/2,0,0 x,0,0 x,0,1/2 x,-x,z",
+    // 167
+    "0,0,1/4 0,0,0 0,0,z 1/2,0,0 x,0,1/4",
+    // 168
+    "0,0,z 1/3,2/3,z 1/2,0,z",
+    // 169
+    "",
+    // 170
+    "",
+    // 171
+    "0,0,z 1/2,1/2,z",
+    // 172
+    "0,0,z 1/2,1/2,z",
+    // 173
+    "0,0,z 1/3,2/3,z",
+    // 174
+    "0,0,0 0,0,1/2 1/3,2/3,0 1/3,2/3,1/2 2/3,1/3,0 2/3,1/3,1/2 0,0,z 1/3,2/3,z 2/3,1/3,z x,y,0 x,y,1/2",
+    // 175
+    "0,0,0 0,0,1/2 1/3,2/3,0 1/3,2/3,1/2 0,0,z 1/2,0,0 1/2,0,1/2 1/3,2/3,z 1/2,0,z x,y,0 x,y,1/2",
+    // 176
+    "0,0,1/4 0,0,0 1/3,2/3,1/4 2/3,1/3,1/4 0,0,z 1/3,2/3,z 1/2,0,0 x,y,1/4",
+    // 177
+    "0,0,0 0,0,1/2 1/3,2/3,0 1/3,2/3,1/2 0,0,z 1/2,0,0 1/2,0,1/2 1/3,2/3,z 1/2,0,z x,0,0 x,0,1/2 x,2x,0 x,2x,1/2",
+    // 178
+    "x,0,0 x,2x,1/4",
+    // 179
+    "x,0,0 x,2x,3/4",
+    // 180
+    "0,0,0 0,0,1/2 1/2,0,0 1/2,0,1/2 0,0,z 1/2,0,z x,0,0 x,0,1/2 x,2x,0 x,2x,1/2",
+    // 181
+    "0,0,0 0,0,1/2 1/2,0,0 1/2,0,1/2 0,0,z 1/2,0,z x,0,0 x,0,1/2 x,2x,0 x,2x,1/2",
+    // 182
+    "0,0,0 0,0,1/4 1/3,2/3,1/4 1/3,2/3,3/4 0,0,z 1/3,2/3,z x,0,0 x,2x,1/4",
+    // 183
+    "0,0,z 1/3,2/3,z 1/2,0,z x,-x,z x,0,z",
+    // 184
+    "0,0,z 1/3,2/3,z 1/2,0,z",
+    // 185
+    "0,0,z 1/3,2/3,z x,0,z",
+    // 186
+    "0,0,z 1/3,2/3,z x,-x,z",
+    // 187
+    "0,0,0 0,0,1/2 1/3,2/3,0 1/3,2/3,1/2 2/3,1/3,0 2/3,1/3,1/2 0,0,z 1/3,2/3,z 2/3,1/3,z x,-x,0 x,-x,1/2 x,y,0 x,y,1/2 x,-x,z",
+    // 188
+    "0,0,0 0,0,1/4 1/3,2/3,0 1/3,2/3,1/4 2/3,1/3,0 2/3,1/3,1/4 0,0,z 1/3,2/3,z 2/3,1/3,z x,-x,0 x,y,1/4",
+    // 189
+    "0,0,0 0,0,1/2 1/3,2/3,0 1/3,2/3,1/2 0,0,z x,0,0 x,0,1/2 1/3,2/3,z x,0,z x,y,0 x,y,1/2",
+    // 190
+    "0,0,0 0,0,1/4 1/3,2/3,1/4 2/3,1/3,1/4 0,0,z 1/3,2/3,z x,0,0 x,y,1/4",
+    // 191
+    "0,0,0 0,0,1/2 1/3,2/3,0 1/3,2/3,1/2 0,0,z 1/2,0,0 1/2,0,1/2 1/3,2/3,z 1/2,0,z x,0,0 x,0,1/2 x,2x,0 x,2x,1/2 x,0,z x,2x,z x,y,0 x,y,1/2",
+    // 192
+    "0,0,1/4 0,0,0 1/3,2/3,1/4 1/3,2/3,0 0,0,z 1/2,0,1/4 1/2,0,0 1/3,2/3,z 1/2,0,z x,0,1/4 x,2x,1/4 x,y,0",
+    // 193
+    "0,0,1/4 0,0,0 1/3,2/3,1/4 1/3,2/3,0 0,0,z 1/2,0,0 x,0,1/4 1/3,2/3,z x,2x,0 x,y,1/4 x,0,z",
+    // 194
+    "0,0,0 0,0,1/4 1/3,2/3,1/4 1/3,2/3,3/4 0,0,z 1/3,2/3,z 1/2,0,0 x,2x,1/4 x,0,0 x,y,1/4 x,2x,z",
+    // 195
+    "0,0,0 1/2,1/2,1/2 0,1/2,1/2 1/2,0,0 x,x,x x,0,0 x,0,1/2 x,1/2,0 x,1/2,1/2",
+    // 196
+    "0,0,0 1/2,1/2,1/2 1/4,1/4,1/4 3/4,3/4,3/4 x,x,x x,0,0 x,1/4,1/4",
+    // 197
+    "0,0,0 0,1/2,1/2 x,x,x x,0,0 x,1/2,0",
+    // 198
+    "x,x,x",
+    // 199
+    "x,x,x x,0,1/4",
+    // 200
+    "0,0,0 1/2,1/2,1/2 0,1/2,1/2 1/2,0,0 x,0,0 x,0,1/2 x,1/2,0 x,1/2,1/2 x,x,x 0,y,z 1/2,y,z",
+    // 201
+    "0,0,0 1/4,1/4,1/4 3/4,3/4,3/4 0,1/2,1/2 x,x,x x,0,0 x,1/2,0",
+    // 202
+    "0,0,0 1/2,1/2,1/2 1/4,1/4,1/4 0,1/4,1/4 x,0,0 x,x,x x,1/4,1/4 0,y,z",
+    // 203
+    "0,0,0 1/2,1/2,1/2 1/8,1/8,1/8 5/8,5/8,5/8 x,x,x x,0,0",
+    // 204
+    "0,0,0 0,1/2,1/2 1/4,1/4,1/4 x,0,0 x,0,1/2 x,x,x 0,y,z",
+    // 205
+    "0,0,0 1/2,1/2,1/2 x,x,x",
+    // 206
+    "1/4,1/4,1/4 0,0,0 x,x,x x,0,1/4",
+    // 207
+    "0,0,0 1/2,1/2,1/2 0,1/2,1/2 1/2,0,0 x,0,0 x,1/2,1/2 x,x,x x,1/2,0 0,y,y 1/2,y,y",
+    // 208
+    "0,0,0 1/4,1/4,1/4 3/4,3/4,3/4 0,1/2,1/2 1/4,0,1/2 1/4,1/2,0 x,x,x x,0,0 x,0,1/2 x,1/2,0 1/4,y,-y+1/2 1/4,y,y+1/2",
+    // 209
+    "0,0,0 1/2,1/2,1/2 1/4,1/4,1/4 0,1/4,1/4 x,0,0 x,x,x x,1/4,1/4 0,y,y 1/2,y,y",
+    // 210
+    "0,0,0 1/2,1/2,1/2 1/8,1/8,1/8 5/8,5/8,5/8 x,x,x x,0,0 1/8,y,-y+1/4",
+    // 211
+    "0,0,0 0,1/2,1/2 1/4,1/4,1/4 1/4,1/2,0 x,0,0 x,x,x x,1/2,0 0,y,y 1/4,y,-y+1/2",
+    // 212
+    "1/8,1/8,1/8 5/8,5/8,5/8 x,x,x 1/8,y,-y+1/4",
+    // 213
+    "3/8,3/8,3/8 7/8,7/8,7/8 x,x,x 1/8,y,y+1/4",
+    // 214
+    "1/8,1/8,1/8 7/8,7/8,7/8 1/8,0,1/4 5/8,0,1/4 x,x,x x,0,1/4 1/8,y,y+1/4 1/8,y,-y+1/4",
+    // 215
+    "0,0,0 1/2,1/2,1/2 0,1/2,1/2 1/2,0,0 x,x,x x,0,0 x,1/2,1/2 x,1/2,0 x,x,z",
+    // 216
+    "0,0,0 1/2,1/2,1/2 1/4,1/4,1/4 3/4,3/4,3/4 x,x,x x,0,0 x,1/4,1/4 x,x,z",
+    // 217
+    "0,0,0 0,1/2,1/2 x,x,x 1/4,1/2,0 x,0,0 x,1/2,0 x,x,z",
+    // 218
+    "0,0,0 0,1/2,1/2 1/4,1/2,0 1/4,0,1/2 x,x,x x,0,0 x,1/2,0 x,0,1/2",
+    // 219
+    "0,0,0 1/4,1/4,1/4 1/4,0,0 0,1/4,1/4 x,x,x x,0,0 x,1/4,1/4",
+    // 220
+    "3/8,0,1/4 7/8,0,1/4 x,x,x x,0,1/4",
+    // 221
+    "0,0,0 1/2,1/2,1/2 0,1/2,1/2 1/2,0,0 x,0,0 x,1/2,1/2 x,x,x x,1/2,0 0,y,y 1/2,y,y 0,y,z 1/2,y,z x,x,z",
+    // 222
+    "0,0,0 0,1/2,1/2 1/4,1/4,1/4 1/4,0,1/2 x,0,0 x,x,x x,1/2,0 0,y,y",
+    // 223
+    "0,0,0 0,1/2,1/2 1/4,0,1/2 1/4,1/2,0 1/4,1/4,1/4 x,0,0 x,0,1/2 x,1/2,0 x,x,x 1/4,y,y+1/2 0,y,z",
+    // 224
+    "0,0,0 1/4,1/4,1/4 3/4,3/4,3/4 0,1/2,1/2 x,x,x 0,1/4,1/2 x,0,0 x,0,1/2 x,1/4,x+1/2 x,x+1/2,3/4 x,y,y",
+    // 225
+    "0,0,0 1/2,1/2,1/2 1/4,1/4,1/4 0,1/4,1/4 x,0,0 x,x,x x,1/4,1/4 0,y,y 1/2,y,y 0,y,z x,x,z",
+    // 226
+    "1/4,1/4,1/4 0,0,0 1/4,0,0 0,1/4,1/4 x,0,0 x,1/4,1/4 x,x,x 1/4,y,y 0,y,z",
+    // 227
+    "0,0,0 1/2,1/2,1/2 1/8,1/8,1/8 5/8,5/8,5/8 x,x,x x,0,0 x,x,z x,x+1/4,3/8",
+    // 228
+    "0,0,0 1/8,1/8,1/8 1/8,1/8,3/8 0,0,1/4 x,x,x x,0,0 x,x+1/4,3/8",
+    // 229
+    "0,0,0 0,1/2,1/2 1/4,1/4,1/4 1/4,0,1/2 x,0,0 x,x,x x,0,1/2 0,y,y 1/4,y,-y+1/2 0,y,z x,x,z",
+    // 230
+    "0,0,0 1/8,1/8,1/8 1/8,0,1/4 3/8,0,1/4 x,x,x x,0,1/4 1/8,y,-y+1/4",
+];
+
+/// The tabulated representatives of the special positions of the space group,
+/// from the position `a`
+pub(crate) fn tabulated_representatives(number: u8) -> Vec<CoordinateTriplet> {
+    WYCKOFF_REPRESENTATIVES[number as usize - 1]
+        .split_whitespace()
+        .map(|input| {
+            let rows = coordinate_rows
+                .parse(input)
+                .expect("Tabulated representatives are valid coordinate triplets");
+            CoordinateTriplet::new([0, 1, 2].map(|i| rows[i].1), [0, 1, 2].map(|i| rows[i].0))
+        })
+        .collect()
+}